@@ -1,122 +1,201 @@
-//! Calendar Runner - Background thread that ticks the simulation at regular intervals
-
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
-use std::thread::{self, JoinHandle};
-use std::time::Duration;
-use crate::world::{SimulationWorld, TickResult};
-
-/// Calendar runner that manages a background thread for automatic ticking
-pub struct CalendarRunner {
-    is_running: Arc<AtomicBool>,
-    thread_handle: Option<JoinHandle<()>>,
-}
-
-impl CalendarRunner {
-    pub fn new() -> Self {
-        Self {
-            is_running: Arc::new(AtomicBool::new(false)),
-            thread_handle: None,
-        }
-    }
-
-    /// Start the calendar ticking at the specified interval
-    ///
-    /// # Arguments
-    /// * `world` - Shared reference to the simulation world
-    /// * `interval_ms` - Milliseconds between ticks (e.g., 1000 for daily speed, 125 for monthly)
-    /// * `callback` - Function to call with tick results (for broadcasting to Node.js)
-    pub fn start<F>(
-        &mut self,
-        world: Arc<Mutex<SimulationWorld>>,
-        interval_ms: u64,
-        callback: F,
-    ) where
-        F: Fn(TickResult) + Send + 'static,
-    {
-        // Don't start if already running
-        if self.is_running.load(Ordering::Relaxed) {
-            eprintln!("[WARN] Calendar runner already running");
-            return;
-        }
-
-        println!("[INFO] Starting Rust calendar runner ({}ms intervals)", interval_ms);
-        self.is_running.store(true, Ordering::Relaxed);
-        let running = Arc::clone(&self.is_running);
-
-        let handle = thread::spawn(move || {
-            while running.load(Ordering::Relaxed) {
-                // Execute tick
-                let tick_result = {
-                    let mut w = world.lock().unwrap();
-                    w.tick()
-                };
-
-                // Call the callback with results
-                callback(tick_result);
-
-                // Sleep until next tick
-                thread::sleep(Duration::from_millis(interval_ms));
-            }
-            println!("[INFO] Calendar runner thread stopped");
-        });
-
-        self.thread_handle = Some(handle);
-    }
-
-    /// Stop the calendar ticking
-    pub fn stop(&mut self) {
-        if !self.is_running.load(Ordering::Relaxed) {
-            return;
-        }
-
-        println!("[INFO] Stopping Rust calendar runner...");
-        self.is_running.store(false, Ordering::Relaxed);
-
-        // Wait for thread to finish
-        if let Some(handle) = self.thread_handle.take() {
-            let _ = handle.join(); // Thread panic result intentionally ignored during shutdown
-        }
-    }
-
-    /// Check if the calendar is currently running
-    pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::Relaxed)
-    }
-}
-
-impl Drop for CalendarRunner {
-    fn drop(&mut self) {
-        self.stop();
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::atomic::AtomicU32;
-
-    #[test]
-    fn test_calendar_runner() {
-        let world = Arc::new(Mutex::new(SimulationWorld::new()));
-        world.lock().unwrap().seed_population(10);
-
-        let tick_count = Arc::new(AtomicU32::new(0));
-        let tick_count_clone = Arc::clone(&tick_count);
-
-        let mut runner = CalendarRunner::new();
-        runner.start(
-            Arc::clone(&world),
-            100, // 100ms between ticks
-            move |_result| {
-                tick_count_clone.fetch_add(1, Ordering::Relaxed);
-            },
-        );
-
-        // Let it run for ~500ms (should get ~5 ticks)
-        thread::sleep(Duration::from_millis(550));
-        runner.stop();
-
-        let count = tick_count.load(Ordering::Relaxed);
-        assert!(count >= 4 && count <= 6, "Expected ~5 ticks, got {}", count);
-    }
-}
+//! Calendar Runner - Background thread that ticks the simulation at regular intervals
+
+use std::sync::{Arc, RwLock, atomic::{AtomicBool, Ordering}};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::world::{SimulationWorld, TickResult};
+
+/// Calendar runner that manages a background thread for automatic ticking
+pub struct CalendarRunner {
+    is_running: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl CalendarRunner {
+    pub fn new() -> Self {
+        Self {
+            is_running: Arc::new(AtomicBool::new(false)),
+            thread_handle: None,
+        }
+    }
+
+    /// Start the calendar ticking at the specified interval
+    ///
+    /// # Arguments
+    /// * `world` - Shared reference to the simulation world
+    /// * `interval_ms` - Milliseconds between ticks (e.g., 1000 for daily speed, 125 for monthly)
+    /// * `callback` - Function to call with tick results (for broadcasting to Node.js)
+    ///
+    /// Returns false (and leaves the existing thread alone) if already running.
+    pub fn start<F>(
+        &mut self,
+        world: Arc<RwLock<SimulationWorld>>,
+        interval_ms: u64,
+        callback: F,
+    ) -> bool
+    where
+        F: Fn(TickResult) + Send + 'static,
+    {
+        // Don't start if already running
+        if self.is_running.load(Ordering::Relaxed) {
+            eprintln!("[WARN] Calendar runner already running");
+            return false;
+        }
+
+        println!("[INFO] Starting Rust calendar runner ({}ms intervals)", interval_ms);
+        self.is_running.store(true, Ordering::Relaxed);
+        let running = Arc::clone(&self.is_running);
+
+        let handle = thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                // Execute tick
+                let tick_result = {
+                    let mut w = world.write().unwrap();
+                    w.tick()
+                };
+
+                // Call the callback with results
+                callback(tick_result);
+
+                // Sleep until next tick
+                thread::sleep(Duration::from_millis(interval_ms));
+            }
+            println!("[INFO] Calendar runner thread stopped");
+        });
+
+        self.thread_handle = Some(handle);
+        true
+    }
+
+    /// Stop the calendar ticking
+    pub fn stop(&mut self) {
+        if !self.is_running.load(Ordering::Relaxed) {
+            return;
+        }
+
+        println!("[INFO] Stopping Rust calendar runner...");
+        self.is_running.store(false, Ordering::Relaxed);
+
+        // Wait for thread to finish
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join(); // Thread panic result intentionally ignored during shutdown
+        }
+    }
+
+    /// Check if the calendar is currently running
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for CalendarRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CalendarRunner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Calendar;
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn test_calendar_runner() {
+        let world = Arc::new(RwLock::new(SimulationWorld::new()));
+        world.write().unwrap().seed_population(10);
+
+        let tick_count = Arc::new(AtomicU32::new(0));
+        let tick_count_clone = Arc::clone(&tick_count);
+
+        let mut runner = CalendarRunner::new();
+        runner.start(
+            Arc::clone(&world),
+            100, // 100ms between ticks
+            move |_result| {
+                tick_count_clone.fetch_add(1, Ordering::Relaxed);
+            },
+        );
+
+        // Let it run for ~500ms (should get ~5 ticks)
+        thread::sleep(Duration::from_millis(550));
+        runner.stop();
+
+        let count = tick_count.load(Ordering::Relaxed);
+        assert!((4..=6).contains(&count), "Expected ~5 ticks, got {}", count);
+    }
+
+    #[test]
+    fn test_second_start_keeps_single_thread() {
+        let world = Arc::new(RwLock::new(SimulationWorld::new()));
+        let first = Arc::new(AtomicU32::new(0));
+        let second = Arc::new(AtomicU32::new(0));
+
+        let mut runner = CalendarRunner::new();
+        let counter = Arc::clone(&first);
+        assert!(runner.start(Arc::clone(&world), 100, move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }));
+        let counter = Arc::clone(&second);
+        assert!(!runner.start(Arc::clone(&world), 100, move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }));
+
+        thread::sleep(Duration::from_millis(550));
+        runner.stop();
+        assert!(!runner.is_running());
+
+        // Only the first thread ever ticked, at the single-runner rate
+        let count = first.load(Ordering::Relaxed);
+        assert!((4..=6).contains(&count), "Expected ~5 ticks, got {}", count);
+        assert_eq!(second.load(Ordering::Relaxed), 0);
+        let elapsed = world.read().unwrap().days_since_start();
+        assert_eq!(elapsed, count as u64);
+
+        // Stopped threads are joined, so no further ticks arrive
+        thread::sleep(Duration::from_millis(250));
+        assert_eq!(first.load(Ordering::Relaxed), count);
+    }
+
+    #[test]
+    fn test_concurrent_readers_alongside_runner() {
+        let world = Arc::new(RwLock::new(SimulationWorld::with_seed(3)));
+        world.write().unwrap().seed_population(500);
+
+        let mut runner = CalendarRunner::new();
+        assert!(runner.start(Arc::clone(&world), 1, |_| {}));
+
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let world = Arc::clone(&world);
+                thread::spawn(move || {
+                    let mut last_day = 0;
+                    for _ in 0..200 {
+                        let w = world.read().unwrap();
+                        let day = w.calendar.to_absolute_days();
+                        assert!(day >= last_day);
+                        last_day = day;
+                        // A read guard never sees a tick half-applied
+                        let on_tiles: u32 = w.population_by_tile().values().sum();
+                        assert_eq!(on_tiles as usize, w.entity_count());
+                    }
+                    last_day
+                })
+            })
+            .collect();
+        for reader in readers {
+            assert!(reader.join().unwrap() >= Calendar::default().to_absolute_days());
+        }
+
+        // The writer was never starved out
+        thread::sleep(Duration::from_millis(20));
+        runner.stop();
+        assert!(world.read().unwrap().calendar.to_absolute_days() > Calendar::default().to_absolute_days());
+    }
+}
@@ -1,344 +1,723 @@
-//! ECS Components for GridWorld entities
-//!
-//! Add components here as you design the simulation.
-
-use serde::{Deserialize, Serialize};
-
-// ============================================================================
-// Identity Components
-// ============================================================================
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct PersonId(pub u64);
-
-/// Tile residency - which tile this person lives on
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct TileId(pub u16);
-
-// ============================================================================
-// Person Components
-// ============================================================================
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Person {
-    pub id: PersonId,
-    pub first_name: String,
-    pub last_name: String,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Sex {
-    Male,
-    Female,
-}
-
-/// Birth date using calendar year/month/day
-/// 4 bytes total (u16 + u8 + u8)
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct BirthDate {
-    pub year: u16,   // 0-65535 (plenty for year 4000+)
-    pub month: u8,   // 1-12
-    pub day: u8,     // 1-8 (custom calendar)
-}
-
-impl BirthDate {
-    /// Create from calendar
-    pub fn new(year: u16, month: u8, day: u8) -> Self {
-        Self { year, month, day }
-    }
-    
-    /// Create from age in years (for seeding)
-    pub fn from_age(age_years: u16, cal: &Calendar) -> Self {
-        Self {
-            year: cal.year.saturating_sub(age_years),
-            month: cal.month,
-            day: cal.day,
-        }
-    }
-    
-    /// Get age in years (approximate - ignores month/day)
-    pub fn age_years(&self, cal: &Calendar) -> u16 {
-        cal.year.saturating_sub(self.year)
-    }
-    
-    /// Get age in months (more precise)
-    pub fn age_months(&self, cal: &Calendar) -> u32 {
-        let years = cal.year.saturating_sub(self.year) as u32;
-        let months = cal.month as i32 - self.month as i32;
-        (years * 12).saturating_add_signed(months)
-    }
-    
-    /// Check if person can have children based on sex and age
-    pub fn can_have_children(&self, sex: Sex, cal: &Calendar) -> bool {
-        let years = self.age_years(cal);
-        match sex {
-            Sex::Female => (16..=33).contains(&years),
-            Sex::Male => (16..=65).contains(&years),
-        }
-    }
-}
-
-// ============================================================================
-// Relationship Components (simple model)
-// ============================================================================
-
-/// Partner/spouse - presence means married, absence means single
-#[derive(Debug, Clone, Copy)]
-pub struct Partner(pub hecs::Entity);
-
-/// Biological mother - tracks maternal lineage
-#[derive(Debug, Clone, Copy)]
-pub struct Mother(pub hecs::Entity);
-
-/// Pregnancy tracking - added when a woman becomes pregnant
-/// Gestation period: ~9 months (72 days in our calendar)
-#[derive(Debug, Clone, Copy)]
-pub struct Pregnant {
-    pub due_year: u16,
-    pub due_month: u8,
-}
-
-impl Pregnant {
-    /// Create a new pregnancy with due date ~9 months from now
-    pub fn new(cal: &Calendar) -> Self {
-        let mut due_month = cal.month + 9;
-        let mut due_year = cal.year;
-        if due_month > 12 {
-            due_month -= 12;
-            due_year += 1;
-        }
-        Self { due_year, due_month }
-    }
-    
-    /// Check if the baby is due (current date >= due date)
-    pub fn is_due(&self, cal: &Calendar) -> bool {
-        if cal.year > self.due_year {
-            return true;
-        }
-        if cal.year == self.due_year && cal.month >= self.due_month {
-            return true;
-        }
-        false
-    }
-}
-
-/// Fertility tracking for women
-/// Uses year/month for birth interval tracking (0 = never gave birth)
-#[derive(Debug, Clone, Copy, Default)]
-pub struct Fertility {
-    pub last_birth_year: u16,   // 0 = never
-    pub last_birth_month: u8,   // 1-12
-    pub children_born: u8,      // Total children (max 255)
-}
-
-impl Fertility {
-    /// Check if enough time has passed since last birth (18 months / 1.5 years minimum)
-    pub fn can_give_birth(&self, cal: &Calendar) -> bool {
-        if self.last_birth_year == 0 {
-            return true;  // Never gave birth
-        }
-        let months_since = (cal.year as i32 - self.last_birth_year as i32) * 12
-            + (cal.month as i32 - self.last_birth_month as i32);
-        months_since >= 18
-    }
-    
-    /// Get fertility reduction factor based on number of children
-    /// -10% per child, minimum 20% fertility
-    pub fn children_factor(&self) -> f64 {
-        (1.0 - self.children_born as f64 * 0.1).max(0.2)
-    }
-    
-    /// Record a birth
-    pub fn record_birth(&mut self, cal: &Calendar) {
-        self.last_birth_year = cal.year;
-        self.last_birth_month = cal.month;
-        self.children_born = self.children_born.saturating_add(1);
-    }
-}
-
-
-
-// ============================================================================
-// Calendar
-// ============================================================================
-
-/// Custom calendar: 8 days/month, 12 months/year
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Calendar {
-    pub year: u16,   // 0-65535
-    pub month: u8,   // 1-12
-    pub day: u8,     // 1-8
-}
-
-impl Default for Calendar {
-    fn default() -> Self {
-        Self { year: 4000, month: 1, day: 1 }
-    }
-}
-
-impl Calendar {
-    pub const DAYS_PER_MONTH: u8 = 8;
-    pub const MONTHS_PER_YEAR: u8 = 12;
-    pub const DAYS_PER_YEAR: u16 = 96;  // 8 * 12
-    
-    pub fn new(year: u16, month: u8, day: u8) -> Self {
-        Self { year, month, day }
-    }
-    
-    /// Advance by one day
-    pub fn advance(&mut self) {
-        self.day += 1;
-        if self.day > Self::DAYS_PER_MONTH {
-            self.day = 1;
-            self.month += 1;
-            if self.month > Self::MONTHS_PER_YEAR {
-                self.month = 1;
-                self.year += 1;
-            }
-        }
-    }
-}
-
-// ============================================================================
-// Event Log (Phase 2 - moved from Node.js)
-// ============================================================================
-
-use std::collections::VecDeque;
-
-/// Event types for tracking simulation history
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum EventType {
-    Birth,
-    Death,
-    Marriage,
-    PregnancyStarted,
-    Dissolution,
-}
-
-/// A single event in the simulation history
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Event {
-    pub event_type: EventType,
-    pub year: u16,
-    pub month: u8,
-    pub day: u8,
-    /// Optional person ID (for births, deaths)
-    pub person_id: Option<u64>,
-}
-
-impl Event {
-    pub fn new(event_type: EventType, calendar: &Calendar) -> Self {
-        Self {
-            event_type,
-            year: calendar.year,
-            month: calendar.month,
-            day: calendar.day,
-            person_id: None,
-        }
-    }
-
-    pub fn with_person(event_type: EventType, calendar: &Calendar, person_id: u64) -> Self {
-        Self {
-            event_type,
-            year: calendar.year,
-            month: calendar.month,
-            day: calendar.day,
-            person_id: Some(person_id),
-        }
-    }
-}
-
-/// Event log with circular buffer (configurable max size)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EventLog {
-    events: VecDeque<Event>,
-    max_size: usize,
-}
-
-impl EventLog {
-    pub fn new(max_size: usize) -> Self {
-        Self {
-            events: VecDeque::with_capacity(max_size),
-            max_size,
-        }
-    }
-
-    /// Add an event to the log
-    pub fn push(&mut self, event: Event) {
-        if self.events.len() >= self.max_size {
-            self.events.pop_front(); // Remove oldest
-        }
-        self.events.push_back(event);
-    }
-
-    /// Iterate all events (newest first), zero allocation
-    pub fn iter_all(&self) -> impl Iterator<Item = &Event> + '_ {
-        self.events.iter().rev()
-    }
-
-    /// Get all events (newest first)
-    pub fn get_all(&self) -> Vec<Event> {
-        self.iter_all().cloned().collect()
-    }
-
-    /// Iterate events by type (newest first), zero allocation
-    pub fn iter_by_type(&self, event_type: EventType) -> impl Iterator<Item = &Event> + '_ {
-        self.events.iter().filter(move |e| e.event_type == event_type).rev()
-    }
-
-    /// Get events filtered by type
-    pub fn get_by_type(&self, event_type: EventType) -> Vec<Event> {
-        self.iter_by_type(event_type).cloned().collect()
-    }
-
-    /// Iterate events within a date range (newest first), zero allocation
-    pub fn iter_by_date_range(&self, start_year: u16, end_year: u16) -> impl Iterator<Item = &Event> + '_ {
-        self.events.iter().filter(move |e| e.year >= start_year && e.year <= end_year).rev()
-    }
-
-    /// Get events within a date range (inclusive)
-    pub fn get_by_date_range(&self, start_year: u16, end_year: u16) -> Vec<Event> {
-        self.iter_by_date_range(start_year, end_year).cloned().collect()
-    }
-
-    /// Get recent events (last N events)
-    pub fn get_recent(&self, count: usize) -> Vec<Event> {
-        self.events
-            .iter()
-            .rev()
-            .take(count)
-            .cloned()
-            .collect()
-    }
-
-    /// Count events by type within a date range
-    pub fn count_by_type(&self, event_type: EventType, start_year: u16, end_year: u16) -> usize {
-        self.events
-            .iter()
-            .filter(|e| {
-                e.event_type == event_type && e.year >= start_year && e.year <= end_year
-            })
-            .count()
-    }
-
-    /// Clear all events
-    pub fn clear(&mut self) {
-        self.events.clear();
-    }
-
-    /// Get total event count
-    pub fn len(&self) -> usize {
-        self.events.len()
-    }
-
-    /// Check if log is empty
-    pub fn is_empty(&self) -> bool {
-        self.events.is_empty()
-    }
-}
-
-impl Default for EventLog {
-    fn default() -> Self {
-        Self::new(10000) // Default to 10k events (~10k ticks of history)
-    }
-}
+//! ECS Components for GridWorld entities
+//!
+//! Add components here as you design the simulation.
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Identity Components
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PersonId(pub u64);
+
+/// Tile residency - which tile this person lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TileId(pub u16);
+
+// ============================================================================
+// Person Components
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Person {
+    pub id: PersonId,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+#[cfg(test)]
+impl Person {
+    /// A person with no names, for tests that only need the id
+    pub(crate) fn unnamed(id: u64) -> Self {
+        Self { id: PersonId(id), first_name: String::new(), last_name: String::new() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+/// Birth date using calendar year/month/day
+/// 4 bytes total (u16 + u8 + u8)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BirthDate {
+    pub year: u16,   // 0-65535 (plenty for year 4000+)
+    pub month: u8,   // 1-12
+    pub day: u8,     // 1-8 (custom calendar)
+}
+
+impl BirthDate {
+    /// Create from calendar
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+    
+    /// Create from age in years (for seeding)
+    pub fn from_age(age_years: u16, cal: &Calendar) -> Self {
+        Self {
+            year: cal.year.saturating_sub(age_years),
+            month: cal.month,
+            day: cal.day,
+        }
+    }
+    
+    /// Get age in years (approximate - ignores month/day)
+    pub fn age_years(&self, cal: &Calendar) -> u16 {
+        cal.year.saturating_sub(self.year)
+    }
+    
+    /// Get age in months (more precise)
+    pub fn age_months(&self, cal: &Calendar) -> u32 {
+        let years = cal.year.saturating_sub(self.year) as u32;
+        let months = cal.month as i32 - self.month as i32;
+        (years * 12).saturating_add_signed(months)
+    }
+    
+    /// Get age in days, using the calendar's month and year lengths
+    pub fn age_days(&self, cal: &Calendar) -> u32 {
+        let born = Calendar::new(self.year, self.month, self.day).to_absolute_days();
+        cal.to_absolute_days().saturating_sub(born) as u32
+    }
+    
+    /// Youngest age at which anyone can have children
+    pub const MIN_FERTILE_AGE: u16 = 16;
+    /// Oldest age at which a woman can conceive
+    pub const MAX_FERTILE_AGE_FEMALE: u16 = 33;
+    /// Oldest age at which a man can father children
+    pub const MAX_FERTILE_AGE_MALE: u16 = 65;
+
+    /// Ages in years at which someone of `sex` can have children
+    pub fn fertile_ages(sex: Sex) -> std::ops::RangeInclusive<u16> {
+        match sex {
+            Sex::Female => Self::MIN_FERTILE_AGE..=Self::MAX_FERTILE_AGE_FEMALE,
+            Sex::Male => Self::MIN_FERTILE_AGE..=Self::MAX_FERTILE_AGE_MALE,
+        }
+    }
+
+    /// Check if person can have children based on sex and age
+    pub fn can_have_children(&self, sex: Sex, cal: &Calendar) -> bool {
+        Self::fertile_ages(sex).contains(&self.age_years(cal))
+    }
+}
+
+/// Economic role - assigned at birth/seeding and updated as people age
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Occupation {
+    Child,
+    Farmer,
+    Laborer,
+    Elder,
+}
+
+impl Occupation {
+    /// Age at which children join the workforce
+    pub const WORKING_AGE: u16 = 14;
+    /// Age at which workers retire
+    pub const ELDER_AGE: u16 = 65;
+
+    /// Occupation appropriate for an age; working-age people become farmers (60%) or laborers
+    pub fn for_age<R: rand::Rng>(years: u16, rng: &mut R) -> Self {
+        if years < Self::WORKING_AGE {
+            Occupation::Child
+        } else if years >= Self::ELDER_AGE {
+            Occupation::Elder
+        } else {
+            Self::random_worker(rng)
+        }
+    }
+
+    /// Pick a working occupation
+    pub fn random_worker<R: rand::Rng>(rng: &mut R) -> Self {
+        if rng.gen::<f64>() < 0.6 { Occupation::Farmer } else { Occupation::Laborer }
+    }
+}
+
+/// Individual health (0-100) - scales mortality risk, declines slowly with age
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Health(pub u8);
+
+impl Health {
+    pub const MAX: u8 = 100;
+    /// Health at which the mortality multiplier is exactly 1.0
+    pub const BASELINE: u8 = 80;
+
+    /// Health at birth/seeding: normally distributed around the baseline
+    pub fn random<R: rand::Rng>(rng: &mut R) -> Self {
+        use rand_distr::{Distribution, Normal};
+        let normal = Normal::new(Self::BASELINE as f64, 10.0).expect("valid normal params");
+        Self(normal.sample(rng).round().clamp(0.0, Self::MAX as f64) as u8)
+    }
+
+    /// Multiplier applied to the age-based death probability.
+    /// 100 health → 0.5x, baseline (80) → 1.0x, 40 → 2.0x, 0 → 3.0x
+    pub fn mortality_multiplier(&self) -> f64 {
+        (1.0 + (Self::BASELINE as f64 - self.0 as f64) / 40.0).max(0.5)
+    }
+}
+
+// ============================================================================
+// Relationship Components (simple model)
+// ============================================================================
+
+/// Partner/spouse - presence means married, absence means single.
+/// Holds the partner's entity and PersonId; the id stays meaningful after a despawn
+/// or a save/load, where the entity does not.
+#[derive(Debug, Clone, Copy)]
+pub struct Partner(pub hecs::Entity, pub PersonId);
+
+/// When the current partnership began - added alongside Partner at marriage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarriedSince {
+    pub year: u16,
+    pub month: u8,
+}
+
+impl MarriedSince {
+    pub fn new(cal: &Calendar) -> Self {
+        Self { year: cal.year, month: cal.month }
+    }
+
+    /// Whole months married as of `cal`
+    pub fn months_married(&self, cal: &Calendar) -> u32 {
+        let now = cal.year as u32 * Calendar::MONTHS_PER_YEAR as u32 + cal.month as u32;
+        let since = self.year as u32 * Calendar::MONTHS_PER_YEAR as u32 + self.month as u32;
+        now.saturating_sub(since)
+    }
+
+    /// Whole years married as of `cal`
+    pub fn years_married(&self, cal: &Calendar) -> u32 {
+        self.months_married(cal) / Calendar::MONTHS_PER_YEAR as u32
+    }
+}
+
+/// Biological mother - tracks maternal lineage. Entity and PersonId as for Partner;
+/// once she has died only the id identifies her (the entity may be `Entity::DANGLING`
+/// after a load).
+#[derive(Debug, Clone, Copy)]
+pub struct Mother(pub hecs::Entity, pub PersonId);
+
+/// Biological father - the mother's partner at the time of birth (see Mother)
+#[derive(Debug, Clone, Copy)]
+pub struct Father(pub hecs::Entity, pub PersonId);
+
+/// Generations since the founders: seeded people and immigrants are 0, and a child
+/// is one past the later of their parents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Generation(pub u16);
+
+/// Family grouping: a couple gets a new household when they marry, and children
+/// are born into their mother's. Members keep it until they marry into a new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Household(pub u64);
+
+/// Pregnancy tracking - added when a woman becomes pregnant
+/// Gestation period: ~9 months (72 days in our calendar)
+#[derive(Debug, Clone, Copy)]
+pub struct Pregnant {
+    pub due_year: u16,
+    pub due_month: u8,
+    /// Father's generation at conception, so the child's survives his death before
+    /// delivery (None = unknown; the mother's partner at delivery is used instead)
+    pub father_generation: Option<u16>,
+}
+
+impl Pregnant {
+    /// Standard gestation length in months
+    pub const GESTATION_MONTHS: u32 = 9;
+
+    /// Create a new pregnancy with due date ~9 months from now
+    pub fn new(cal: &Calendar) -> Self {
+        Self::with_gestation(cal, Self::GESTATION_MONTHS)
+    }
+    
+    /// Create a pregnancy due at the start of the month `months` from now, or in the
+    /// calendar's final month if that comes first
+    pub fn with_gestation(cal: &Calendar, months: u32) -> Self {
+        let months_per_year = Calendar::MONTHS_PER_YEAR as u32;
+        let last_month = Calendar::MAX_YEAR as u32 * months_per_year + months_per_year - 1;
+        let due = (cal.year as u32 * months_per_year + cal.month.saturating_sub(1) as u32 + months).min(last_month);
+        Self {
+            due_year: (due / months_per_year) as u16,
+            due_month: (due % months_per_year) as u8 + 1,
+            father_generation: None,
+        }
+    }
+    
+    /// Check if the baby is due (current date >= due date)
+    pub fn is_due(&self, cal: &Calendar) -> bool {
+        cal.to_absolute_days() >= Calendar::new(self.due_year, self.due_month, 1).to_absolute_days()
+    }
+}
+
+/// Fertility tracking for women
+/// Uses year/month for birth interval tracking (0 = never gave birth)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fertility {
+    pub last_birth_year: u16,   // 0 = never
+    pub last_birth_month: u8,   // 1-12
+    pub children_born: u8,      // Total children (saturates at MAX_CHILDREN)
+}
+
+impl Fertility {
+    /// Largest `children_born` can count; later births still happen but leave it there
+    pub const MAX_CHILDREN: u8 = u8::MAX;
+
+    /// Check if at least `min_interval_months` have passed since last birth
+    pub fn can_give_birth(&self, cal: &Calendar, min_interval_months: u32) -> bool {
+        if self.last_birth_year == 0 {
+            return true;  // Never gave birth
+        }
+        let months_since = (cal.year as i32 - self.last_birth_year as i32) * 12
+            + (cal.month as i32 - self.last_birth_month as i32);
+        months_since >= min_interval_months as i32
+    }
+    
+    /// Get fertility reduction factor based on number of children:
+    /// `penalty` less per child, but never below `floor`
+    pub fn children_factor(&self, penalty: f64, floor: f64) -> f64 {
+        (1.0 - self.children_born as f64 * penalty).max(floor)
+    }
+    
+    /// Record a live birth. Returns false if `children_born` was already at
+    /// `MAX_CHILDREN` and could not count it.
+    pub fn record_birth(&mut self, cal: &Calendar) -> bool {
+        self.record_delivery(cal);
+        if self.children_born == Self::MAX_CHILDREN {
+            return false;
+        }
+        self.children_born += 1;
+        true
+    }
+
+    /// Record a delivery with no living child: restarts the birth interval
+    /// without adding to `children_born`
+    pub fn record_delivery(&mut self, cal: &Calendar) {
+        self.last_birth_year = cal.year;
+        self.last_birth_month = cal.month;
+    }
+}
+
+
+
+// ============================================================================
+// Calendar
+// ============================================================================
+
+/// Custom calendar: 8 days/month, 12 months/year
+/// Ordering is chronological (year, then month, then day).
+/// Years are u16, so the latest supported date is `Calendar::last()` (year 65535);
+/// advancing past it leaves the calendar there instead of wrapping.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Calendar {
+    pub year: u16,   // 0-65535
+    pub month: u8,   // 1-12
+    pub day: u8,     // 1-8
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self { year: 4000, month: 1, day: 1 }
+    }
+}
+
+impl Calendar {
+    pub const DAYS_PER_MONTH: u8 = 8;
+    pub const MONTHS_PER_YEAR: u8 = 12;
+    pub const DAYS_PER_YEAR: u16 = 96;  // 8 * 12
+    pub const MAX_YEAR: u16 = u16::MAX;
+    
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// The final day the calendar can represent
+    pub fn last() -> Self {
+        Self { year: Self::MAX_YEAR, month: Self::MONTHS_PER_YEAR, day: Self::DAYS_PER_MONTH }
+    }
+
+    /// Whether the calendar has reached `Calendar::last()` and can no longer advance
+    pub fn is_last(&self) -> bool {
+        *self >= Self::last()
+    }
+    
+    /// Days elapsed since year 0, month 1, day 1
+    pub fn to_absolute_days(&self) -> u64 {
+        self.year as u64 * Self::DAYS_PER_YEAR as u64
+            + self.month.saturating_sub(1) as u64 * Self::DAYS_PER_MONTH as u64
+            + self.day.saturating_sub(1) as u64
+    }
+    
+    /// Inverse of `to_absolute_days` (days past `Calendar::last()` clamp to it)
+    pub fn from_absolute_days(days: u64) -> Self {
+        let days = days.min(Self::last().to_absolute_days());
+        let year = (days / Self::DAYS_PER_YEAR as u64) as u16;
+        let day_of_year = days % Self::DAYS_PER_YEAR as u64;
+        Self {
+            year,
+            month: (day_of_year / Self::DAYS_PER_MONTH as u64) as u8 + 1,
+            day: (day_of_year % Self::DAYS_PER_MONTH as u64) as u8 + 1,
+        }
+    }
+    
+    /// Advance by `days` days, stopping at `Calendar::last()`
+    pub fn advance_days(&mut self, days: u32) {
+        *self = Self::from_absolute_days(self.to_absolute_days() + days as u64);
+    }
+
+    /// Advance by one day, stopping at `Calendar::last()`
+    pub fn advance(&mut self) {
+        if self.is_last() {
+            return;
+        }
+        self.day += 1;
+        if self.day > Self::DAYS_PER_MONTH {
+            self.day = 1;
+            self.month += 1;
+            if self.month > Self::MONTHS_PER_YEAR {
+                self.month = 1;
+                self.year += 1;
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Event Log (Phase 2 - moved from Node.js)
+// ============================================================================
+
+use std::collections::VecDeque;
+
+/// Event types for tracking simulation history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventType {
+    Birth,
+    Death,
+    Marriage,
+    PregnancyStarted,
+    Dissolution,
+    Immigration,
+    Emigration,
+    /// Delivery without a living child (person_id = mother)
+    Stillbirth,
+    /// Population reached the world's `max_population`; conception is paused
+    CapacityReached,
+    /// The calendar reached `Calendar::last()`; later ticks stay on that day
+    CalendarExhausted,
+    /// Game-specific event (battle, festival, ...) named by `Event::label`
+    Custom,
+}
+
+/// What someone died of, recorded on `Death` events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathCause {
+    /// Age- and health-driven mortality from the mortality tables
+    Natural,
+    /// Mother lost in childbirth
+    Childbirth,
+}
+
+/// A single event in the simulation history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub event_type: EventType,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    /// Optional person ID (for births, deaths)
+    pub person_id: Option<u64>,
+    /// Tile it happened on (births, deaths, marriages and migration)
+    pub tile_id: Option<u16>,
+    /// What happened, for `EventType::Custom` events
+    #[serde(default)]
+    pub label: Option<String>,
+    /// What the person died of, for `Death` events
+    #[serde(default)]
+    pub cause: Option<DeathCause>,
+}
+
+impl Event {
+    /// Longest label a custom event may carry, in bytes
+    pub const MAX_LABEL_LEN: usize = 64;
+
+    pub fn new(event_type: EventType, calendar: &Calendar) -> Self {
+        Self {
+            event_type,
+            year: calendar.year,
+            month: calendar.month,
+            day: calendar.day,
+            person_id: None,
+            tile_id: None,
+            label: None,
+            cause: None,
+        }
+    }
+
+    pub fn with_person(event_type: EventType, calendar: &Calendar, person_id: u64) -> Self {
+        Self {
+            event_type,
+            year: calendar.year,
+            month: calendar.month,
+            day: calendar.day,
+            person_id: Some(person_id),
+            tile_id: None,
+            label: None,
+            cause: None,
+        }
+    }
+
+    /// A `Custom` event named `label`
+    pub fn custom(label: &str, calendar: &Calendar) -> Self {
+        Self { label: Some(label.to_string()), ..Self::new(EventType::Custom, calendar) }
+    }
+
+    /// The same event, recorded as happening on `tile_id`
+    pub fn on_tile(self, tile_id: u16) -> Self {
+        Self { tile_id: Some(tile_id), ..self }
+    }
+
+    /// The same event, attributed to `cause`
+    pub fn with_cause(self, cause: DeathCause) -> Self {
+        Self { cause: Some(cause), ..self }
+    }
+}
+
+/// Event log with circular buffer (configurable max size)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLog {
+    events: VecDeque<Event>,
+    max_size: usize,
+}
+
+impl EventLog {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(max_size),
+            max_size,
+        }
+    }
+
+    /// Add an event to the log
+    pub fn push(&mut self, event: Event) {
+        if self.events.len() >= self.max_size {
+            self.events.pop_front(); // Remove oldest
+        }
+        self.events.push_back(event);
+    }
+
+    /// Iterate all events (newest first), zero allocation
+    pub fn iter_all(&self) -> impl Iterator<Item = &Event> + '_ {
+        self.events.iter().rev()
+    }
+
+    /// Get all events (newest first)
+    pub fn get_all(&self) -> Vec<Event> {
+        self.iter_all().cloned().collect()
+    }
+
+    /// Iterate events by type (newest first), zero allocation
+    pub fn iter_by_type(&self, event_type: EventType) -> impl Iterator<Item = &Event> + '_ {
+        self.events.iter().filter(move |e| e.event_type == event_type).rev()
+    }
+
+    /// Get events filtered by type
+    pub fn get_by_type(&self, event_type: EventType) -> Vec<Event> {
+        self.iter_by_type(event_type).cloned().collect()
+    }
+
+    /// Iterate events within a date range (newest first), zero allocation
+    pub fn iter_by_date_range(&self, start_year: u16, end_year: u16) -> impl Iterator<Item = &Event> + '_ {
+        self.events.iter().filter(move |e| e.year >= start_year && e.year <= end_year).rev()
+    }
+
+    /// Get events within a date range (inclusive)
+    pub fn get_by_date_range(&self, start_year: u16, end_year: u16) -> Vec<Event> {
+        self.iter_by_date_range(start_year, end_year).cloned().collect()
+    }
+
+    /// Get recent events (last N events)
+    pub fn get_recent(&self, count: usize) -> Vec<Event> {
+        self.events
+            .iter()
+            .rev()
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// The last `count` events on a tile (newest first)
+    pub fn events_on_tile(&self, tile_id: u16, count: usize) -> Vec<Event> {
+        self.events
+            .iter()
+            .rev()
+            .filter(|e| e.tile_id == Some(tile_id))
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// Count events by type within a date range
+    pub fn count_by_type(&self, event_type: EventType, start_year: u16, end_year: u16) -> usize {
+        self.events
+            .iter()
+            .filter(|e| {
+                e.event_type == event_type && e.year >= start_year && e.year <= end_year
+            })
+            .count()
+    }
+
+    /// Clear all events
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Drop every event from before `year`. Events are stored oldest first, so this
+    /// only pops from the front and the remaining events stay in place.
+    /// Returns how many were removed.
+    pub fn prune_before(&mut self, year: u16) -> usize {
+        let before = self.events.len();
+        while self.events.front().is_some_and(|e| e.year < year) {
+            self.events.pop_front();
+        }
+        before - self.events.len()
+    }
+
+    /// Get total event count
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if log is empty
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(10000) // Default to 10k events (~10k ticks of history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_events_before_year() {
+        let mut log = EventLog::default();
+        for year in 3998..=4001 {
+            let cal = Calendar::new(year, 1, 1);
+            log.push(Event::new(EventType::Birth, &cal));
+            log.push(Event::with_person(EventType::Death, &cal, year as u64));
+        }
+
+        assert_eq!(log.prune_before(4000), 4);
+        assert_eq!(log.len(), 4);
+        assert!(log.iter_all().all(|e| e.year >= 4000));
+        assert_eq!(log.get_recent(1)[0].year, 4001);
+
+        // Nothing older left to drop
+        assert_eq!(log.prune_before(4000), 0);
+        assert_eq!(log.prune_before(5000), 4);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_calendar_stops_at_last_day() {
+        let mut cal = Calendar::new(Calendar::MAX_YEAR, 12, Calendar::DAYS_PER_MONTH - 1);
+        cal.advance();
+        assert!(cal.is_last());
+        cal.advance();
+        assert_eq!(cal, Calendar::last());
+
+        let mut cal = Calendar::new(Calendar::MAX_YEAR - 1, 6, 3);
+        cal.advance_days(10 * Calendar::DAYS_PER_YEAR as u32);
+        assert_eq!(cal, Calendar::last());
+        assert_eq!(Calendar::from_absolute_days(u64::MAX), Calendar::last());
+
+        // Ages stay defined for someone born on the last day
+        let birth = BirthDate::new(Calendar::MAX_YEAR, 12, Calendar::DAYS_PER_MONTH);
+        assert_eq!(birth.age_days(&cal), 0);
+        assert_eq!(BirthDate::from_age(20, &cal).age_years(&cal), 20);
+    }
+
+    #[test]
+    fn test_age_days_across_month_boundary() {
+        let birth = BirthDate::new(10, 3, Calendar::DAYS_PER_MONTH - 1);
+        let mut cal = Calendar::new(10, 3, Calendar::DAYS_PER_MONTH - 1);
+        assert_eq!(birth.age_days(&cal), 0);
+
+        for _ in 0..4 {
+            cal.advance();
+        }
+        assert_eq!((cal.month, cal.day), (4, 3));
+        assert_eq!(birth.age_days(&cal), 4);
+
+        // Across a year boundary as well
+        let birth = BirthDate::new(10, Calendar::MONTHS_PER_YEAR, Calendar::DAYS_PER_MONTH);
+        let cal = Calendar::new(11, 1, 2);
+        assert_eq!(birth.age_days(&cal), 2);
+
+        // Dates before birth clamp to zero
+        assert_eq!(BirthDate::new(12, 1, 1).age_days(&cal), 0);
+    }
+
+    #[test]
+    fn test_long_gestation_crosses_years() {
+        let last_month = Calendar::new(4000, Calendar::MONTHS_PER_YEAR, 5);
+
+        let pregnant = Pregnant::new(&last_month);
+        assert_eq!((pregnant.due_year, pregnant.due_month), (4001, 9));
+
+        // Longer than two calendar years
+        let months = Calendar::MONTHS_PER_YEAR as u32 * 2 + 3;
+        let pregnant = Pregnant::with_gestation(&last_month, months);
+        assert_eq!((pregnant.due_year, pregnant.due_month), (4003, 3));
+        assert!(!pregnant.is_due(&Calendar::new(4003, 2, Calendar::DAYS_PER_MONTH)));
+        assert!(pregnant.is_due(&Calendar::new(4003, 3, 1)));
+        assert!(pregnant.is_due(&Calendar::new(4004, 1, 1)));
+
+        // Whole years land on the same month
+        let pregnant = Pregnant::with_gestation(&last_month, Calendar::MONTHS_PER_YEAR as u32 * 3);
+        assert_eq!((pregnant.due_year, pregnant.due_month), (4003, Calendar::MONTHS_PER_YEAR));
+    }
+
+    #[test]
+    fn test_births_at_end_of_calendar() {
+        // Pregnancies that would run past the calendar are due in its final month
+        let final_month = (Calendar::MAX_YEAR, Calendar::MONTHS_PER_YEAR);
+        let pregnant = Pregnant::new(&Calendar::new(Calendar::MAX_YEAR, 10, 1));
+        assert_eq!((pregnant.due_year, pregnant.due_month), final_month);
+        assert!(!pregnant.is_due(&Calendar::new(Calendar::MAX_YEAR, 11, Calendar::DAYS_PER_MONTH)));
+        assert!(pregnant.is_due(&Calendar::last()));
+        let pregnant = Pregnant::new(&Calendar::last());
+        assert_eq!((pregnant.due_year, pregnant.due_month), final_month);
+
+        // The child count stops at its maximum instead of wrapping
+        let cal = Calendar::last();
+        let mut fertility = Fertility { children_born: Fertility::MAX_CHILDREN - 1, ..Fertility::default() };
+        assert!(fertility.record_birth(&cal));
+        assert!(!fertility.record_birth(&cal));
+        assert_eq!(fertility.children_born, Fertility::MAX_CHILDREN);
+        assert_eq!((fertility.last_birth_year, fertility.last_birth_month), final_month);
+    }
+
+    #[test]
+    fn test_absolute_days_roundtrip() {
+        let mut cal = Calendar::new(3990, 1, 1);
+        let start = cal.to_absolute_days();
+        for offset in 0..(Calendar::DAYS_PER_YEAR as u64 * 20 + 5) {
+            assert_eq!(cal.to_absolute_days(), start + offset);
+            assert_eq!(Calendar::from_absolute_days(cal.to_absolute_days()), cal);
+            cal.advance();
+        }
+
+        assert_eq!(Calendar::from_absolute_days(0), Calendar::new(0, 1, 1));
+        let last = Calendar::new(u16::MAX, Calendar::MONTHS_PER_YEAR, Calendar::DAYS_PER_MONTH);
+        assert_eq!(Calendar::from_absolute_days(last.to_absolute_days()), last);
+    }
+}
@@ -0,0 +1,296 @@
+//! Simulation tunables
+//!
+//! Configuration structs held on `SimulationWorld` and persisted in saves.
+//! Defaults reproduce the original hard-coded behavior.
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::Sex;
+
+/// Every tunable on `SimulationWorld`, for reading or replacing them in one go
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimConfig {
+    pub fertility: FertilityConfig,
+    pub marriage: MarriageConfig,
+    pub mortality: MortalityConfig,
+    pub home_leaving: HomeLeavingConfig,
+    /// Interpolate mortality between age brackets instead of stepping
+    pub smooth_mortality: bool,
+    /// Hard population cap (None = unlimited)
+    pub max_population: Option<u32>,
+}
+
+impl SimConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        self.fertility.validate()?;
+        self.mortality.validate()?;
+        self.home_leaving.validate()
+    }
+}
+
+/// Fertility and conception parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FertilityConfig {
+    /// Minimum months between a birth and the next conception
+    pub min_birth_interval_months: u32,
+    /// Probability (0-1) that a delivery produces no living child
+    pub stillbirth_chance: f64,
+    /// Probability (0-1) that the mother dies in childbirth
+    pub maternal_mortality: f64,
+    /// Extra conception chance for newlyweds, as a fraction (0.5 = +50%; 0 = off)
+    pub newlywed_boost: f64,
+    /// How many months after marriage the newlywed boost lasts
+    pub newlywed_months: u32,
+    /// Months after marriage before a wife can first conceive (0 = immediately)
+    pub post_marriage_delay_months: u32,
+    /// Youngest age at which a woman can conceive, independent of the marriage age
+    pub min_motherhood_age: u16,
+    /// Age after which the conception chance starts to fall
+    pub fertility_decline_start: u16,
+    /// Fraction of the conception chance lost per year past `fertility_decline_start`
+    pub fertility_decline_per_year: f64,
+    /// Fraction of conception chance lost per child already born (0 = no penalty)
+    pub children_penalty: f64,
+    /// Lowest the per-child penalty can take the conception chance, as a fraction
+    pub children_factor_floor: f64,
+}
+
+impl Default for FertilityConfig {
+    fn default() -> Self {
+        Self {
+            min_birth_interval_months: 18,
+            stillbirth_chance: 0.0,
+            maternal_mortality: 0.0,
+            newlywed_boost: 0.0,
+            newlywed_months: 24,
+            post_marriage_delay_months: 0,
+            min_motherhood_age: 16,
+            fertility_decline_start: 28,
+            fertility_decline_per_year: 0.15,
+            children_penalty: 0.1,
+            children_factor_floor: 0.2,
+        }
+    }
+}
+
+impl FertilityConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.stillbirth_chance) {
+            return Err(format!("Stillbirth chance {} is not a probability", self.stillbirth_chance));
+        }
+        if !(0.0..=1.0).contains(&self.maternal_mortality) {
+            return Err(format!("Maternal mortality {} is not a probability", self.maternal_mortality));
+        }
+        if !(0.0..=1.0).contains(&self.fertility_decline_per_year) {
+            return Err(format!("Fertility decline per year {} is not a fraction", self.fertility_decline_per_year));
+        }
+        if !(0.0..=1.0).contains(&self.children_penalty) {
+            return Err(format!("Children penalty {} is not a fraction", self.children_penalty));
+        }
+        if !(0.0..=1.0).contains(&self.children_factor_floor) {
+            return Err(format!("Children factor floor {} is not a fraction", self.children_factor_floor));
+        }
+        Ok(())
+    }
+}
+
+/// Who may marry whom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarriageConfig {
+    pub min_age_male: u16,
+    pub min_age_female: u16,
+    /// Largest allowed age difference between partners, in years
+    pub max_age_gap: u16,
+    /// Prefer pairing men with women no older than themselves
+    pub prefer_older_male: bool,
+    /// Adjacency hops to search for a partner (0 = same tile only)
+    pub search_radius: u8,
+    /// Who moves when partners come from different tiles
+    pub relocation: MarriageRelocation,
+    /// Also forbid partners sharing one parent (parent/child and full siblings never marry)
+    pub forbid_half_siblings: bool,
+    /// Also forbid partners sharing a grandparent
+    pub forbid_first_cousins: bool,
+}
+
+/// Which partner moves to the other's tile after a cross-tile marriage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MarriageRelocation {
+    #[default]
+    WifeMoves,
+    HusbandMoves,
+}
+
+impl Default for MarriageConfig {
+    fn default() -> Self {
+        Self {
+            min_age_male: 16,
+            min_age_female: 16,
+            max_age_gap: 15,
+            prefer_older_male: false,
+            search_radius: 0,
+            relocation: MarriageRelocation::WifeMoves,
+            forbid_half_siblings: false,
+            forbid_first_cousins: false,
+        }
+    }
+}
+
+/// Young adults moving out of the family home to a tile of their own
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HomeLeavingConfig {
+    /// Age at which an unmarried person may leave home
+    pub age: u16,
+    /// Probability (0-1) that an unmarried person leaves on reaching `age` (0 = off)
+    pub fraction: f64,
+    /// Move only to a tile adjacent to the current one, rather than anywhere on the map
+    pub adjacent_only: bool,
+}
+
+impl Default for HomeLeavingConfig {
+    fn default() -> Self {
+        Self {
+            age: 18,
+            fraction: 0.0,
+            adjacent_only: true,
+        }
+    }
+}
+
+impl HomeLeavingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.fraction) {
+            return Err(format!("Home-leaving fraction {} is not a probability", self.fraction));
+        }
+        Ok(())
+    }
+}
+
+/// Annual mortality by age: an infant curve decaying from `neonatal_rate` toward the
+/// first bracket's rate, then the brackets themselves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MortalityTable {
+    /// Annual mortality on the day of birth. The default, 0.23, makes the curve average
+    /// the 0.05 of the flat 0-5 infant bracket it replaced: as many childhood deaths
+    /// overall, but concentrated in the first months
+    pub neonatal_rate: f64,
+    /// (starting age in years, annual probability), ascending by age
+    pub brackets: Vec<(u32, f64)>,
+}
+
+impl Default for MortalityTable {
+    fn default() -> Self {
+        Self {
+            neonatal_rate: 0.23,
+            brackets: vec![
+                (5, 0.005),    // Child
+                (15, 0.002),   // Teen
+                (30, 0.003),   // Young adult
+                (50, 0.01),    // Middle age
+                (60, 0.025),   // Senior
+                (70, 0.05),    // Elderly
+                (80, 0.12),    // Very old
+                (90, 0.25),    // Ancient
+                (100, 0.5),    // Centenarian
+            ],
+        }
+    }
+}
+
+impl MortalityTable {
+    /// Check that there is at least one bracket, rates are probabilities and
+    /// brackets are strictly ascending
+    pub fn validate(&self) -> Result<(), String> {
+        if self.brackets.is_empty() {
+            return Err("Mortality table has no age brackets".to_string());
+        }
+        let valid_rate = |rate: f64| (0.0..=1.0).contains(&rate);
+        if !valid_rate(self.neonatal_rate) {
+            return Err(format!("Neonatal rate {} is not a probability", self.neonatal_rate));
+        }
+        if let Some((age, rate)) = self.brackets.iter().find(|(_, rate)| !valid_rate(*rate)) {
+            return Err(format!("Mortality rate {} at age {} is not a probability", rate, age));
+        }
+        if self.brackets.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err("Mortality brackets must be in ascending age order".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Age distribution for founders placed by seeding
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum AgeProfile {
+    /// Built-in mix: 55% aged 0-20, 23% 21-40, 17% 41-60, 5% 61-80
+    #[default]
+    Standard,
+    /// Every age in [min, max] equally likely
+    Uniform { min: u16, max: u16 },
+    /// Ages drawn in proportion to their weights
+    Weighted(Vec<(u16, f64)>),
+    /// Exponentially fewer people at each older age, truncated at `max`
+    Exponential { mean: f64, max: u16 },
+}
+
+impl AgeProfile {
+    /// Check that the profile can be sampled
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            AgeProfile::Standard => Ok(()),
+            AgeProfile::Uniform { min, max } if min > max => {
+                Err(format!("Uniform age range {}..={} is empty", min, max))
+            }
+            AgeProfile::Uniform { .. } => Ok(()),
+            AgeProfile::Weighted(weights) => {
+                if let Some((age, weight)) = weights.iter().find(|(_, w)| !w.is_finite() || *w < 0.0) {
+                    return Err(format!("Weight {} for age {} must be a non-negative number", weight, age));
+                }
+                if weights.iter().all(|(_, w)| *w == 0.0) {
+                    return Err("Age weights must include at least one positive weight".to_string());
+                }
+                Ok(())
+            }
+            AgeProfile::Exponential { mean, .. } if !mean.is_finite() || *mean <= 0.0 => {
+                Err(format!("Exponential mean age {} must be positive", mean))
+            }
+            AgeProfile::Exponential { .. } => Ok(()),
+        }
+    }
+}
+
+/// Mortality tables, optionally split by sex
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MortalityConfig {
+    /// Used for any sex without its own table
+    pub shared: MortalityTable,
+    pub male: Option<MortalityTable>,
+    pub female: Option<MortalityTable>,
+}
+
+impl MortalityConfig {
+    /// The table that applies to `sex`
+    pub fn table_for(&self, sex: Sex) -> &MortalityTable {
+        let own = match sex {
+            Sex::Male => self.male.as_ref(),
+            Sex::Female => self.female.as_ref(),
+        };
+        own.unwrap_or(&self.shared)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.shared.validate()?;
+        if let Some(male) = &self.male {
+            male.validate().map_err(|e| format!("Male table: {}", e))?;
+        }
+        if let Some(female) = &self.female {
+            female.validate().map_err(|e| format!("Female table: {}", e))?;
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,17 @@
-//! GridWorld Simulation Engine
-//!
-//! High-performance population simulation using ECS architecture.
-//! Designed for 10M+ entities with parallel system execution.
-
-pub mod components;
-pub mod systems;
-pub mod world;
-pub mod persistence;
-pub mod calendar_runner;
-pub mod names;
-
-pub use components::*;
-pub use world::SimulationWorld;
-pub use persistence::{ExportData, ImportResult, SaveStats, LoadFileResult};
+//! GridWorld Simulation Engine
+//!
+//! High-performance population simulation using ECS architecture.
+//! Designed for 10M+ entities with parallel system execution.
+
+pub mod components;
+pub mod config;
+pub mod systems;
+pub mod world;
+pub mod persistence;
+pub mod calendar_runner;
+pub mod names;
+
+pub use components::*;
+pub use config::{AgeProfile, FertilityConfig, HomeLeavingConfig, MarriageConfig, MarriageRelocation, MortalityConfig, MortalityTable, SimConfig};
+pub use world::SimulationWorld;
+pub use persistence::{ExportData, ImportResult, SaveStats, LoadFileResult, LoadError, SaveFormat, SaveHeader, SaveSlot, WorldDiff, Compatibility, CompatibilityStatus, read_save_header, save_compatibility, list_saves};
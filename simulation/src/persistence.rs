@@ -1,419 +1,2288 @@
-//! Persistence module for export/import of simulation state
-//!
-//! Supports two formats:
-//! - JSON export/import (for live sync with Node.js)
-//! - Bincode save files (for fast local persistence)
-
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
-
-use crate::components::*;
-
-// ============================================================================
-// Export Data Structures
-// ============================================================================
-
-/// Complete world state for persistence
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExportData {
-    pub version: u8,  // Schema version for forward compatibility
-    pub calendar: CalendarData,
-    pub next_person_id: u64,
-    pub people: Vec<ExportedPerson>,
-    pub event_log: Vec<ExportedEvent>,
-}
-
-/// Calendar state (matches our Calendar component)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CalendarData {
-    pub year: u16,
-    pub month: u8,
-    pub day: u8,
-}
-
-/// Single person with all their components
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExportedPerson {
-    pub person_id: u64,
-    pub tile_id: u16,
-    pub first_name: String,
-    pub last_name: String,
-    pub sex: ExportedSex,
-    pub birth_year: u16,
-    pub birth_month: u8,
-    pub birth_day: u8,
-    /// PersonId of partner (None = single)
-    pub partner_id: Option<u64>,
-    /// PersonId of mother (None = genesis seed)
-    pub mother_id: Option<u64>,
-    /// Fertility data (only for women who've given birth)
-    pub fertility: Option<ExportedFertility>,
-    /// Pregnancy data (only for currently pregnant women)
-    pub pregnancy: Option<ExportedPregnancy>,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-pub enum ExportedSex {
-    Male,
-    Female,
-}
-
-impl From<Sex> for ExportedSex {
-    fn from(sex: Sex) -> Self {
-        match sex {
-            Sex::Male => ExportedSex::Male,
-            Sex::Female => ExportedSex::Female,
-        }
-    }
-}
-
-impl From<ExportedSex> for Sex {
-    fn from(sex: ExportedSex) -> Self {
-        match sex {
-            ExportedSex::Male => Sex::Male,
-            ExportedSex::Female => Sex::Female,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExportedFertility {
-    pub last_birth_year: u16,
-    pub last_birth_month: u8,
-    pub children_born: u8,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExportedPregnancy {
-    pub due_year: u16,
-    pub due_month: u8,
-}
-
-/// Exported event from event log
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExportedEvent {
-    pub event_type: ExportedEventType,
-    pub year: u16,
-    pub month: u8,
-    pub day: u8,
-    pub person_id: Option<u64>,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-pub enum ExportedEventType {
-    Birth,
-    Death,
-    Marriage,
-    PregnancyStarted,
-    Dissolution,
-}
-
-impl From<EventType> for ExportedEventType {
-    fn from(et: EventType) -> Self {
-        match et {
-            EventType::Birth => ExportedEventType::Birth,
-            EventType::Death => ExportedEventType::Death,
-            EventType::Marriage => ExportedEventType::Marriage,
-            EventType::PregnancyStarted => ExportedEventType::PregnancyStarted,
-            EventType::Dissolution => ExportedEventType::Dissolution,
-        }
-    }
-}
-
-impl From<ExportedEventType> for EventType {
-    fn from(et: ExportedEventType) -> Self {
-        match et {
-            ExportedEventType::Birth => EventType::Birth,
-            ExportedEventType::Death => EventType::Death,
-            ExportedEventType::Marriage => EventType::Marriage,
-            ExportedEventType::PregnancyStarted => EventType::PregnancyStarted,
-            ExportedEventType::Dissolution => EventType::Dissolution,
-        }
-    }
-}
-
-// ============================================================================
-// Export Implementation
-// ============================================================================
-
-impl crate::world::SimulationWorld {
-    /// Export entire world state to JSON string
-    pub fn export_world(&self) -> String {
-        let export_data = self.build_export_data();
-        serde_json::to_string(&export_data).unwrap_or_else(|e| {
-            eprintln!("[ERROR] Failed to serialize world export: {}", e);
-            "{}".to_string()
-        })
-    }
-
-    /// Import world state from JSON string, replacing current state
-    pub fn import_world(&mut self, json: &str) -> Result<ImportResult, String> {
-        let data: ExportData = serde_json::from_str(json)
-            .map_err(|e| format!("JSON parse error: {}", e))?;
-        self.import_from_export_data(data)
-    }
-}
-
-/// Result of import operation
-#[derive(Debug, Clone)]
-pub struct ImportResult {
-    pub population: u32,
-    pub partners: u32,
-    pub mothers: u32,
-    pub calendar_year: u16,
-}
-
-// ============================================================================
-// Bincode Save File
-// ============================================================================
-
-/// On-disk save file format (bincode-serialized)
-#[derive(Serialize, Deserialize)]
-pub struct SaveFile {
-    pub version: u8,
-    pub seed: u32,
-    pub ecs_data: ExportData,
-    /// Node-side state (families, person extensions) stored as raw JSON bytes
-    pub node_state: Vec<u8>,
-}
-
-/// Stats returned after saving
-#[derive(Debug, Clone)]
-pub struct SaveStats {
-    pub population: u32,
-    pub file_bytes: u64,
-}
-
-/// Stats returned after loading
-#[derive(Debug, Clone)]
-pub struct LoadFileResult {
-    pub import_result: ImportResult,
-    pub seed: u32,
-    pub node_state_json: String,
-}
-
-impl crate::world::SimulationWorld {
-    /// Build ExportData from current world state (shared by export_world and save_to_file)
-    fn build_export_data(&self) -> ExportData {
-        let entity_count = self.world.len() as usize;
-        let mut people: Vec<ExportedPerson> = Vec::with_capacity(entity_count);
-
-        let mut person_id_to_entity: HashMap<u64, hecs::Entity> = HashMap::with_capacity(entity_count);
-        let mut entity_to_person_id: HashMap<hecs::Entity, u64> = HashMap::with_capacity(entity_count);
-
-        for (entity, person) in self.world.query::<&Person>().iter() {
-            person_id_to_entity.insert(person.id.0, entity);
-            entity_to_person_id.insert(entity, person.id.0);
-        }
-
-        for (entity, (person, tile, birth, sex)) in self
-            .world
-            .query::<(&Person, &TileId, &BirthDate, &Sex)>()
-            .iter()
-        {
-            let partner_id = self.world.get::<&Partner>(entity)
-                .ok()
-                .and_then(|p| entity_to_person_id.get(&p.0).copied());
-
-            let mother_id = self.world.get::<&Mother>(entity)
-                .ok()
-                .and_then(|m| entity_to_person_id.get(&m.0).copied());
-
-            let fertility = self.world.get::<&Fertility>(entity)
-                .ok()
-                .map(|f| ExportedFertility {
-                    last_birth_year: f.last_birth_year,
-                    last_birth_month: f.last_birth_month,
-                    children_born: f.children_born,
-                });
-
-            let pregnancy = self.world.get::<&Pregnant>(entity)
-                .ok()
-                .map(|p| ExportedPregnancy {
-                    due_year: p.due_year,
-                    due_month: p.due_month,
-                });
-
-            people.push(ExportedPerson {
-                person_id: person.id.0,
-                tile_id: tile.0,
-                first_name: person.first_name.clone(),
-                last_name: person.last_name.clone(),
-                sex: (*sex).into(),
-                birth_year: birth.year,
-                birth_month: birth.month,
-                birth_day: birth.day,
-                partner_id,
-                mother_id,
-                fertility,
-                pregnancy,
-            });
-        }
-
-        // Export event log (uses zero-allocation iterator)
-        let event_log = self.event_log.iter_all()
-            .map(|event| ExportedEvent {
-                event_type: event.event_type.into(),
-                year: event.year,
-                month: event.month,
-                day: event.day,
-                person_id: event.person_id,
-            })
-            .collect();
-
-        ExportData {
-            version: 1,
-            calendar: CalendarData {
-                year: self.calendar.year,
-                month: self.calendar.month,
-                day: self.calendar.day,
-            },
-            next_person_id: self.next_person_id,
-            people,
-            event_log,
-        }
-    }
-
-    /// Import from ExportData (shared by import_world and load_from_file)
-    fn import_from_export_data(&mut self, data: ExportData) -> Result<ImportResult, String> {
-        if data.version != 1 {
-            return Err(format!("Unsupported export version: {}", data.version));
-        }
-
-        self.world.clear();
-        self.calendar = Calendar::new(data.calendar.year, data.calendar.month, data.calendar.day);
-        self.next_person_id = data.next_person_id;
-
-        let mut person_id_to_entity: HashMap<u64, hecs::Entity> = HashMap::with_capacity(data.people.len());
-
-        for person in &data.people {
-            let entity = self.world.spawn((
-                Person {
-                    id: PersonId(person.person_id),
-                    first_name: person.first_name.clone(),
-                    last_name: person.last_name.clone(),
-                },
-                TileId(person.tile_id),
-                BirthDate::new(person.birth_year, person.birth_month, person.birth_day),
-                Sex::from(person.sex),
-            ));
-
-            if let Some(ref fert) = person.fertility {
-                let _ = self.world.insert_one(entity, Fertility { // Just spawned, always valid
-                    last_birth_year: fert.last_birth_year,
-                    last_birth_month: fert.last_birth_month,
-                    children_born: fert.children_born,
-                });
-            }
-
-            if let Some(ref preg) = person.pregnancy {
-                let _ = self.world.insert_one(entity, Pregnant { // Just spawned, always valid
-                    due_year: preg.due_year,
-                    due_month: preg.due_month,
-                });
-            }
-
-            person_id_to_entity.insert(person.person_id, entity);
-        }
-
-        let mut partners_added = 0u32;
-        let mut mothers_added = 0u32;
-
-        for person in &data.people {
-            let entity = person_id_to_entity[&person.person_id];
-
-            if let Some(partner_pid) = person.partner_id {
-                if let Some(&partner_entity) = person_id_to_entity.get(&partner_pid) {
-                    let _ = self.world.insert_one(entity, Partner(partner_entity)); // Entity just spawned above
-                    partners_added += 1;
-                }
-            }
-
-            if let Some(mother_pid) = person.mother_id {
-                if let Some(&mother_entity) = person_id_to_entity.get(&mother_pid) {
-                    let _ = self.world.insert_one(entity, Mother(mother_entity)); // Entity just spawned above
-                    mothers_added += 1;
-                }
-            }
-        }
-
-        // Restore event log
-        self.event_log.clear();
-        for event in data.event_log {
-            self.event_log.push(Event {
-                event_type: event.event_type.into(),
-                year: event.year,
-                month: event.month,
-                day: event.day,
-                person_id: event.person_id,
-            });
-        }
-
-        Ok(ImportResult {
-            population: data.people.len() as u32,
-            partners: partners_added,
-            mothers: mothers_added,
-            calendar_year: self.calendar.year,
-        })
-    }
-
-    /// Save world + Node state to a bincode file (atomic write via tmp + rename)
-    pub fn save_to_file(&self, node_state_json: &str, seed: u32, path: &str) -> Result<SaveStats, String> {
-        let ecs_data = self.build_export_data();
-        let population = ecs_data.people.len() as u32;
-
-        let save_file = SaveFile {
-            version: 1,
-            seed,
-            ecs_data,
-            node_state: node_state_json.as_bytes().to_vec(),
-        };
-
-        let encoded = bincode::serialize(&save_file)
-            .map_err(|e| format!("Bincode serialize error: {}", e))?;
-
-        let file_bytes = encoded.len() as u64;
-
-        // Atomic write: write to .tmp then rename
-        let tmp_path = format!("{}.tmp", path);
-
-        // Ensure parent directory exists
-        if let Some(parent) = Path::new(path).parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create save directory: {}", e))?;
-        }
-
-        std::fs::write(&tmp_path, &encoded)
-            .map_err(|e| format!("Failed to write tmp file: {}", e))?;
-
-        std::fs::rename(&tmp_path, path)
-            .map_err(|e| format!("Failed to rename tmp to final: {}", e))?;
-
-        Ok(SaveStats { population, file_bytes })
-    }
-
-    /// Load world + Node state from a bincode file
-    pub fn load_from_file(&mut self, path: &str) -> Result<LoadFileResult, String> {
-        let data = std::fs::read(path)
-            .map_err(|e| format!("Failed to read save file: {}", e))?;
-
-        let save_file: SaveFile = bincode::deserialize(&data)
-            .map_err(|e| format!("Bincode deserialize error: {}", e))?;
-
-        if save_file.version != 1 {
-            return Err(format!("Unsupported save file version: {}", save_file.version));
-        }
-
-        let node_state_json = String::from_utf8(save_file.node_state)
-            .map_err(|e| format!("Invalid UTF-8 in node_state: {}", e))?;
-
-        let import_result = self.import_from_export_data(save_file.ecs_data)?;
-
-        Ok(LoadFileResult {
-            import_result,
-            seed: save_file.seed,
-            node_state_json,
-        })
-    }
-}
+//! Persistence module for export/import of simulation state
+//!
+//! Supports two formats:
+//! - JSON export/import (for live sync with Node.js)
+//! - Bincode save files (for fast local persistence)
+
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::components::*;
+use crate::config::{FertilityConfig, HomeLeavingConfig, MarriageConfig, MortalityConfig, SimConfig};
+use crate::world::{SimRng, YearStats};
+
+mod legacy;
+
+// ============================================================================
+// Export Data Structures
+// ============================================================================
+
+/// Current ExportData schema version. Older JSON exports (>= 1) still import,
+/// with missing fields taking their defaults.
+pub const EXPORT_VERSION: u8 = 2;
+
+/// Current bincode SaveFile version. Bincode is not self-describing, so a save
+/// decodes only with the exact structs that wrote it: this version directly, and
+/// released older versions through the frozen layouts in `legacy`.
+pub const SAVE_VERSION: u8 = 2;
+
+/// Complete world state for persistence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportData {
+    pub version: u8,  // Schema version for forward compatibility
+    pub calendar: CalendarData,
+    /// Next PersonId to hand out; if absent (or too low) import uses one past the highest id
+    #[serde(default)]
+    pub next_person_id: u64,
+    pub people: Vec<ExportedPerson>,
+    /// Newest first; hand-written or partial exports may leave it out
+    #[serde(default)]
+    pub event_log: Vec<ExportedEvent>,
+    /// Fertility tunables (defaults in version 1 exports)
+    #[serde(default)]
+    pub fertility_config: FertilityConfig,
+    /// Marriage rules (defaults in version 1 exports)
+    #[serde(default)]
+    pub marriage_config: MarriageConfig,
+    /// Family groupings (empty in version 1 exports)
+    #[serde(default)]
+    pub households: Vec<ExportedHousehold>,
+    /// Next household id to hand out (0 in version 1 exports)
+    #[serde(default)]
+    pub next_household_id: u64,
+    /// Mortality tables (defaults in version 1 exports)
+    #[serde(default)]
+    pub mortality_config: MortalityConfig,
+    /// Per-year vital counts keyed by year (empty in version 1 exports)
+    #[serde(default)]
+    pub year_stats: BTreeMap<u16, YearStats>,
+    /// Mortality interpolation switch (off in version 1 exports)
+    #[serde(default)]
+    pub smooth_mortality: bool,
+    /// Hard population cap (unlimited in version 1 exports)
+    #[serde(default)]
+    pub max_population: Option<u32>,
+    /// Ticks run so far (0 in version 1 exports)
+    #[serde(default)]
+    pub ticks_elapsed: u64,
+    /// World RNG position, so a loaded world continues the same run
+    /// (None in version 1 exports; the importing world keeps its own RNG)
+    #[serde(default)]
+    pub rng: Option<ExportedRng>,
+    /// Date the world began (None in version 1 exports, read as `Calendar::default()`)
+    #[serde(default)]
+    pub start_date: Option<CalendarData>,
+    /// Leaving-home rules (defaults, i.e. off, in version 1 exports)
+    #[serde(default)]
+    pub home_leaving_config: HomeLeavingConfig,
+}
+
+/// Where the world RNG was in its stream when exported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedRng {
+    /// `SimulationWorld::seed` the RNG was created from
+    pub world_seed: u64,
+    pub key: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+/// A household and the people in it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedHousehold {
+    pub household_id: u64,
+    /// PersonIds of members, ascending
+    pub members: Vec<u64>,
+}
+
+/// What changed between two snapshots, keyed on person_id
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorldDiff {
+    /// In the newer snapshot only (births and arrivals)
+    pub born: Vec<u64>,
+    /// In the older snapshot only (deaths and departures)
+    pub died: Vec<u64>,
+    /// Couples partnered in the newer snapshot but not the older, lower id first
+    pub new_partnerships: Vec<(u64, u64)>,
+    /// People present in both whose tile changed: (person_id, from, to)
+    pub relocations: Vec<(u64, u16, u16)>,
+}
+
+impl ExportData {
+    /// Serialize to compact JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|e| {
+            eprintln!("[ERROR] Failed to serialize world export: {}", e);
+            "{}".to_string()
+        })
+    }
+
+    /// Serialize to indented JSON
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| {
+            eprintln!("[ERROR] Failed to serialize world export: {}", e);
+            "{}".to_string()
+        })
+    }
+
+    /// Write this snapshot + Node state to a bincode file (atomic write via tmp + rename)
+    pub fn save_to_file(self, node_state_json: &str, seed: u32, path: &str) -> Result<SaveStats, String> {
+        self.save_to_file_as(node_state_json, seed, path, SaveFormat::Monolithic)
+    }
+
+    /// Like `save_to_file`, choosing the body layout with `format`
+    pub fn save_to_file_as(
+        mut self,
+        node_state_json: &str,
+        seed: u32,
+        path: &str,
+        format: SaveFormat,
+    ) -> Result<SaveStats, String> {
+        use bincode::Options;
+
+        let population = self.people.len() as u32;
+        let calendar = self.calendar.clone();
+        let people = match format {
+            SaveFormat::Monolithic => Vec::new(),
+            SaveFormat::Records => std::mem::take(&mut self.people),
+        };
+
+        let save_file = SaveFile {
+            version: SAVE_VERSION,
+            seed,
+            ecs_data: self,
+            node_state: node_state_json.as_bytes().to_vec(),
+        };
+
+        let header = SaveHeader {
+            version: save_file.version,
+            codec: match format {
+                SaveFormat::Monolithic => SAVE_CODEC,
+                SaveFormat::Records => SAVE_CODEC_RECORDS,
+            },
+            population,
+            year: calendar.year,
+            month: calendar.month,
+            day: calendar.day,
+            seed,
+            saved_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+
+        let body = save_codec()
+            .serialize(&save_file)
+            .map_err(|e| format!("Bincode serialize error: {}", e))?;
+
+        let mut encoded = Vec::with_capacity(SAVE_HEADER_LEN + body.len());
+        encoded.extend_from_slice(&header.to_bytes());
+        match format {
+            SaveFormat::Monolithic => encoded.extend_from_slice(&body),
+            SaveFormat::Records => {
+                push_record(&mut encoded, &body)?;
+                for person in &people {
+                    let record = save_codec()
+                        .serialize(person)
+                        .map_err(|e| format!("Bincode serialize error: {}", e))?;
+                    push_record(&mut encoded, &record)?;
+                }
+                encoded.extend_from_slice(&record_trailer(people.len() as u32));
+            }
+        }
+
+        let file_bytes = encoded.len() as u64;
+
+        write_atomic(path, &encoded)?;
+
+        Ok(SaveStats { population, file_bytes })
+    }
+
+    /// Compare this (older) snapshot against `other` (newer). All lists are sorted.
+    pub fn diff(&self, other: &ExportData) -> WorldDiff {
+        let before: HashMap<u64, &ExportedPerson> =
+            self.people.iter().map(|p| (p.person_id, p)).collect();
+        let after: HashMap<u64, &ExportedPerson> =
+            other.people.iter().map(|p| (p.person_id, p)).collect();
+
+        let mut diff = WorldDiff::default();
+        for (id, person) in &after {
+            match before.get(id) {
+                None => diff.born.push(*id),
+                Some(old) if old.tile_id != person.tile_id => {
+                    diff.relocations.push((*id, old.tile_id, person.tile_id));
+                }
+                Some(_) => {}
+            }
+
+            if let Some(partner) = person.partner_id {
+                let was_partnered = before.get(id).and_then(|old| old.partner_id) == Some(partner);
+                if *id < partner && !was_partnered {
+                    diff.new_partnerships.push((*id, partner));
+                }
+            }
+        }
+        diff.died = before.keys().filter(|id| !after.contains_key(id)).copied().collect();
+
+        diff.born.sort_unstable();
+        diff.died.sort_unstable();
+        diff.new_partnerships.sort_unstable();
+        diff.relocations.sort_unstable();
+        diff
+    }
+}
+
+/// Calendar state (matches our Calendar component)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarData {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Single person with all their components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedPerson {
+    pub person_id: u64,
+    pub tile_id: u16,
+    pub first_name: String,
+    pub last_name: String,
+    pub sex: ExportedSex,
+    pub birth_year: u16,
+    pub birth_month: u8,
+    pub birth_day: u8,
+    /// PersonId of partner (None = single)
+    pub partner_id: Option<u64>,
+    /// PersonId of mother (None = genesis seed)
+    pub mother_id: Option<u64>,
+    /// Fertility data (only for women who've given birth)
+    pub fertility: Option<ExportedFertility>,
+    /// Pregnancy data (only for currently pregnant women)
+    pub pregnancy: Option<ExportedPregnancy>,
+    /// Occupation (None in version 1 exports)
+    #[serde(default)]
+    pub occupation: Option<ExportedOccupation>,
+    /// Health 0-100 (None in version 1 exports)
+    #[serde(default)]
+    pub health: Option<u8>,
+    /// PersonId of father (None = unknown, or version 1 exports)
+    #[serde(default)]
+    pub father_id: Option<u64>,
+    /// Start of the current partnership (None = single, or version 1 exports)
+    #[serde(default)]
+    pub married_since: Option<ExportedMarriedSince>,
+    /// Generations since the founders (None in version 1 exports)
+    #[serde(default)]
+    pub generation: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportedSex {
+    Male,
+    Female,
+}
+
+impl From<Sex> for ExportedSex {
+    fn from(sex: Sex) -> Self {
+        match sex {
+            Sex::Male => ExportedSex::Male,
+            Sex::Female => ExportedSex::Female,
+        }
+    }
+}
+
+impl From<ExportedSex> for Sex {
+    fn from(sex: ExportedSex) -> Self {
+        match sex {
+            ExportedSex::Male => Sex::Male,
+            ExportedSex::Female => Sex::Female,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportedOccupation {
+    Child,
+    Farmer,
+    Laborer,
+    Elder,
+}
+
+impl From<Occupation> for ExportedOccupation {
+    fn from(occupation: Occupation) -> Self {
+        match occupation {
+            Occupation::Child => ExportedOccupation::Child,
+            Occupation::Farmer => ExportedOccupation::Farmer,
+            Occupation::Laborer => ExportedOccupation::Laborer,
+            Occupation::Elder => ExportedOccupation::Elder,
+        }
+    }
+}
+
+impl From<ExportedOccupation> for Occupation {
+    fn from(occupation: ExportedOccupation) -> Self {
+        match occupation {
+            ExportedOccupation::Child => Occupation::Child,
+            ExportedOccupation::Farmer => Occupation::Farmer,
+            ExportedOccupation::Laborer => Occupation::Laborer,
+            ExportedOccupation::Elder => Occupation::Elder,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedFertility {
+    pub last_birth_year: u16,
+    pub last_birth_month: u8,
+    pub children_born: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedPregnancy {
+    pub due_year: u16,
+    pub due_month: u8,
+    /// Father's generation at conception (None = unknown, or version 1 exports)
+    #[serde(default)]
+    pub father_generation: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedMarriedSince {
+    pub year: u16,
+    pub month: u8,
+}
+
+/// Exported event from event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedEvent {
+    pub event_type: ExportedEventType,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub person_id: Option<u64>,
+    /// Tile the event happened on (absent in version 1 exports)
+    #[serde(default)]
+    pub tile_id: Option<u16>,
+    /// Label of a custom event (absent in version 1 exports)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Cause of a death event (absent in version 1 exports)
+    #[serde(default)]
+    pub cause: Option<ExportedDeathCause>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportedEventType {
+    Birth,
+    Death,
+    Marriage,
+    PregnancyStarted,
+    Dissolution,
+    Immigration,
+    Emigration,
+    Stillbirth,
+    CapacityReached,
+    CalendarExhausted,
+    Custom,
+}
+
+impl From<EventType> for ExportedEventType {
+    fn from(et: EventType) -> Self {
+        match et {
+            EventType::Birth => ExportedEventType::Birth,
+            EventType::Death => ExportedEventType::Death,
+            EventType::Marriage => ExportedEventType::Marriage,
+            EventType::PregnancyStarted => ExportedEventType::PregnancyStarted,
+            EventType::Dissolution => ExportedEventType::Dissolution,
+            EventType::Immigration => ExportedEventType::Immigration,
+            EventType::Emigration => ExportedEventType::Emigration,
+            EventType::Stillbirth => ExportedEventType::Stillbirth,
+            EventType::CapacityReached => ExportedEventType::CapacityReached,
+            EventType::CalendarExhausted => ExportedEventType::CalendarExhausted,
+            EventType::Custom => ExportedEventType::Custom,
+        }
+    }
+}
+
+impl From<ExportedEventType> for EventType {
+    fn from(et: ExportedEventType) -> Self {
+        match et {
+            ExportedEventType::Birth => EventType::Birth,
+            ExportedEventType::Death => EventType::Death,
+            ExportedEventType::Marriage => EventType::Marriage,
+            ExportedEventType::PregnancyStarted => EventType::PregnancyStarted,
+            ExportedEventType::Dissolution => EventType::Dissolution,
+            ExportedEventType::Immigration => EventType::Immigration,
+            ExportedEventType::Emigration => EventType::Emigration,
+            ExportedEventType::Stillbirth => EventType::Stillbirth,
+            ExportedEventType::CapacityReached => EventType::CapacityReached,
+            ExportedEventType::CalendarExhausted => EventType::CalendarExhausted,
+            ExportedEventType::Custom => EventType::Custom,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportedDeathCause {
+    Natural,
+    Childbirth,
+}
+
+impl From<DeathCause> for ExportedDeathCause {
+    fn from(cause: DeathCause) -> Self {
+        match cause {
+            DeathCause::Natural => ExportedDeathCause::Natural,
+            DeathCause::Childbirth => ExportedDeathCause::Childbirth,
+        }
+    }
+}
+
+impl From<ExportedDeathCause> for DeathCause {
+    fn from(cause: ExportedDeathCause) -> Self {
+        match cause {
+            ExportedDeathCause::Natural => DeathCause::Natural,
+            ExportedDeathCause::Childbirth => DeathCause::Childbirth,
+        }
+    }
+}
+
+// ============================================================================
+// Export Implementation
+// ============================================================================
+
+impl crate::world::SimulationWorld {
+    /// Export entire world state to JSON string (compact, used for live sync)
+    pub fn export_world(&self) -> String {
+        self.snapshot().to_json()
+    }
+
+    /// Export entire world state to indented JSON (for debugging small worlds)
+    pub fn export_world_pretty(&self) -> String {
+        self.snapshot().to_json_pretty()
+    }
+
+    /// Owned copy of the world state. Building it is much cheaper than serializing it,
+    /// so callers sharing the world behind a lock should snapshot under the lock and
+    /// serialize after releasing it.
+    pub fn snapshot(&self) -> ExportData {
+        self.build_export_data()
+    }
+
+    /// Export only the people living on `tiles` to JSON (for sharing or sharding).
+    /// Partner/mother links are kept only when the other person is also in the region.
+    pub fn export_region(&self, tiles: &[u16]) -> String {
+        let tiles: std::collections::HashSet<u16> = tiles.iter().copied().collect();
+        self.build_export_data_filtered(|tile| tiles.contains(&tile)).to_json()
+    }
+
+    /// Import world state from JSON string, replacing current state
+    pub fn import_world(&mut self, json: &str) -> Result<ImportResult, String> {
+        let data: ExportData = serde_json::from_str(json)
+            .map_err(|e| format!("JSON parse error: {}", e))?;
+        self.import_from_export_data(data)
+    }
+
+    /// Self-test for save fidelity: export, re-import into a scratch world, and compare
+    /// population, partners, mothers, pregnancies, calendar and per-tile counts.
+    /// The error lists every aggregate that changed.
+    pub fn assert_roundtrip(&self) -> Result<(), String> {
+        self.verify_reimport(&self.export_world())
+    }
+
+    /// Import `json` into a scratch world and compare it with this one
+    fn verify_reimport(&self, json: &str) -> Result<(), String> {
+        let mut scratch = crate::world::SimulationWorld::new();
+        scratch.import_world(json)?;
+
+        let (before, after) = (IntegritySummary::of(self), IntegritySummary::of(&scratch));
+        let mut mismatches = Vec::new();
+        let mut compare = |what: &str, before: String, after: String| {
+            if before != after {
+                mismatches.push(format!("{}: {} before, {} after", what, before, after));
+            }
+        };
+        compare("population", before.population.to_string(), after.population.to_string());
+        compare("partners", before.partners.to_string(), after.partners.to_string());
+        compare("mothers", before.mothers.to_string(), after.mothers.to_string());
+        compare("pregnancies", before.pregnancies.to_string(), after.pregnancies.to_string());
+        compare("calendar", format!("{:?}", before.calendar), format!("{:?}", after.calendar));
+        compare("tiles", format!("{:?}", before.tiles), format!("{:?}", after.tiles));
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Round trip changed the world: {}", mismatches.join("; ")))
+        }
+    }
+}
+
+/// Aggregates compared by `assert_roundtrip`
+struct IntegritySummary {
+    population: usize,
+    partners: usize,
+    mothers: usize,
+    pregnancies: usize,
+    calendar: (u16, u8, u8),
+    tiles: BTreeMap<u16, u32>,
+}
+
+impl IntegritySummary {
+    fn of(world: &crate::world::SimulationWorld) -> Self {
+        Self {
+            population: world.world.query::<&BirthDate>().iter().count(),
+            partners: world.world.query::<&Partner>().iter().count(),
+            mothers: world.world.query::<&Mother>().iter().count(),
+            pregnancies: world.world.query::<&Pregnant>().iter().count(),
+            calendar: (world.calendar.year, world.calendar.month, world.calendar.day),
+            tiles: world.population_by_tile().into_iter().collect(),
+        }
+    }
+}
+
+/// Result of import operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportResult {
+    pub population: u32,
+    pub partners: u32,
+    pub mothers: u32,
+    pub calendar_year: u16,
+    /// Impossible components dropped on import (pregnancy or fertility on a man)
+    pub anomalies: u32,
+}
+
+// ============================================================================
+// Bincode Save File
+// ============================================================================
+
+/// On-disk save file format (bincode-serialized)
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u8,
+    pub seed: u32,
+    pub ecs_data: ExportData,
+    /// Node-side state (families, person extensions) stored as raw JSON bytes
+    pub node_state: Vec<u8>,
+}
+
+// ============================================================================
+// Save Header
+// ============================================================================
+
+/// Magic bytes identifying a headered save file
+pub const SAVE_MAGIC: [u8; 4] = *b"GWSV";
+
+/// Size of the fixed-layout header in bytes
+pub const SAVE_HEADER_LEN: usize = 26;
+
+/// Identifies the bincode options used for the save body. Bump together with
+/// `save_codec` if the encoding ever changes, so older saves are detected rather
+/// than misread. Headers written before this field existed carry 0, which was
+/// the same encoding as codec 1.
+pub const SAVE_CODEC: u8 = 1;
+
+/// Codec for the record layout: the same bincode options as `SAVE_CODEC`, but the
+/// body is a stream of length-prefixed records. The first record is the `SaveFile`
+/// with no people; each one after it is a single `ExportedPerson`. Each record is
+/// prefixed with its length as a little-endian u32. The body ends with a trailer: a
+/// zero length marker, then the number of person records as a u32. A file cut anywhere,
+/// even between records, lacks a matching trailer, while `append_records` can add
+/// people by rewriting only the trailer and the header's `population`.
+pub const SAVE_CODEC_RECORDS: u8 = 2;
+
+/// Bytes in the trailer closing a `SAVE_CODEC_RECORDS` body
+const RECORD_TRAILER_LEN: usize = 8;
+
+/// Largest record the loader will allocate for, so a corrupt length fails fast
+const MAX_RECORD_LEN: u32 = 1 << 30;
+
+/// Body layout for `save_to_file_as`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveFormat {
+    /// One bincode `SaveFile` (`SAVE_CODEC`)
+    #[default]
+    Monolithic,
+    /// Length-prefixed person records (`SAVE_CODEC_RECORDS`), read back in a streaming loop
+    Records,
+}
+
+fn push_record(out: &mut Vec<u8>, record: &[u8]) -> Result<(), String> {
+    let len = u32::try_from(record.len())
+        .ok()
+        .filter(|len| *len <= MAX_RECORD_LEN)
+        .ok_or_else(|| format!("Save record too large: {} bytes", record.len()))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(record);
+    Ok(())
+}
+
+fn record_trailer(count: u32) -> [u8; RECORD_TRAILER_LEN] {
+    let mut trailer = [0u8; RECORD_TRAILER_LEN];
+    trailer[4..].copy_from_slice(&count.to_le_bytes());
+    trailer
+}
+
+/// Read the next length-prefixed record, or None at a clean end of stream
+fn read_record<R: std::io::Read>(reader: &mut R) -> Result<Option<Vec<u8>>, LoadError> {
+    let io_err = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => LoadError::Corrupt("truncated record".to_string()),
+        _ => LoadError::Io(e.to_string()),
+    };
+
+    let mut len = [0u8; 4];
+    let first = loop {
+        match reader.read(&mut len[..1]) {
+            Ok(n) => break n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(io_err(e)),
+        }
+    };
+    if first == 0 {
+        return Ok(None);
+    }
+    reader.read_exact(&mut len[1..]).map_err(io_err)?;
+
+    let len = u32::from_le_bytes(len);
+    if len > MAX_RECORD_LEN {
+        return Err(LoadError::Corrupt(format!("record of {} bytes", len)));
+    }
+    let mut record = vec![0u8; len as usize];
+    reader.read_exact(&mut record).map_err(io_err)?;
+    Ok(Some(record))
+}
+
+/// The pinned bincode options for save bodies (codec 1): fixed-width little-endian
+/// integers and trailing bytes allowed, identical to bincode 1.x `serialize` defaults.
+fn save_codec() -> impl bincode::Options {
+    use bincode::Options;
+
+    bincode::options()
+        .with_fixint_encoding()
+        .with_little_endian()
+        .allow_trailing_bytes()
+}
+
+/// Small fixed-layout header prepended to the bincode body so a file picker
+/// can show population/date without deserializing the whole world.
+///
+/// Layout (little-endian): magic[4], version u8, codec u8, population u32,
+/// year u16, month u8, day u8, seed u32, saved_at u64 (unix seconds).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SaveHeader {
+    pub version: u8,
+    /// Body encoding (`SAVE_CODEC`; 0 in older headers)
+    pub codec: u8,
+    pub population: u32,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub seed: u32,
+    pub saved_at: u64,
+}
+
+impl SaveHeader {
+    pub fn to_bytes(&self) -> [u8; SAVE_HEADER_LEN] {
+        let mut buf = [0u8; SAVE_HEADER_LEN];
+        buf[0..4].copy_from_slice(&SAVE_MAGIC);
+        buf[4] = self.version;
+        buf[5] = self.codec;
+        buf[6..10].copy_from_slice(&self.population.to_le_bytes());
+        buf[10..12].copy_from_slice(&self.year.to_le_bytes());
+        buf[12] = self.month;
+        buf[13] = self.day;
+        buf[14..18].copy_from_slice(&self.seed.to_le_bytes());
+        buf[18..26].copy_from_slice(&self.saved_at.to_le_bytes());
+        buf
+    }
+
+    /// Parse a header from the start of `bytes`. Returns None if the magic is absent
+    /// (legacy headerless save) or the buffer is too short.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < SAVE_HEADER_LEN || bytes[0..4] != SAVE_MAGIC {
+            return None;
+        }
+        Some(Self {
+            version: bytes[4],
+            codec: bytes[5],
+            population: u32::from_le_bytes(bytes[6..10].try_into().ok()?),
+            year: u16::from_le_bytes(bytes[10..12].try_into().ok()?),
+            month: bytes[12],
+            day: bytes[13],
+            seed: u32::from_le_bytes(bytes[14..18].try_into().ok()?),
+            saved_at: u64::from_le_bytes(bytes[18..26].try_into().ok()?),
+        })
+    }
+}
+
+/// Why a save file could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// No file at the given path
+    NotFound,
+    /// The file could not be read (permissions, I/O failure)
+    Io(String),
+    /// Neither a headered save nor a legacy bincode body
+    BadMagic,
+    /// The body is truncated or does not decode as a save
+    Corrupt(String),
+    /// Written by a different save format version
+    UnsupportedVersion(u8),
+    /// Body encoded with unknown bincode options
+    UnsupportedCodec(u8),
+    /// The embedded Node state is not valid UTF-8
+    BadNodeState,
+    /// The embedded Node state was rejected by the caller's check
+    InvalidNodeState(String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::NotFound => write!(f, "Save file not found"),
+            LoadError::Io(e) => write!(f, "Failed to read save file: {}", e),
+            LoadError::BadMagic => write!(f, "Not a save file"),
+            LoadError::Corrupt(e) => write!(f, "Save file is corrupt: {}", e),
+            LoadError::UnsupportedVersion(v) => write!(f, "Unsupported save file version: {}", v),
+            LoadError::UnsupportedCodec(c) => write!(f, "Unsupported save file encoding: {}", c),
+            LoadError::BadNodeState => write!(f, "Save file has invalid node state"),
+            LoadError::InvalidNodeState(e) => write!(f, "Save file has invalid node state: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Read only the header of a save file, without deserializing the world
+pub fn read_save_header(path: &str) -> Result<SaveHeader, String> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open save file: {}", e))?;
+    let mut buf = [0u8; SAVE_HEADER_LEN];
+    file.read_exact(&mut buf)
+        .map_err(|e| format!("Failed to read save header: {}", e))?;
+
+    SaveHeader::from_bytes(&buf).ok_or_else(|| "Save file has no header".to_string())
+}
+
+/// Add `people` to a save written with `SaveFormat::Records`, without rewriting it.
+/// Only the old trailer and the header's `population` are overwritten, so a write
+/// interrupted partway leaves a file that fails to load as corrupt rather than one
+/// that loads short. Ids must not clash with anyone already saved.
+/// Returns the new number of people in the save.
+pub fn append_records(path: &str, people: &[ExportedPerson]) -> Result<u32, String> {
+    use bincode::Options;
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open save file: {}", e))?;
+    let mut header = [0u8; SAVE_HEADER_LEN];
+    file.read_exact(&mut header)
+        .map_err(|e| format!("Failed to read save header: {}", e))?;
+    let mut header = SaveHeader::from_bytes(&header).ok_or_else(|| "Save file has no header".to_string())?;
+    if header.version != SAVE_VERSION || header.codec != SAVE_CODEC_RECORDS {
+        return Err("Only current record-format saves can be appended to".to_string());
+    }
+
+    let len = file.metadata().map_err(|e| format!("Failed to read save file: {}", e))?.len();
+    let trailer_at = len
+        .checked_sub(RECORD_TRAILER_LEN as u64)
+        .filter(|at| *at >= SAVE_HEADER_LEN as u64)
+        .ok_or_else(|| "Save file is truncated".to_string())?;
+    let mut trailer = [0u8; RECORD_TRAILER_LEN];
+    file.seek(SeekFrom::Start(trailer_at))
+        .and_then(|_| file.read_exact(&mut trailer))
+        .map_err(|e| format!("Failed to read save file: {}", e))?;
+    let count = u32::from_le_bytes(trailer[4..].try_into().unwrap());
+    if trailer != record_trailer(count) {
+        return Err("Save file is missing its end of records".to_string());
+    }
+
+    let count = u32::try_from(people.len())
+        .ok()
+        .and_then(|added| count.checked_add(added))
+        .ok_or_else(|| "Too many people for one save".to_string())?;
+    let mut appended = Vec::new();
+    for person in people {
+        let record = save_codec()
+            .serialize(person)
+            .map_err(|e| format!("Bincode serialize error: {}", e))?;
+        push_record(&mut appended, &record)?;
+    }
+    appended.extend_from_slice(&record_trailer(count));
+    header.population = count;
+
+    let write_err = |e: std::io::Error| format!("Failed to write save file: {}", e);
+    file.seek(SeekFrom::Start(trailer_at)).map_err(write_err)?;
+    file.write_all(&appended).map_err(write_err)?;
+    file.seek(SeekFrom::Start(0)).map_err(write_err)?;
+    file.write_all(&header.to_bytes()).map_err(write_err)?;
+    file.sync_all().map_err(write_err)?;
+    Ok(count)
+}
+
+/// How a save's format version relates to what this build reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CompatibilityStatus {
+    Current,
+    /// Written by an older version; it loads only if a migration exists (`needs_migration`)
+    Older,
+    /// Written by a newer version of the app
+    NewerThanSupported,
+}
+
+/// Whether a save can be loaded, from its header alone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Compatibility {
+    pub save_version: u8,
+    pub status: CompatibilityStatus,
+    /// `load_from_file` will accept this version and body codec
+    pub supported: bool,
+    /// From an older schema that `load_from_file` migrates while loading
+    pub needs_migration: bool,
+}
+
+/// Check a save's version against `SAVE_VERSION` without loading it.
+/// Works on headered and legacy headerless saves; errors if the file isn't a save.
+pub fn save_compatibility(path: &str) -> Result<Compatibility, String> {
+    use std::io::Read;
+
+    let mut prefix = Vec::with_capacity(SAVE_HEADER_LEN);
+    std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open save file: {}", e))?
+        .take(SAVE_HEADER_LEN as u64)
+        .read_to_end(&mut prefix)
+        .map_err(|e| format!("Failed to read save header: {}", e))?;
+
+    let (save_version, codec_ok) = if prefix.starts_with(&SAVE_MAGIC) {
+        let header = SaveHeader::from_bytes(&prefix).ok_or_else(|| "Save header is truncated".to_string())?;
+        (header.version, matches!(header.codec, 0 | SAVE_CODEC | SAVE_CODEC_RECORDS))
+    } else {
+        // Legacy saves start with their version; anything newer would carry a header
+        match prefix.first() {
+            Some(&v) if (1..=SAVE_VERSION).contains(&v) => (v, true),
+            _ => return Err("Not a save file".to_string()),
+        }
+    };
+
+    let status = match save_version.cmp(&SAVE_VERSION) {
+        std::cmp::Ordering::Equal => CompatibilityStatus::Current,
+        std::cmp::Ordering::Less => CompatibilityStatus::Older,
+        std::cmp::Ordering::Greater => CompatibilityStatus::NewerThanSupported,
+    };
+    // Only headerless saves predate the header, so only they can be migrated
+    let needs_migration = status == CompatibilityStatus::Older
+        && !prefix.starts_with(&SAVE_MAGIC)
+        && legacy::can_migrate(save_version);
+    Ok(Compatibility {
+        save_version,
+        status,
+        supported: (status == CompatibilityStatus::Current || needs_migration) && codec_ok,
+        needs_migration,
+    })
+}
+
+// ============================================================================
+// Named Save Slots
+// ============================================================================
+
+/// File extension for named save slots
+const SLOT_EXTENSION: &str = "bin";
+
+/// Metadata for a named save slot, for save-game menus
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveSlot {
+    pub name: String,
+    pub path: String,
+    pub header: SaveHeader,
+}
+
+/// Path of the slot file for `slot_name` inside `dir`
+fn slot_path(dir: &str, slot_name: &str) -> Result<String, String> {
+    if slot_name.is_empty()
+        || slot_name.contains(['/', '\\'])
+        || slot_name.starts_with('.')
+    {
+        return Err(format!("Invalid save slot name: {:?}", slot_name));
+    }
+    Ok(Path::new(dir)
+        .join(format!("{}.{}", slot_name, SLOT_EXTENSION))
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// List save slots in `dir`, newest first.
+/// A nonexistent directory yields an empty list; files without a readable header are skipped.
+pub fn list_saves(dir: &str) -> Vec<SaveSlot> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut slots: Vec<(SaveSlot, std::time::SystemTime)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SLOT_EXTENSION) {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            let path_str = path.to_string_lossy().into_owned();
+            let header = read_save_header(&path_str).ok()?;
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            Some((SaveSlot { name, path: path_str, header }, modified))
+        })
+        .collect();
+
+    slots.sort_by(|(a, a_mtime), (b, b_mtime)| {
+        newest_first((a.header.saved_at, *a_mtime), (b.header.saved_at, *b_mtime))
+    });
+    slots.into_iter().map(|(slot, _)| slot).collect()
+}
+
+/// Order two slots by `(saved_at, file mtime)`, newest first. `saved_at` has second
+/// resolution, so ties are broken by the file's mtime.
+fn newest_first(
+    a: (u64, std::time::SystemTime),
+    b: (u64, std::time::SystemTime),
+) -> std::cmp::Ordering {
+    b.0.cmp(&a.0).then(b.1.cmp(&a.1))
+}
+
+/// Stats returned after saving
+#[derive(Debug, Clone)]
+pub struct SaveStats {
+    pub population: u32,
+    pub file_bytes: u64,
+}
+
+/// Stats returned after loading
+#[derive(Debug, Clone)]
+pub struct LoadFileResult {
+    pub import_result: ImportResult,
+    pub seed: u32,
+    pub node_state_json: String,
+}
+
+const CHECKPOINT_PREFIX: &str = "checkpoint_";
+const CHECKPOINT_SUFFIX: &str = ".sav";
+
+/// Atomic write: write to `<path>.tmp` then rename over the final path
+fn write_atomic(path: &str, bytes: &[u8]) -> Result<(), String> {
+    let tmp_path = format!("{}.tmp", path);
+
+    // Ensure parent directory exists
+    if let Some(parent) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create save directory: {}", e))?;
+    }
+
+    std::fs::write(&tmp_path, bytes)
+        .map_err(|e| format!("Failed to write tmp file: {}", e))?;
+
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to rename tmp to final: {}", e))?;
+
+    Ok(())
+}
+
+/// Paths of all checkpoint files in `dir` (unsorted)
+fn list_checkpoints(dir: &str) -> Result<Vec<std::path::PathBuf>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read checkpoint directory: {}", e))?;
+
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(CHECKPOINT_PREFIX) && name.ends_with(CHECKPOINT_SUFFIX))
+                .unwrap_or(false)
+        })
+        .collect())
+}
+
+impl crate::world::SimulationWorld {
+    /// Build ExportData from current world state (shared by export_world and save_to_file)
+    pub(crate) fn build_export_data(&self) -> ExportData {
+        self.build_export_data_filtered(|_| true)
+    }
+
+    /// Build ExportData for only the people whose tile passes `include_tile`.
+    /// Links to living people outside the filter are exported as None; parents who
+    /// have died keep their id so lineage survives a save/load.
+    fn build_export_data_filtered<F: Fn(u16) -> bool>(&self, include_tile: F) -> ExportData {
+        let entity_count = self.world.len() as usize;
+        let mut people: Vec<ExportedPerson> = Vec::with_capacity(entity_count);
+        let mut households: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+
+        let included: std::collections::HashSet<u64> = self.world
+            .query::<(&Person, &TileId)>()
+            .iter()
+            .filter(|(_, (_, tile))| include_tile(tile.0))
+            .map(|(_, (person, _))| person.id.0)
+            .collect();
+        let partner_link = |id: PersonId| Some(id.0).filter(|id| included.contains(id));
+        let parent_link = |id: PersonId| Some(id.0).filter(|id| included.contains(id) || self.find_person(*id).is_none());
+
+        for (entity, (person, tile, birth, sex)) in self
+            .world
+            .query::<(&Person, &TileId, &BirthDate, &Sex)>()
+            .iter()
+        {
+            if !include_tile(tile.0) {
+                continue;
+            }
+
+            let partner_id = self.world.get::<&Partner>(entity)
+                .ok()
+                .and_then(|p| partner_link(p.1));
+
+            let mother_id = self.world.get::<&Mother>(entity)
+                .ok()
+                .and_then(|m| parent_link(m.1));
+
+            let father_id = self.world.get::<&Father>(entity)
+                .ok()
+                .and_then(|f| parent_link(f.1));
+
+            let fertility = self.world.get::<&Fertility>(entity)
+                .ok()
+                .map(|f| ExportedFertility {
+                    last_birth_year: f.last_birth_year,
+                    last_birth_month: f.last_birth_month,
+                    children_born: f.children_born,
+                });
+
+            let pregnancy = self.world.get::<&Pregnant>(entity)
+                .ok()
+                .map(|p| ExportedPregnancy {
+                    due_year: p.due_year,
+                    due_month: p.due_month,
+                    father_generation: p.father_generation,
+                });
+
+            let occupation = self.world.get::<&Occupation>(entity)
+                .ok()
+                .map(|o| (*o).into());
+
+            let health = self.world.get::<&Health>(entity)
+                .ok()
+                .map(|h| h.0);
+
+            let married_since = self.world.get::<&MarriedSince>(entity)
+                .ok()
+                .map(|m| ExportedMarriedSince {
+                    year: m.year,
+                    month: m.month,
+                });
+
+            let generation = self.world.get::<&Generation>(entity)
+                .ok()
+                .map(|g| g.0);
+
+            if let Ok(household) = self.world.get::<&Household>(entity) {
+                households.entry(household.0).or_default().push(person.id.0);
+            }
+
+            people.push(ExportedPerson {
+                person_id: person.id.0,
+                tile_id: tile.0,
+                first_name: person.first_name.clone(),
+                last_name: person.last_name.clone(),
+                sex: (*sex).into(),
+                birth_year: birth.year,
+                birth_month: birth.month,
+                birth_day: birth.day,
+                partner_id,
+                mother_id,
+                fertility,
+                pregnancy,
+                occupation,
+                health,
+                father_id,
+                married_since,
+                generation,
+            });
+        }
+
+        // Export event log (uses zero-allocation iterator)
+        let event_log = self.event_log.iter_all()
+            .map(|event| ExportedEvent {
+                event_type: event.event_type.into(),
+                year: event.year,
+                month: event.month,
+                day: event.day,
+                person_id: event.person_id,
+                tile_id: event.tile_id,
+                label: event.label.clone(),
+                cause: event.cause.map(Into::into),
+            })
+            .collect();
+
+        ExportData {
+            version: EXPORT_VERSION,
+            calendar: CalendarData {
+                year: self.calendar.year,
+                month: self.calendar.month,
+                day: self.calendar.day,
+            },
+            next_person_id: self.next_person_id,
+            people,
+            event_log,
+            fertility_config: self.fertility_config.clone(),
+            marriage_config: self.marriage_config.clone(),
+            households: households
+                .into_iter()
+                .map(|(household_id, mut members)| {
+                    members.sort_unstable();
+                    ExportedHousehold { household_id, members }
+                })
+                .collect(),
+            next_household_id: self.next_household_id,
+            mortality_config: self.mortality_config.clone(),
+            home_leaving_config: self.home_leaving_config.clone(),
+            year_stats: self.year_stats.iter().map(|(year, stats)| (*year, *stats)).collect(),
+            smooth_mortality: self.smooth_mortality,
+            max_population: self.max_population,
+            ticks_elapsed: self.ticks_elapsed,
+            start_date: Some(CalendarData {
+                year: self.start_date.year,
+                month: self.start_date.month,
+                day: self.start_date.day,
+            }),
+            rng: Some(ExportedRng {
+                world_seed: self.seed,
+                key: self.rng.get_seed(),
+                stream: self.rng.get_stream(),
+                word_pos: self.rng.get_word_pos(),
+            }),
+        }
+    }
+
+    /// Import from ExportData (shared by import_world and load_from_file)
+    pub(crate) fn import_from_export_data(&mut self, data: ExportData) -> Result<ImportResult, String> {
+        if data.version == 0 || data.version > EXPORT_VERSION {
+            return Err(format!("Unsupported export version: {}", data.version));
+        }
+        // Reject bad tunables before touching the world, as set_sim_config does
+        let config = SimConfig {
+            fertility: data.fertility_config,
+            marriage: data.marriage_config,
+            mortality: data.mortality_config,
+            home_leaving: data.home_leaving_config,
+            smooth_mortality: data.smooth_mortality,
+            max_population: data.max_population,
+        };
+        config.validate().map_err(|e| format!("Invalid config in export: {}", e))?;
+
+        self.world.clear();
+        self.clear_vitals();
+        self.calendar = Calendar::new(data.calendar.year, data.calendar.month, data.calendar.day);
+        self.start_date = data.start_date.map_or_else(Calendar::default, |start| Calendar::new(start.year, start.month, start.day));
+        let max_person_id = data.people.iter().map(|p| p.person_id).max().unwrap_or(0);
+        self.next_person_id = data.next_person_id.max(max_person_id + 1);
+        self.fertility_config = config.fertility;
+        self.marriage_config = config.marriage;
+        self.mortality_config = config.mortality;
+        self.home_leaving_config = config.home_leaving;
+        self.smooth_mortality = config.smooth_mortality;
+        self.max_population = config.max_population;
+        self.ticks_elapsed = data.ticks_elapsed;
+        self.year_stats = data.year_stats.into_iter().collect();
+
+        let mut person_id_to_entity: HashMap<u64, hecs::Entity> = HashMap::with_capacity(data.people.len());
+        let mut anomalies = 0u32;
+
+        for person in &data.people {
+            // Hand-edited or corrupt files can give men pregnancies; drop rather than import them
+            let male = person.sex == ExportedSex::Male;
+            if male {
+                anomalies += person.fertility.is_some() as u32 + person.pregnancy.is_some() as u32;
+            }
+
+            let entity = self.world.spawn((
+                Person {
+                    id: PersonId(person.person_id),
+                    first_name: person.first_name.clone(),
+                    last_name: person.last_name.clone(),
+                },
+                TileId(person.tile_id),
+                BirthDate::new(person.birth_year, person.birth_month, person.birth_day),
+                Sex::from(person.sex),
+            ));
+
+            if let Some(fert) = person.fertility.as_ref().filter(|_| !male) {
+                let _ = self.world.insert_one(entity, Fertility { // Just spawned, always valid
+                    last_birth_year: fert.last_birth_year,
+                    last_birth_month: fert.last_birth_month,
+                    children_born: fert.children_born,
+                });
+            }
+
+            // Exports predating occupations get one derived from age
+            let occupation = match person.occupation {
+                Some(occupation) => occupation.into(),
+                None => {
+                    let years = self.calendar.year.saturating_sub(person.birth_year);
+                    Occupation::for_age(years, &mut self.rng)
+                }
+            };
+            let _ = self.world.insert_one(entity, occupation); // Just spawned, always valid
+
+            if let Some(health) = person.health {
+                let _ = self.world.insert_one(entity, Health(health.min(Health::MAX))); // Just spawned, always valid
+            }
+
+            if let Some(ref married) = person.married_since {
+                let _ = self.world.insert_one(entity, MarriedSince { // Just spawned, always valid
+                    year: married.year,
+                    month: married.month,
+                });
+            }
+
+            // Exports predating generations treat everyone as a founder
+            let _ = self.world.insert_one(entity, Generation(person.generation.unwrap_or(0))); // Just spawned, always valid
+
+            if let Some(preg) = person.pregnancy.as_ref().filter(|_| !male) {
+                let _ = self.world.insert_one(entity, Pregnant { // Just spawned, always valid
+                    due_year: preg.due_year,
+                    due_month: preg.due_month,
+                    father_generation: preg.father_generation,
+                });
+            }
+
+            person_id_to_entity.insert(person.person_id, entity);
+        }
+
+        let mut partners_added = 0u32;
+        let mut mothers_added = 0u32;
+
+        for person in &data.people {
+            let entity = person_id_to_entity[&person.person_id];
+
+            if let Some(partner_pid) = person.partner_id {
+                if let Some(&partner_entity) = person_id_to_entity.get(&partner_pid) {
+                    let _ = self.world.insert_one(entity, Partner(partner_entity, PersonId(partner_pid))); // Entity just spawned above
+                    partners_added += 1;
+                }
+            }
+
+            // Parents missing from the file have died; keep their id with a dangling entity
+            if let Some(mother_pid) = person.mother_id {
+                let mother_entity = person_id_to_entity.get(&mother_pid).copied();
+                let _ = self.world.insert_one(entity, Mother(mother_entity.unwrap_or(hecs::Entity::DANGLING), PersonId(mother_pid))); // Entity just spawned above
+                if mother_entity.is_some() {
+                    mothers_added += 1;
+                }
+            }
+
+            if let Some(father_pid) = person.father_id {
+                let father_entity = person_id_to_entity.get(&father_pid).copied().unwrap_or(hecs::Entity::DANGLING);
+                let _ = self.world.insert_one(entity, Father(father_entity, PersonId(father_pid))); // Entity just spawned above
+            }
+        }
+
+        for household in &data.households {
+            for member in &household.members {
+                if let Some(&entity) = person_id_to_entity.get(member) {
+                    let _ = self.world.insert_one(entity, Household(household.household_id)); // Entity just spawned above
+                }
+            }
+        }
+        self.set_person_index(person_id_to_entity);
+        let max_household = data.households.iter().map(|h| h.household_id).max().unwrap_or(0);
+        self.next_household_id = data.next_household_id.max(max_household + 1);
+
+        // Restore event log
+        self.event_log.clear();
+        // Exported newest first; the log is stored oldest first
+        for event in data.event_log.into_iter().rev() {
+            self.event_log.push(Event {
+                event_type: event.event_type.into(),
+                year: event.year,
+                month: event.month,
+                day: event.day,
+                person_id: event.person_id,
+                tile_id: event.tile_id,
+                label: event.label,
+                cause: event.cause.map(Into::into),
+            });
+        }
+
+        // A world saved at its cap has already logged CapacityReached
+        self.capacity_reached = self.remaining_capacity() == Some(0);
+
+        // Restored last, so nothing drawn during import disturbs the saved position
+        if let Some(rng) = data.rng {
+            self.seed = rng.world_seed;
+            self.rng = SimRng::from_seed(rng.key);
+            self.rng.set_stream(rng.stream);
+            self.rng.set_word_pos(rng.word_pos);
+        }
+
+        Ok(ImportResult {
+            population: data.people.len() as u32,
+            partners: partners_added,
+            mothers: mothers_added,
+            calendar_year: self.calendar.year,
+            anomalies,
+        })
+    }
+
+    /// Save world + Node state to a bincode file (atomic write via tmp + rename)
+    pub fn save_to_file(&self, node_state_json: &str, seed: u32, path: &str) -> Result<SaveStats, String> {
+        self.snapshot().save_to_file(node_state_json, seed, path)
+    }
+
+    /// Like `save_to_file`, choosing the body layout with `format`
+    pub fn save_to_file_as(
+        &self,
+        node_state_json: &str,
+        seed: u32,
+        path: &str,
+        format: SaveFormat,
+    ) -> Result<SaveStats, String> {
+        self.snapshot().save_to_file_as(node_state_json, seed, path, format)
+    }
+
+    /// Write a rotating autosave checkpoint into `dir`.
+    ///
+    /// Files are named `checkpoint_<year>_<tick>.sav` where `<tick>` is the day of the
+    /// year, zero-padded so that name order matches chronological order. After writing,
+    /// all but the newest `keep` checkpoints in the directory are deleted.
+    /// Returns the path written.
+    pub fn autosave_checkpoint(
+        &self,
+        dir: &str,
+        keep: usize,
+        node_state_json: &str,
+        seed: u32,
+    ) -> Result<String, String> {
+        let day_of_year = self.calendar.to_absolute_days() % Calendar::DAYS_PER_YEAR as u64 + 1;
+        let file_name = format!(
+            "{}{:05}_{:03}{}",
+            CHECKPOINT_PREFIX, self.calendar.year, day_of_year, CHECKPOINT_SUFFIX
+        );
+        let path = Path::new(dir).join(file_name);
+        let path_str = path.to_string_lossy().into_owned();
+
+        self.save_to_file(node_state_json, seed, &path_str)?;
+
+        let mut checkpoints = list_checkpoints(dir)?;
+        if checkpoints.len() > keep {
+            checkpoints.sort();
+            let excess = checkpoints.len() - keep;
+            for old in checkpoints.into_iter().take(excess) {
+                std::fs::remove_file(&old)
+                    .map_err(|e| format!("Failed to remove old checkpoint: {}", e))?;
+            }
+        }
+
+        Ok(path_str)
+    }
+
+    /// Save into the named slot `slot_name` inside `dir`
+    pub fn save_slot(&self, dir: &str, slot_name: &str, node_state_json: &str, seed: u32) -> Result<SaveStats, String> {
+        let path = slot_path(dir, slot_name)?;
+        self.save_to_file(node_state_json, seed, &path)
+    }
+
+    /// Load the named slot `slot_name` from `dir`
+    pub fn load_slot(&mut self, dir: &str, slot_name: &str) -> Result<LoadFileResult, String> {
+        let path = slot_path(dir, slot_name)?;
+        self.load_from_file(&path)
+    }
+
+    /// Load world + Node state from a bincode file
+    pub fn load_from_file(&mut self, path: &str) -> Result<LoadFileResult, String> {
+        self.try_load_from_file(path).map_err(|e| e.to_string())
+    }
+
+    /// Load world + Node state from a bincode file, reporting why a load failed.
+    /// The format is checked before the body is decoded, so foreign files fail fast.
+    pub fn try_load_from_file(&mut self, path: &str) -> Result<LoadFileResult, LoadError> {
+        self.try_load_from_file_checked(path, |_| Ok(()))
+    }
+
+    /// Like `try_load_from_file`, but runs `check_node_state` on the embedded Node
+    /// state before anything is imported; if it fails the world is left untouched.
+    pub fn try_load_from_file_checked<F>(&mut self, path: &str, check_node_state: F) -> Result<LoadFileResult, LoadError>
+    where
+        F: FnOnce(&str) -> Result<(), String>,
+    {
+        use std::io::Read;
+
+        let file = std::fs::File::open(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => LoadError::NotFound,
+            _ => LoadError::Io(e.to_string()),
+        })?;
+        let mut reader = std::io::BufReader::new(file);
+        let mut data = Vec::with_capacity(SAVE_HEADER_LEN);
+        (&mut reader)
+            .take(SAVE_HEADER_LEN as u64)
+            .read_to_end(&mut data)
+            .map_err(|e| LoadError::Io(e.to_string()))?;
+
+        // Record saves are decoded as they are read; everything else needs the whole body
+        let save_file = match SaveHeader::from_bytes(&data) {
+            Some(header) if header.version == SAVE_VERSION && header.codec == SAVE_CODEC_RECORDS => {
+                read_record_body(&mut reader)?
+            }
+            _ => {
+                reader.read_to_end(&mut data).map_err(|e| LoadError::Io(e.to_string()))?;
+                read_monolithic_body(&data)?
+            }
+        };
+
+        if save_file.version != SAVE_VERSION {
+            return Err(LoadError::UnsupportedVersion(save_file.version));
+        }
+
+        let node_state_json = String::from_utf8(save_file.node_state)
+            .map_err(|_| LoadError::BadNodeState)?;
+        check_node_state(&node_state_json).map_err(LoadError::InvalidNodeState)?;
+
+        let import_result = self.import_from_export_data(save_file.ecs_data)
+            .map_err(LoadError::Corrupt)?;
+
+        Ok(LoadFileResult {
+            import_result,
+            seed: save_file.seed,
+            node_state_json,
+        })
+    }
+}
+
+/// Decode a monolithic save: a headered body or a legacy headerless one
+fn read_monolithic_body(data: &[u8]) -> Result<SaveFile, LoadError> {
+    use bincode::Options;
+
+    // Headered saves carry SAVE_MAGIC; legacy saves are a bare bincode body
+    // whose first byte is the format version
+    let body = if data.starts_with(&SAVE_MAGIC) {
+        let header = SaveHeader::from_bytes(data)
+            .ok_or_else(|| LoadError::Corrupt("truncated header".to_string()))?;
+        if header.version != SAVE_VERSION {
+            return Err(LoadError::UnsupportedVersion(header.version));
+        }
+        if header.codec != 0 && header.codec != SAVE_CODEC {
+            return Err(LoadError::UnsupportedCodec(header.codec));
+        }
+        &data[SAVE_HEADER_LEN..]
+    } else {
+        match data.first() {
+            None => return Err(LoadError::Corrupt("empty file".to_string())),
+            Some(&SAVE_VERSION) => data,
+            Some(&v) if legacy::can_migrate(v) => {
+                return legacy::migrate(save_codec().with_limit(data.len() as u64), data)
+                    .map_err(|e| LoadError::Corrupt(e.to_string()));
+            }
+            Some(&v) if (1..SAVE_VERSION).contains(&v) => return Err(LoadError::UnsupportedVersion(v)),
+            Some(_) => return Err(LoadError::BadMagic),
+        }
+    };
+
+    // Legacy headerless saves used bincode::serialize defaults, which codec 1 matches
+    save_codec()
+        .with_limit(body.len() as u64)
+        .deserialize(body)
+        .map_err(|e| LoadError::Corrupt(e.to_string()))
+}
+
+/// Decode a `SAVE_CODEC_RECORDS` body, one record at a time, up to its trailer.
+/// A missing trailer, or one whose count disagrees with the records read, means the
+/// file is damaged.
+fn read_record_body<R: std::io::Read>(reader: &mut R) -> Result<SaveFile, LoadError> {
+    use bincode::Options;
+
+    let decode_err = |e: bincode::Error| LoadError::Corrupt(e.to_string());
+    let first = read_record(reader)?
+        .filter(|record| !record.is_empty())
+        .ok_or_else(|| LoadError::Corrupt("empty body".to_string()))?;
+    let mut save_file: SaveFile = save_codec().deserialize(&first).map_err(decode_err)?;
+    loop {
+        let record = read_record(reader)?
+            .ok_or_else(|| LoadError::Corrupt("missing end of records".to_string()))?;
+        if record.is_empty() {
+            break;
+        }
+        let person: ExportedPerson = save_codec().deserialize(&record).map_err(decode_err)?;
+        save_file.ecs_data.people.push(person);
+    }
+
+    let mut count = [0u8; 4];
+    reader.read_exact(&mut count).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => LoadError::Corrupt("truncated record count".to_string()),
+        _ => LoadError::Io(e.to_string()),
+    })?;
+    let count = u32::from_le_bytes(count);
+    if save_file.ecs_data.people.len() != count as usize {
+        return Err(LoadError::Corrupt(format!(
+            "expected {} people, found {}",
+            count,
+            save_file.ecs_data.people.len()
+        )));
+    }
+    let mut rest = [0u8; 1];
+    if reader.read(&mut rest).map_err(|e| LoadError::Io(e.to_string()))? != 0 {
+        return Err(LoadError::Corrupt("data after end of records".to_string()));
+    }
+    Ok(save_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::SimulationWorld;
+
+    fn populated_world() -> SimulationWorld {
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(50, 1);
+        world.seed_population_on_tile(50, 2);
+        for _ in 0..200 {
+            world.tick();
+        }
+        world
+    }
+
+    #[test]
+    fn test_pretty_and_compact_export_roundtrip() {
+        let world = populated_world();
+        let compact = world.export_world();
+        let pretty = world.export_world_pretty();
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+
+        let from_compact = SimulationWorld::new().import_world(&compact).unwrap();
+        let from_pretty = SimulationWorld::new().import_world(&pretty).unwrap();
+        assert_eq!(from_compact, from_pretty);
+    }
+
+    #[test]
+    fn test_import_keeps_event_log_order() {
+        // Exports list events newest first while the log stores them oldest first, so
+        // import has to reverse them; otherwise every round-trip flips the log, and
+        // `prune_before` and the newest-first queries work on the wrong end
+        let world = populated_world();
+        let json = world.export_world();
+        let mut reloaded = SimulationWorld::new();
+        reloaded.import_world(&json).unwrap();
+
+        let date = |e: &crate::components::Event| (e.year, e.month, e.day);
+        assert_eq!(reloaded.event_log.iter_all().next().map(date), world.event_log.iter_all().next().map(date));
+        assert_eq!(reloaded.event_log.iter_all().last().map(date), world.event_log.iter_all().last().map(date));
+        let events = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap()["event_log"].clone();
+        assert_eq!(events(&reloaded.export_world()), events(&json));
+    }
+
+    #[test]
+    fn test_export_region_drops_cross_tile_links() {
+        use crate::components::{Partner, Person, PersonId};
+
+        let mut world = SimulationWorld::new();
+        world.immigrate(1, 1, 25, 25);
+        world.immigrate(2, 1, 25, 25);
+        world.immigrate(1, 1, 25, 25);
+        let entities: Vec<(hecs::Entity, u64)> = world.world
+            .query::<&Person>()
+            .iter()
+            .map(|(e, p)| (e, p.id.0))
+            .collect();
+        let find = |id: u64| entities.iter().find(|(_, pid)| *pid == id).unwrap().0;
+        // Person 1 (tile 1) partnered with person 2 (tile 2)
+        world.world.insert_one(find(1), Partner(find(2), PersonId(2))).unwrap();
+        world.world.insert_one(find(2), Partner(find(1), PersonId(1))).unwrap();
+
+        let data: super::ExportData = serde_json::from_str(&world.export_region(&[1])).unwrap();
+        let mut ids: Vec<u64> = data.people.iter().map(|p| p.person_id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 3]);
+        assert!(data.people.iter().all(|p| p.partner_id.is_none()));
+
+        // Full export keeps the link
+        let full: super::ExportData = serde_json::from_str(&world.export_world()).unwrap();
+        let person_1 = full.people.iter().find(|p| p.person_id == 1).unwrap();
+        assert_eq!(person_1.partner_id, Some(2));
+    }
+
+    #[test]
+    fn test_autosave_checkpoint_rotation() {
+        let dir = std::env::temp_dir().join(format!("gridworld_checkpoints_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir_str = dir.to_string_lossy().into_owned();
+
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(20, 1);
+
+        let mut written = Vec::new();
+        for _ in 0..5 {
+            written.push(world.autosave_checkpoint(&dir_str, 3, "{}", 42).unwrap());
+            world.tick();
+        }
+
+        let mut remaining = super::list_checkpoints(&dir_str).unwrap();
+        remaining.sort();
+        let remaining: Vec<String> = remaining
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(remaining, written[2..].to_vec());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_read_save_header_matches_world() {
+        let dir = std::env::temp_dir().join(format!("gridworld_header_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+
+        let world = populated_world();
+        world.save_to_file("{}", 777, &path).unwrap();
+
+        let header = super::read_save_header(&path).unwrap();
+        assert_eq!(header.population, world.entity_count() as u32);
+        assert_eq!(header.year, world.calendar.year);
+        assert_eq!(header.month, world.calendar.month);
+        assert_eq!(header.day, world.calendar.day);
+        assert_eq!(header.seed, 777);
+        assert!(header.saved_at > 0);
+
+        // Full load still works with the header present
+        let mut loaded = SimulationWorld::new();
+        let result = loaded.load_from_file(&path).unwrap();
+        assert_eq!(result.import_result.population, header.population);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sim_config_survives_save_and_load() {
+        use crate::config::{MarriageRelocation, MortalityTable, SimConfig};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_sim_config_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+
+        let mut world = populated_world();
+        let mut config = SimConfig::default();
+        config.fertility.min_birth_interval_months = 30;
+        config.fertility.stillbirth_chance = 0.05;
+        config.marriage.max_age_gap = 8;
+        config.marriage.relocation = MarriageRelocation::HusbandMoves;
+        config.mortality.female = Some(MortalityTable { neonatal_rate: 0.1, brackets: vec![(5, 0.004)] });
+        config.smooth_mortality = true;
+        config.max_population = Some(5_000);
+        world.set_sim_config(config.clone()).unwrap();
+        world.save_to_file("{}", 1, &path).unwrap();
+
+        let mut loaded = SimulationWorld::new();
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(loaded.sim_config(), config);
+
+        // Invalid configs are rejected whole
+        let mut invalid = config.clone();
+        invalid.mortality.shared.neonatal_rate = 2.0;
+        invalid.max_population = None;
+        assert!(loaded.set_sim_config(invalid).is_err());
+        assert_eq!(loaded.max_population, Some(5_000));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_import_drops_pregnancy_from_men() {
+        use super::{ExportedFertility, ExportedPregnancy, ExportedSex};
+        use crate::components::{Fertility, Pregnant, Sex};
+
+        let mut data = populated_world().snapshot();
+        let man = data.people.iter_mut().find(|p| p.sex == ExportedSex::Male).unwrap();
+        man.pregnancy = Some(ExportedPregnancy { due_year: 4001, due_month: 2, father_generation: None });
+        man.fertility = Some(ExportedFertility { last_birth_year: 3999, last_birth_month: 1, children_born: 1 });
+        let women_pregnant = data.people.iter().filter(|p| p.pregnancy.is_some()).count() - 1;
+
+        let mut world = SimulationWorld::new();
+        let result = world.import_world(&data.to_json()).unwrap();
+        assert_eq!(result.anomalies, 2);
+        assert_eq!(result.population as usize, data.people.len());
+        assert_eq!(world.world.query::<&Pregnant>().iter().count(), women_pregnant);
+        assert!(world.world.query::<(&Sex, Option<&Pregnant>, Option<&Fertility>)>().iter()
+            .all(|(_, (sex, pregnant, fertility))| *sex == Sex::Female || (pregnant.is_none() && fertility.is_none())));
+
+        // Clean exports report nothing
+        assert_eq!(SimulationWorld::new().import_world(&world.snapshot().to_json()).unwrap().anomalies, 0);
+    }
+
+    #[test]
+    fn test_import_json_without_event_log() {
+        let world = populated_world();
+        let mut json: serde_json::Value = serde_json::from_str(&world.snapshot().to_json()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        assert!(fields.remove("event_log").is_some());
+        assert!(fields.remove("next_person_id").is_some());
+
+        let mut restored = SimulationWorld::new();
+        let result = restored.import_world(&json.to_string()).unwrap();
+        assert_eq!(result.population, world.entity_count() as u32);
+        assert_eq!(restored.event_log.len(), 0);
+        let max_id = world.snapshot().people.iter().map(|p| p.person_id).max().unwrap();
+        assert_eq!(restored.next_person_id, max_id + 1);
+    }
+
+    #[test]
+    fn test_import_and_load_reject_invalid_config() {
+        let mut world = populated_world();
+        let mut json: serde_json::Value = serde_json::from_str(&world.snapshot().to_json()).unwrap();
+        json["mortality_config"]["shared"]["brackets"] = serde_json::json!([]);
+
+        let mut target = populated_world();
+        let population = target.entity_count();
+        assert!(target.import_world(&json.to_string()).is_err());
+        assert_eq!(target.entity_count(), population);
+
+        let dir = std::env::temp_dir().join(format!("gridworld_bad_config_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+        world.mortality_config.shared.neonatal_rate = f64::NAN;
+        world.save_to_file("{}", 1, &path).unwrap();
+        assert!(target.load_from_file(&path).is_err());
+        assert_eq!(target.entity_count(), population);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_loaded_world_continues_the_same_run() {
+        let dir = std::env::temp_dir().join(format!("gridworld_continue_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+
+        let mut world = SimulationWorld::with_seed(77);
+        world.immigrate(1, 300, 16, 30);
+        for _ in 0..200 {
+            world.tick();
+        }
+        world.save_to_file("{}", 1, &path).unwrap();
+
+        let mut loaded = SimulationWorld::with_seed(1);
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(loaded.seed, 77);
+        for _ in 0..50 {
+            let (expected, actual) = (world.tick(), loaded.tick());
+            assert_eq!(
+                (expected.births, expected.deaths, expected.marriages, expected.pregnancies, expected.population),
+                (actual.births, actual.deaths, actual.marriages, actual.pregnancies, actual.population)
+            );
+        }
+
+        let ids = |w: &SimulationWorld| {
+            let mut people: Vec<(u64, String, u16)> = w.snapshot().people.into_iter().map(|p| (p.person_id, p.first_name, p.tile_id)).collect();
+            people.sort();
+            people
+        };
+        assert_eq!(ids(&loaded), ids(&world));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_list_saves_newest_first() {
+        let dir = std::env::temp_dir().join(format!("gridworld_slots_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir_str = dir.to_string_lossy().into_owned();
+
+        assert!(super::list_saves(&dir_str).is_empty());
+
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(10, 1);
+        // Saves within the same second tie on saved_at; pin the mtimes that break the tie
+        for (i, name) in ["alpha", "beta", "gamma"].into_iter().enumerate() {
+            world.save_slot(&dir_str, name, "{}", 1).unwrap();
+            world.tick();
+            let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000 + i as u64);
+            let path = dir.join(format!("{}.{}", name, super::SLOT_EXTENSION));
+            std::fs::File::options().write(true).open(path).unwrap().set_modified(mtime).unwrap();
+        }
+
+        let names: Vec<String> = super::list_saves(&dir_str).into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["gamma", "beta", "alpha"]);
+
+        let result = SimulationWorld::new().load_slot(&dir_str, "beta").unwrap();
+        assert_eq!(result.import_result.population, 10);
+        assert!(world.save_slot(&dir_str, "../escape", "{}", 1).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_slot_order_breaks_saved_at_ties_by_mtime() {
+        use std::cmp::Ordering;
+        use std::time::{Duration, UNIX_EPOCH};
+        let (early, late) = (UNIX_EPOCH + Duration::from_secs(10), UNIX_EPOCH + Duration::from_secs(20));
+
+        assert_eq!(super::newest_first((5, early), (5, late)), Ordering::Greater);
+        assert_eq!(super::newest_first((5, late), (5, early)), Ordering::Less);
+        // A later saved_at wins regardless of mtime
+        assert_eq!(super::newest_first((6, early), (5, late)), Ordering::Less);
+        assert_eq!(super::newest_first((5, early), (5, early)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_load_legacy_headerless_save() {
+        let dir = std::env::temp_dir().join(format!("gridworld_legacy_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("legacy.bin").to_string_lossy().into_owned();
+
+        let world = populated_world();
+        let legacy = super::SaveFile {
+            version: super::SAVE_VERSION,
+            seed: 5,
+            ecs_data: world.build_export_data(),
+            node_state: b"{}".to_vec(),
+        };
+        std::fs::write(&path, bincode::serialize(&legacy).unwrap()).unwrap();
+
+        assert!(super::read_save_header(&path).is_err());
+        let result = SimulationWorld::new().load_from_file(&path).unwrap();
+        assert_eq!(result.import_result.population, world.entity_count() as u32);
+        assert_eq!(result.seed, 5);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_ticks_proceed_while_snapshot_serializes() {
+        use std::sync::{Arc, Barrier, Mutex};
+
+        let mut world = SimulationWorld::with_seed(8);
+        for tile in 0..20 {
+            world.seed_population_on_tile(500, tile);
+        }
+        let start_day = world.calendar.to_absolute_days();
+        let shared = Arc::new(Mutex::new(world));
+        let started = Arc::new(Barrier::new(2));
+
+        let serializer = {
+            let shared = Arc::clone(&shared);
+            let started = Arc::clone(&started);
+            std::thread::spawn(move || {
+                let snapshot = shared.lock().unwrap().snapshot();
+                started.wait();
+                snapshot.to_json_pretty()
+            })
+        };
+
+        // The lock is free again as soon as the snapshot is taken
+        started.wait();
+        for _ in 0..10 {
+            shared.lock().unwrap().tick();
+        }
+        assert_eq!(shared.lock().unwrap().calendar.to_absolute_days(), start_day + 10);
+
+        // The serialized state is the one captured before the ticks
+        let json = serializer.join().unwrap();
+        let restored = SimulationWorld::new().import_world(&json).unwrap();
+        assert_eq!(restored.population, 10_000);
+        let data: super::ExportData = serde_json::from_str(&json).unwrap();
+        let cal = crate::components::Calendar::new(data.calendar.year, data.calendar.month, data.calendar.day);
+        assert_eq!(cal.to_absolute_days(), start_day);
+    }
+
+    #[test]
+    fn test_households_roundtrip() {
+        let world = populated_world();
+        let exported = world.snapshot();
+        assert!(!exported.households.is_empty());
+
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&exported.to_json()).unwrap();
+        let reexported = restored.snapshot();
+        assert_eq!(reexported.households, exported.households);
+        assert_eq!(restored.next_household_id, world.next_household_id);
+
+        // Couples still share a household after import
+        use crate::components::{Household, Partner};
+        for (_, (partner, household)) in restored.world.query::<(&Partner, &Household)>().iter() {
+            assert_eq!(*restored.world.get::<&Household>(partner.0).unwrap(), *household);
+        }
+    }
+
+    #[test]
+    fn test_married_since_set_at_marriage_and_saved() {
+        use crate::components::{MarriedSince, Partner, Person};
+
+        let married = |world: &SimulationWorld| {
+            let mut couples: Vec<(u64, MarriedSince)> = world.world
+                .query::<(&Person, &MarriedSince)>()
+                .with::<&Partner>()
+                .iter()
+                .map(|(_, (person, since))| (person.id.0, *since))
+                .collect();
+            couples.sort_by_key(|(id, _)| *id);
+            couples
+        };
+
+        let mut world = SimulationWorld::with_seed(6);
+        world.immigrate(1, 20, 20, 24);
+        let start = world.calendar.clone();
+        world.tick();
+        let couples = married(&world);
+        assert!(!couples.is_empty());
+        assert_eq!(couples.len(), world.world.query::<&Partner>().iter().count());
+        assert!(couples.iter().all(|(_, since)| since.year == start.year && since.month == start.month));
+
+        let dir = std::env::temp_dir().join(format!("gridworld_married_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+        world.save_to_file("{}", 1, &path).unwrap();
+        let mut loaded = SimulationWorld::new();
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(married(&loaded), couples);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_diff_birth_and_relocation() {
+        let mut world = SimulationWorld::with_seed(4);
+        let ids = world.immigrate(1, 4, 20, 30);
+        let before = world.build_export_data();
+
+        let born = world.immigrate(1, 1, 0, 0)[0];
+        world.remap_tiles(&std::collections::HashMap::from([(1, 2)]));
+        let after = world.build_export_data();
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.born, vec![born]);
+        assert!(diff.died.is_empty());
+        let moved: Vec<(u64, u16, u16)> = ids.iter().map(|id| (*id, 1, 2)).collect();
+        assert_eq!(diff.relocations, moved);
+
+        // Reversed, the newcomer disappears and everyone moves back
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.died, vec![born]);
+        assert_eq!(reverse.relocations.len(), ids.len());
+        assert!(reverse.new_partnerships.is_empty());
+    }
+
+    #[test]
+    fn test_save_codec_is_byte_stable() {
+        use super::*;
+        use bincode::Options;
+
+        let person = ExportedPerson {
+            person_id: 2,
+            tile_id: 7,
+            first_name: "Ada".to_string(),
+            last_name: "Ng".to_string(),
+            sex: ExportedSex::Female,
+            birth_year: 3980,
+            birth_month: 3,
+            birth_day: 4,
+            partner_id: None,
+            mother_id: None,
+            fertility: None,
+            pregnancy: None,
+            occupation: None,
+            health: None,
+            father_id: None,
+            married_since: None,
+            generation: None,
+        };
+        let mut data = SimulationWorld::with_seed(1).snapshot();
+        data.next_person_id = 3;
+        data.people = vec![person];
+        let save_file = SaveFile { version: SAVE_VERSION, seed: 42, ecs_data: data, node_state: Vec::new() };
+
+        let bytes = save_codec().serialize(&save_file).unwrap();
+        let mut expected = vec![SAVE_VERSION, 42, 0, 0, 0, EXPORT_VERSION];
+        expected.extend_from_slice(&[0xa0, 0x0f, 1, 1]); // calendar 4000-1-1
+        expected.extend_from_slice(&3u64.to_le_bytes()); // next_person_id
+        expected.extend_from_slice(&1u64.to_le_bytes()); // people.len()
+        expected.extend_from_slice(&2u64.to_le_bytes()); // person_id
+        expected.extend_from_slice(&[7, 0]); // tile_id
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(b"Ada");
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(b"Ng");
+        expected.extend_from_slice(&1u32.to_le_bytes()); // ExportedSex::Female
+        assert_eq!(&bytes[..expected.len()], &expected[..]);
+
+        // Matches what headerless saves were written with
+        assert_eq!(bytes, bincode::serialize(&save_file).unwrap());
+    }
+
+    #[test]
+    fn test_try_load_reports_error_kind() {
+        use super::{LoadError, SaveHeader, SAVE_VERSION};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_corrupt_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let load = |p: &str| SimulationWorld::new().try_load_from_file(p).unwrap_err();
+
+        assert_eq!(load(&path("missing.bin")), LoadError::NotFound);
+
+        std::fs::write(path("empty.bin"), b"").unwrap();
+        assert!(matches!(load(&path("empty.bin")), LoadError::Corrupt(_)));
+
+        std::fs::write(path("random.bin"), b"\x9c\x13\xf0 definitely not a save").unwrap();
+        assert_eq!(load(&path("random.bin")), LoadError::BadMagic);
+
+        let header = SaveHeader {
+            version: SAVE_VERSION,
+            codec: super::SAVE_CODEC,
+            population: 10,
+            year: 1,
+            month: 1,
+            day: 1,
+            seed: 0,
+            saved_at: 0,
+        };
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend_from_slice(&[SAVE_VERSION, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+        std::fs::write(path("bad_body.bin"), &bytes).unwrap();
+        assert!(matches!(load(&path("bad_body.bin")), LoadError::Corrupt(_)));
+
+        bytes[5] = 9;
+        std::fs::write(path("bad_codec.bin"), &bytes).unwrap();
+        assert_eq!(load(&path("bad_codec.bin")), LoadError::UnsupportedCodec(9));
+
+        // Truncating a real save must fail cleanly rather than panic
+        let world = populated_world();
+        world.save_to_file("{}", 0, &path("good.bin")).unwrap();
+        let good = std::fs::read(path("good.bin")).unwrap();
+        std::fs::write(path("truncated.bin"), &good[..good.len() / 2]).unwrap();
+        assert!(matches!(load(&path("truncated.bin")), LoadError::Corrupt(_)));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_save_compatibility_from_header() {
+        use super::{save_compatibility, CompatibilityStatus, SaveHeader, SAVE_VERSION};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_compat_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        populated_world().save_to_file("{}", 0, &path("current.bin")).unwrap();
+        let current = save_compatibility(&path("current.bin")).unwrap();
+        assert_eq!(current.save_version, SAVE_VERSION);
+        assert_eq!(current.status, CompatibilityStatus::Current);
+        assert!(current.supported && !current.needs_migration);
+
+        let header = |version: u8| {
+            SaveHeader { version, codec: super::SAVE_CODEC, population: 0, year: 1, month: 1, day: 1, seed: 0, saved_at: 0 }
+        };
+        std::fs::write(path("future.bin"), header(99).to_bytes()).unwrap();
+        let future = save_compatibility(&path("future.bin")).unwrap();
+        assert_eq!(future.save_version, 99);
+        assert_eq!(future.status, CompatibilityStatus::NewerThanSupported);
+        assert!(!future.supported && !future.needs_migration);
+
+        // No released save had both a header and an older version, so there's no migration
+        std::fs::write(path("old.bin"), header(SAVE_VERSION - 1).to_bytes()).unwrap();
+        let old = save_compatibility(&path("old.bin")).unwrap();
+        assert_eq!(old.status, CompatibilityStatus::Older);
+        assert!(!old.supported && !old.needs_migration);
+
+        // Legacy headerless saves are identified by their leading version byte
+        std::fs::write(path("legacy.bin"), [1, 0, 0]).unwrap();
+        let legacy = save_compatibility(&path("legacy.bin")).unwrap();
+        assert_eq!(legacy.status, CompatibilityStatus::Older);
+        assert!(legacy.supported && legacy.needs_migration);
+        std::fs::write(path("junk.bin"), b"\x9c not a save").unwrap();
+        assert!(save_compatibility(&path("junk.bin")).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_version_1_save_is_migrated() {
+        use super::legacy::{CalendarV1, EventTypeV1, EventV1, ExportDataV1, FertilityV1, PersonV1, PregnancyV1, SaveFileV1, SexV1};
+        use crate::components::{Fertility, Partner, Pregnant};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_v1_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("v1.bin").to_string_lossy().into_owned();
+
+        let person = |person_id: u64, sex: SexV1, partner_id: Option<u64>, mother_id: Option<u64>| PersonV1 {
+            person_id,
+            tile_id: 3,
+            first_name: format!("P{}", person_id),
+            last_name: "Old".to_string(),
+            sex,
+            birth_year: 80,
+            birth_month: 2,
+            birth_day: 3,
+            partner_id,
+            mother_id,
+            fertility: None,
+            pregnancy: None,
+        };
+        let mut mother = person(1, SexV1::Female, Some(2), None);
+        mother.fertility = Some(FertilityV1 { last_birth_year: 98, last_birth_month: 4, children_born: 1 });
+        mother.pregnancy = Some(PregnancyV1 { due_year: 100, due_month: 9 });
+        let mut child = person(3, SexV1::Male, None, Some(1));
+        child.birth_year = 98;
+        let v1 = SaveFileV1 {
+            version: 1,
+            seed: 77,
+            ecs_data: ExportDataV1 {
+                version: 1,
+                calendar: CalendarV1 { year: 100, month: 3, day: 4 },
+                next_person_id: 4,
+                people: vec![mother, person(2, SexV1::Male, Some(1), None), child],
+                event_log: vec![EventV1 { event_type: EventTypeV1::Marriage, year: 90, month: 1, day: 1, person_id: None }],
+            },
+            node_state: b"{\"families\":[]}".to_vec(),
+        };
+        std::fs::write(&path, bincode::serialize(&v1).unwrap()).unwrap();
+
+        let mut world = SimulationWorld::new();
+        let result = world.load_from_file(&path).unwrap();
+        assert_eq!(result.seed, 77);
+        assert_eq!(result.node_state_json, "{\"families\":[]}");
+        assert_eq!((result.import_result.population, result.import_result.partners, result.import_result.mothers), (3, 2, 1));
+        assert_eq!((world.calendar.year, world.calendar.month, world.calendar.day), (100, 3, 4));
+        assert_eq!(world.next_person_id, 4);
+        assert_eq!(world.event_log.iter_by_type(crate::components::EventType::Marriage).count(), 1);
+        let mother = world.find_person(1).unwrap();
+        assert_eq!(world.world.get::<&Fertility>(mother).unwrap().children_born, 1);
+        assert!(world.world.get::<&Pregnant>(mother).is_ok());
+        assert!(world.world.get::<&Partner>(mother).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_format_matches_monolithic() {
+        use super::{read_save_header, save_compatibility, LoadError, SaveFormat, SAVE_CODEC_RECORDS};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_records_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        let mut world = SimulationWorld::with_seed(12);
+        world.seed_population_on_tile(100_000, 1);
+        world.tick();
+        world.save_to_file_as("{\"k\":1}", 5, &path("whole.bin"), SaveFormat::Monolithic).unwrap();
+        let stats = world.save_to_file_as("{\"k\":1}", 5, &path("records.bin"), SaveFormat::Records).unwrap();
+        assert_eq!(stats.population as usize, world.entity_count());
+
+        let header = read_save_header(&path("records.bin")).unwrap();
+        assert_eq!(header.codec, SAVE_CODEC_RECORDS);
+        assert!(save_compatibility(&path("records.bin")).unwrap().supported);
+
+        let mut from_whole = SimulationWorld::new();
+        let whole = from_whole.load_from_file(&path("whole.bin")).unwrap();
+        let mut from_records = SimulationWorld::new();
+        let records = from_records.load_from_file(&path("records.bin")).unwrap();
+        assert_eq!(records.import_result, whole.import_result);
+        assert_eq!((records.seed, records.node_state_json), (5, "{\"k\":1}".to_string()));
+        assert_eq!(from_records.entity_count(), world.entity_count());
+        assert_eq!(from_records.export_world(), from_whole.export_world());
+
+        // A record cut short is corrupt, not a smaller world
+        let bytes = std::fs::read(path("records.bin")).unwrap();
+        std::fs::write(path("cut.bin"), &bytes[..bytes.len() - 3]).unwrap();
+        let err = SimulationWorld::new().try_load_from_file(&path("cut.bin")).unwrap_err();
+        assert!(matches!(err, LoadError::Corrupt(_)));
+
+        // So is one cut cleanly between records
+        let mut record_starts = Vec::new();
+        let mut at = super::SAVE_HEADER_LEN;
+        while at < bytes.len() - super::RECORD_TRAILER_LEN {
+            record_starts.push(at);
+            at += 4 + u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+        }
+        std::fs::write(path("short.bin"), &bytes[..*record_starts.last().unwrap()]).unwrap();
+        let err = SimulationWorld::new().try_load_from_file(&path("short.bin")).unwrap_err();
+        assert_eq!(err, LoadError::Corrupt("missing end of records".to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_save_accepts_appended_people() {
+        use super::{append_records, read_save_header, SaveFormat};
+
+        let dir = std::env::temp_dir().join(format!("gridworld_append_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("records.bin").to_string_lossy().into_owned();
+
+        let world = populated_world();
+        let stats = world.save_to_file_as("{}", 1, &path, SaveFormat::Records).unwrap();
+
+        let mut newcomer = world.snapshot().people.remove(0);
+        newcomer.person_id = world.next_person_id + 10;
+        newcomer.partner_id = None;
+        newcomer.married_since = None;
+        newcomer.pregnancy = None;
+        assert_eq!(append_records(&path, &[newcomer]).unwrap(), stats.population + 1);
+        assert_eq!(read_save_header(&path).unwrap().population, stats.population + 1);
+
+        let mut loaded = SimulationWorld::new();
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(loaded.entity_count(), world.entity_count() + 1);
+        assert_eq!(loaded.next_person_id, world.next_person_id + 11);
+
+        // Appending twice keeps a single valid trailer
+        append_records(&path, &[]).unwrap();
+        assert_eq!(SimulationWorld::new().load_from_file(&path).unwrap().import_result.population, stats.population + 1);
+
+        // Monolithic saves have no records to extend
+        world.save_to_file("{}", 1, &path).unwrap();
+        assert!(append_records(&path, &[]).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_reseeded_branches_diverge() {
+        let dir = std::env::temp_dir().join(format!("gridworld_reseed_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("branch.bin").to_string_lossy().into_owned();
+        populated_world().save_to_file("{}", 0, &path).unwrap();
+
+        let branch = |seed: u64| {
+            let mut world = SimulationWorld::new();
+            world.load_from_file(&path).unwrap();
+            world.reseed(seed);
+            assert_eq!(world.seed, seed);
+            for _ in 0..300 {
+                world.tick();
+            }
+            serde_json::to_string(&world.snapshot().people).unwrap()
+        };
+
+        let (a, b, a_again) = (branch(1), branch(2), branch(1));
+        assert_ne!(a, b);
+        assert_eq!(a, a_again);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_roundtrip_self_check() {
+        let world = populated_world();
+        assert!(world.world.query::<&crate::components::Partner>().iter().count() > 0);
+        world.assert_roundtrip().unwrap();
+
+        // An export that lost someone along the way is caught
+        let mut corrupted = world.snapshot();
+        corrupted.people.pop();
+        let err = world.verify_reimport(&corrupted.to_json()).unwrap_err();
+        assert!(err.contains("population"), "{}", err);
+        assert!(err.contains("tiles"), "{}", err);
+
+        let mut moved = world.snapshot();
+        moved.people[0].tile_id = 999;
+        let err = world.verify_reimport(&moved.to_json()).unwrap_err();
+        assert!(err.contains("tiles") && !err.contains("population"), "{}", err);
+    }
+}
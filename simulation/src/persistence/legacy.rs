@@ -0,0 +1,177 @@
+//! Frozen save layouts from released versions, and their migrations
+//!
+//! Bincode is not self-describing, so a save can only be decoded with the exact
+//! structs that wrote it. These copies must never change; each one is converted
+//! into the current `ExportData` and imported like an old JSON export.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CalendarData, ExportData, ExportedEvent, ExportedEventType, ExportedFertility, ExportedPerson,
+    ExportedPregnancy, ExportedSex, SaveFile,
+};
+
+/// Save format version 1: headerless bincode, the first released layout
+pub(super) const V1: u8 = 1;
+
+/// Whether `version` is an older save format that `migrate` can read
+pub(super) fn can_migrate(version: u8) -> bool {
+    version == V1
+}
+
+/// `SaveFile` as written by version 1
+#[derive(Serialize, Deserialize)]
+pub(super) struct SaveFileV1 {
+    pub version: u8,
+    pub seed: u32,
+    pub ecs_data: ExportDataV1,
+    pub node_state: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct ExportDataV1 {
+    pub version: u8,
+    pub calendar: CalendarV1,
+    pub next_person_id: u64,
+    pub people: Vec<PersonV1>,
+    pub event_log: Vec<EventV1>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct CalendarV1 {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct PersonV1 {
+    pub person_id: u64,
+    pub tile_id: u16,
+    pub first_name: String,
+    pub last_name: String,
+    pub sex: SexV1,
+    pub birth_year: u16,
+    pub birth_month: u8,
+    pub birth_day: u8,
+    pub partner_id: Option<u64>,
+    pub mother_id: Option<u64>,
+    pub fertility: Option<FertilityV1>,
+    pub pregnancy: Option<PregnancyV1>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) enum SexV1 {
+    Male,
+    Female,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct FertilityV1 {
+    pub last_birth_year: u16,
+    pub last_birth_month: u8,
+    pub children_born: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct PregnancyV1 {
+    pub due_year: u16,
+    pub due_month: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct EventV1 {
+    pub event_type: EventTypeV1,
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub person_id: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) enum EventTypeV1 {
+    Birth,
+    Death,
+    Marriage,
+    PregnancyStarted,
+    Dissolution,
+}
+
+/// Decode a version-1 body with `codec` and convert it to the current schema.
+/// Everything added since takes the same defaults as in a version-1 JSON export.
+pub(super) fn migrate<O: bincode::Options>(codec: O, body: &[u8]) -> Result<SaveFile, bincode::Error> {
+    let old: SaveFileV1 = codec.deserialize(body)?;
+    let data = old.ecs_data;
+    Ok(SaveFile {
+        version: super::SAVE_VERSION,
+        seed: old.seed,
+        ecs_data: ExportData {
+            version: data.version,
+            calendar: CalendarData { year: data.calendar.year, month: data.calendar.month, day: data.calendar.day },
+            next_person_id: data.next_person_id,
+            people: data.people.into_iter().map(migrate_person).collect(),
+            event_log: data.event_log.into_iter().map(migrate_event).collect(),
+            fertility_config: Default::default(),
+            marriage_config: Default::default(),
+            households: Vec::new(),
+            next_household_id: 0,
+            mortality_config: Default::default(),
+            year_stats: Default::default(),
+            smooth_mortality: false,
+            max_population: None,
+            ticks_elapsed: 0,
+            rng: None,
+            start_date: None,
+            home_leaving_config: Default::default(),
+        },
+        node_state: old.node_state,
+    })
+}
+
+fn migrate_person(p: PersonV1) -> ExportedPerson {
+    ExportedPerson {
+        person_id: p.person_id,
+        tile_id: p.tile_id,
+        first_name: p.first_name,
+        last_name: p.last_name,
+        sex: match p.sex {
+            SexV1::Male => ExportedSex::Male,
+            SexV1::Female => ExportedSex::Female,
+        },
+        birth_year: p.birth_year,
+        birth_month: p.birth_month,
+        birth_day: p.birth_day,
+        partner_id: p.partner_id,
+        mother_id: p.mother_id,
+        fertility: p.fertility.map(|f| ExportedFertility {
+            last_birth_year: f.last_birth_year,
+            last_birth_month: f.last_birth_month,
+            children_born: f.children_born,
+        }),
+        pregnancy: p.pregnancy.map(|p| ExportedPregnancy { due_year: p.due_year, due_month: p.due_month, father_generation: None }),
+        occupation: None,
+        health: None,
+        father_id: None,
+        married_since: None,
+        generation: None,
+    }
+}
+
+fn migrate_event(e: EventV1) -> ExportedEvent {
+    ExportedEvent {
+        event_type: match e.event_type {
+            EventTypeV1::Birth => ExportedEventType::Birth,
+            EventTypeV1::Death => ExportedEventType::Death,
+            EventTypeV1::Marriage => ExportedEventType::Marriage,
+            EventTypeV1::PregnancyStarted => ExportedEventType::PregnancyStarted,
+            EventTypeV1::Dissolution => ExportedEventType::Dissolution,
+        },
+        year: e.year,
+        month: e.month,
+        day: e.day,
+        person_id: e.person_id,
+        tile_id: None,
+        label: None,
+        cause: None,
+    }
+}
@@ -1,11 +1,98 @@
-//! ECS Systems - process entities each tick
-//!
-//! Add system modules here as you implement them.
-
-pub mod death;
-pub mod matchmaking;
-pub mod family;
-
-pub use death::death_system;
-pub use matchmaking::matchmaking_system;
-pub use family::{family_system, FamilyResult};
+//! ECS Systems - process entities each tick
+//!
+//! Add system modules here as you implement them.
+
+pub mod death;
+pub mod matchmaking;
+pub mod family;
+pub mod occupation;
+pub mod health;
+pub mod leaving_home;
+
+pub use death::{death_system, DeathRecord};
+pub use matchmaking::{matchmaking_system, MarriageRecord};
+pub use family::{dissolution_system, family_system, fertility_status, BirthRecord, FamilyResult, FertilityStatus};
+pub use occupation::occupation_system;
+pub use health::health_system;
+pub use leaving_home::leaving_home_system;
+
+use rand::RngCore;
+
+/// Order a system's candidates by person id before it draws for them. ECS iteration
+/// order changes as entities move between archetypes (imports, component edits), so
+/// drawing in query order would let the same seed give different outcomes.
+pub(crate) fn sort_by_person_id<T>(candidates: &mut [T], person_id: impl Fn(&T) -> u64) {
+    candidates.sort_by_key(|candidate| person_id(candidate));
+}
+
+/// RNG values each system drew during one tick, for comparing runs that diverge.
+/// Counts only, so two audits can be compared without the cost of logging values.
+#[derive(serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RngAudit {
+    pub death: u64,
+    pub pregnancy: u64,
+    pub delivery: u64,
+    pub leaving_home: u64,
+    pub matchmaking: u64,
+    pub occupation: u64,
+}
+
+/// Draws made so far by an RNG handed to a system: `CountingRng` reports its count,
+/// and a plain RNG, which `tick` passes when the audit is off, reports 0
+pub trait DrawCount {
+    fn draws(&self) -> u64 {
+        0
+    }
+}
+
+impl DrawCount for crate::world::SimRng {}
+
+impl<R: RngCore> DrawCount for CountingRng<'_, R> {
+    fn draws(&self) -> u64 {
+        self.draws
+    }
+}
+
+/// Passes draws through to `inner`, counting them. The stream is unchanged, so wrapping
+/// an RNG never alters outcomes.
+pub(crate) struct CountingRng<'a, R: RngCore> {
+    inner: &'a mut R,
+    pub draws: u64,
+}
+
+impl<'a, R: RngCore> CountingRng<'a, R> {
+    pub fn new(inner: &'a mut R) -> Self {
+        Self { inner, draws: 0 }
+    }
+}
+
+impl<R: RngCore> RngCore for CountingRng<'_, R> {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += 1;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draws += 1;
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+/// Chance of at least one occurrence over `days` days, given a daily probability
+pub(crate) fn over_days(daily: f64, days: u32) -> f64 {
+    if days == 1 {
+        daily
+    } else {
+        1.0 - (1.0 - daily).powi(days as i32)
+    }
+}
@@ -1,73 +1,227 @@
-//! Death System
-//! 
-//! Determines mortality based on age and despawns dead entities.
-
-use hecs::World;
-use rand::Rng;
-use crate::components::{BirthDate, Calendar};
-
-/// Base mortality rates by age bracket (annual probability)
-const MORTALITY_RATES: &[(u32, f64)] = &[
-    (0, 0.05),     // Infant mortality
-    (5, 0.005),    // Child
-    (15, 0.002),   // Teen
-    (30, 0.003),   // Young adult
-    (50, 0.01),    // Middle age
-    (60, 0.025),   // Senior
-    (70, 0.05),    // Elderly
-    (80, 0.12),    // Very old
-    (90, 0.25),    // Ancient
-    (100, 0.5),    // Centenarian
-];
-
-/// Get daily mortality rate for a given age
-fn get_mortality_rate(years: u16) -> f64 {
-    let annual = MORTALITY_RATES
-        .iter()
-        .rev()
-        .find(|(age, _)| years >= *age as u16)
-        .map(|(_, rate)| *rate)
-        .unwrap_or(0.002);
-    
-    // Convert annual to daily: 1 - (1 - annual)^(1/96) for 96 days/year
-    1.0 - (1.0 - annual).powf(1.0 / Calendar::DAYS_PER_YEAR as f64)
-}
-
-/// Process death for all entities - despawns dead ones immediately.
-/// Returns the number of deaths this tick.
-pub fn death_system(world: &mut World, cal: &Calendar) -> u32 {
-    let mut rng = rand::thread_rng();
-    let mut deaths = Vec::new();
-    
-    // Determine who dies this tick
-    for (entity, birth) in world.query::<&BirthDate>().iter() {
-        let years = birth.age_years(cal);
-        let rate = get_mortality_rate(years);
-        if rng.gen::<f64>() < rate {
-            deaths.push(entity);
-        }
-    }
-    
-    let count = deaths.len() as u32;
-    
-    // Despawn dead entities
-    for entity in deaths {
-        let _ = world.despawn(entity); // Entity guaranteed to exist from query above
-    }
-    
-    count
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_mortality_rates() {
-        // Very old should have high mortality
-        assert!(get_mortality_rate(90) > get_mortality_rate(30));
-        
-        // Infant mortality should be notable
-        assert!(get_mortality_rate(0) > get_mortality_rate(10));
-    }
-}
+//! Death System
+//! 
+//! Determines mortality based on age and health, and despawns dead entities.
+
+use hecs::World;
+use rand::Rng;
+use crate::components::{BirthDate, Calendar, DeathCause, Health, Person, Sex, TileId};
+use crate::config::{MortalityConfig, MortalityTable};
+
+/// Infant excess mortality falls by a factor of e every this many days
+const INFANT_DECAY_DAYS: f64 = Calendar::DAYS_PER_YEAR as f64;
+
+/// Get daily mortality rate for a given age in days.
+/// Children younger than the first bracket follow a curve decaying from the table's
+/// neonatal rate toward the first bracket's rate; everyone older uses the brackets,
+/// either stepwise or (with `smooth`) linearly interpolated between adjacent brackets.
+fn get_mortality_rate(table: &MortalityTable, age_days: u32, smooth: bool) -> f64 {
+    let days_per_year = Calendar::DAYS_PER_YEAR as u32;
+    let years = age_days / days_per_year;
+    let (infant_years, child_rate) = table.brackets.first().copied().unwrap_or((u32::MAX, 0.0));
+    let annual = if years < infant_years {
+        child_rate + (table.neonatal_rate - child_rate) * (-(age_days as f64) / INFANT_DECAY_DAYS).exp()
+    } else {
+        let bracket = table.brackets
+            .iter()
+            .rposition(|(age, _)| years >= *age)
+            .unwrap_or(0);
+        let (age, rate) = table.brackets[bracket];
+        match table.brackets.get(bracket + 1) {
+            Some(&(next_age, next_rate)) if smooth => {
+                let span = ((next_age - age) * days_per_year) as f64;
+                let t = (age_days - age * days_per_year) as f64 / span;
+                rate + (next_rate - rate) * t
+            }
+            _ => rate,
+        }
+    };
+    
+    // Convert annual to daily: 1 - (1 - annual)^(1/96) for 96 days/year
+    1.0 - (1.0 - annual).powf(1.0 / Calendar::DAYS_PER_YEAR as f64)
+}
+
+/// A person who died this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeathRecord {
+    pub person_id: u64,
+    pub tile_id: u16,
+    pub cause: DeathCause,
+}
+
+/// Process death for all entities - despawns dead ones immediately.
+/// Each person uses the table for their sex from `config`; `smooth` interpolates
+/// mortality between age brackets instead of stepping. The daily rate is compounded
+/// over `days` (1 for an ordinary tick), using each person's age at `cal`.
+/// Returns who died this tick.
+pub fn death_system<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    config: &MortalityConfig,
+    smooth: bool,
+    days: u32,
+    rng: &mut R,
+) -> Vec<DeathRecord> {
+    let mut candidates = Vec::new();
+    
+    // Determine who dies this tick
+    for (entity, (person, birth, tile, sex, health)) in world
+        .query::<(&Person, &BirthDate, &TileId, Option<&Sex>, Option<&Health>)>()
+        .iter()
+    {
+        let table = sex.map(|s| config.table_for(*s)).unwrap_or(&config.shared);
+        let multiplier = health.map(|h| h.mortality_multiplier()).unwrap_or(1.0);
+        let daily = (get_mortality_rate(table, birth.age_days(cal), smooth) * multiplier).min(1.0);
+        let rate = crate::systems::over_days(daily, days);
+        candidates.push((entity, DeathRecord { person_id: person.id.0, tile_id: tile.0, cause: DeathCause::Natural }, rate));
+    }
+    
+    super::sort_by_person_id(&mut candidates, |(_, record, _)| record.person_id);
+    let deaths: Vec<(hecs::Entity, DeathRecord)> = candidates
+        .into_iter()
+        .filter_map(|(entity, record, rate)| (rng.gen::<f64>() < rate).then_some((entity, record)))
+        .collect();
+    
+    // Despawn dead entities
+    for (entity, _) in &deaths {
+        let _ = world.despawn(*entity); // Entity guaranteed to exist from query above
+    }
+    
+    deaths.into_iter().map(|(_, record)| record).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::SimRng;
+    use rand::SeedableRng;
+
+    fn spawn_person(world: &mut World, id: u64, birth: BirthDate, health: Health) -> hecs::Entity {
+        let person = Person::unnamed(id);
+        world.spawn((person, birth, TileId(0), health))
+    }
+
+    #[test]
+    fn test_mortality_rates() {
+        let table = MortalityTable::default();
+        let year = Calendar::DAYS_PER_YEAR as u32;
+
+        // Very old should have high mortality
+        assert!(get_mortality_rate(&table, 90 * year, false) > get_mortality_rate(&table, 30 * year, false));
+        
+        // Infant mortality should be notable
+        assert!(get_mortality_rate(&table, 0, false) > get_mortality_rate(&table, 10 * year, false));
+    }
+
+    #[test]
+    fn test_infant_mortality_declines_smoothly() {
+        let table = MortalityTable::default();
+        let handover = table.brackets[0].0 * Calendar::DAYS_PER_YEAR as u32;
+        let rates: Vec<f64> = (0..handover).map(|days| get_mortality_rate(&table, days, false)).collect();
+        assert!(rates.windows(2).all(|w| w[1] < w[0]));
+
+        // No cliff where the curve meets the bracket table: all that's left of the
+        // neonatal excess after five decay periods is e^-5 of it, about 30% of the child rate
+        let last_infant = rates[rates.len() - 1];
+        let first_child = get_mortality_rate(&table, handover, false);
+        assert!(last_infant > first_child && last_infant < first_child * 1.35);
+
+        // On average over those years it matches the flat 0.05 infant bracket it replaced
+        let annual = |daily: f64| 1.0 - (1.0 - daily).powf(Calendar::DAYS_PER_YEAR as f64);
+        let mean = rates.iter().map(|r| annual(*r)).sum::<f64>() / rates.len() as f64;
+        assert!((mean - 0.05).abs() < 0.0005, "mean infant rate {}", mean);
+    }
+
+    #[test]
+    fn test_smoothing_interpolates_between_brackets() {
+        let table = MortalityTable::default();
+        let year = Calendar::DAYS_PER_YEAR as u32;
+        // Just before the 50 bracket: between the 30 and 50 rates, not stuck at 30's
+        let (at_30, at_50) = (get_mortality_rate(&table, 30 * year, true), get_mortality_rate(&table, 50 * year, true));
+        let almost_50 = get_mortality_rate(&table, 50 * year - year / 10, true);
+        assert!(almost_50 > at_30 && almost_50 < at_50);
+        assert_eq!(get_mortality_rate(&table, 50 * year - year / 10, false), at_30);
+
+        // Halfway between the 60 and 70 brackets
+        let (at_60, at_70) = (get_mortality_rate(&table, 60 * year, true), get_mortality_rate(&table, 70 * year, true));
+        let mid = get_mortality_rate(&table, 65 * year, true);
+        assert!(mid > at_60 && mid < at_70);
+
+        // Stepwise stays flat within a bracket
+        assert_eq!(get_mortality_rate(&table, 65 * year, false), get_mortality_rate(&table, 60 * year, false));
+        // Past the last bracket both modes agree
+        assert_eq!(get_mortality_rate(&table, 105 * year, true), get_mortality_rate(&table, 105 * year, false));
+    }
+
+    #[test]
+    fn test_per_sex_tables() {
+        let cal = Calendar::default();
+        let config = MortalityConfig {
+            male: Some(MortalityTable { neonatal_rate: 1.0, brackets: vec![(0, 1.0)] }),
+            female: Some(MortalityTable { neonatal_rate: 0.0, brackets: vec![(0, 0.0)] }),
+            ..MortalityConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let mut world = World::new();
+        for id in 0..40 {
+            let entity = spawn_person(&mut world, id, BirthDate::from_age(id as u16 * 2, &cal), Health(80));
+            let sex = if id % 2 == 0 { Sex::Male } else { Sex::Female };
+            world.insert_one(entity, sex).unwrap();
+        }
+
+        let mut rng = SimRng::seed_from_u64(5);
+        let deaths = death_system(&mut world, &cal, &config, false, 1, &mut rng);
+        assert_eq!(deaths.len(), 20);
+        assert!(deaths.iter().all(|d| d.person_id % 2 == 0));
+        for _ in 0..50 {
+            assert!(death_system(&mut world, &cal, &config, false, 1, &mut rng).is_empty());
+        }
+        assert!(world.query::<&Sex>().iter().all(|(_, sex)| *sex == Sex::Female));
+    }
+
+    #[test]
+    fn test_mortality_table_validation() {
+        let unordered = MortalityTable { neonatal_rate: 0.1, brackets: vec![(10, 0.1), (5, 0.1)] };
+        assert!(unordered.validate().is_err());
+        let not_probability = MortalityTable { neonatal_rate: 1.5, brackets: vec![] };
+        assert!(not_probability.validate().is_err());
+        let empty = MortalityTable { neonatal_rate: 0.1, brackets: vec![] };
+        assert!(empty.validate().is_err());
+        assert!(MortalityConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_unhealthy_die_sooner() {
+        let cal = Calendar::default();
+        let trials = 200;
+        let mut healthy_days = 0u64;
+        let mut unhealthy_days = 0u64;
+
+        let mut rng = SimRng::seed_from_u64(7);
+        for _ in 0..trials {
+            let mut world = World::new();
+            let healthy = spawn_person(&mut world, 1, BirthDate::from_age(90, &cal), Health(100));
+            let unhealthy = spawn_person(&mut world, 2, BirthDate::from_age(90, &cal), Health(0));
+
+            let mut day = 0u64;
+            while world.contains(healthy) || world.contains(unhealthy) {
+                day += 1;
+                death_system(&mut world, &cal, &MortalityConfig::default(), false, 1, &mut rng);
+                if world.contains(healthy) {
+                    healthy_days += 1;
+                }
+                if world.contains(unhealthy) {
+                    unhealthy_days += 1;
+                }
+            }
+            assert!(day > 0);
+        }
+
+        assert!(
+            unhealthy_days < healthy_days,
+            "unhealthy survived {} days vs healthy {}",
+            unhealthy_days,
+            healthy_days
+        );
+    }
+}
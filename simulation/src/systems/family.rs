@@ -1,194 +1,695 @@
-//! Family System
-//! 
-//! Handles pregnancy, delivery, and family dissolution.
-
-use hecs::World;
-use rand::Rng;
-use crate::components::{
-    BirthDate, Calendar, Fertility, Mother, Partner, Person, PersonId, Pregnant, Sex, TileId
-};
-use crate::names;
-
-/// Base conception rate per day for eligible partnered women
-/// Annual ~0.40 / 96 days ≈ 0.004 per day (slightly lower than instant birth)
-const BASE_CONCEPTION_RATE: f64 = 0.004;
-
-/// Result of running family systems
-pub struct FamilyResult {
-    pub new_pregnancies: u32,
-    pub deliveries: u32,
-    pub dissolutions: u32,
-}
-
-/// Run all family systems in order.
-/// Returns counts of pregnancies, deliveries, and dissolutions.
-pub fn family_system(world: &mut World, cal: &Calendar, next_person_id: &mut u64) -> FamilyResult {
-    let dissolutions = dissolution_system(world);
-    let new_pregnancies = pregnancy_system(world, cal);
-    let deliveries = delivery_system(world, cal, next_person_id);
-    
-    FamilyResult {
-        new_pregnancies,
-        deliveries,
-        dissolutions,
-    }
-}
-
-/// Initiate pregnancies for eligible partnered women.
-/// Returns the number of new pregnancies.
-fn pregnancy_system(world: &mut World, cal: &Calendar) -> u32 {
-    let mut rng = rand::thread_rng();
-    let mut to_conceive: Vec<hecs::Entity> = Vec::new();
-    
-    // Find eligible women: partnered, fertile age, not already pregnant, birth interval passed
-    for (entity, (birth, fertility)) in world
-        .query::<(&BirthDate, &Fertility)>()
-        .with::<&Partner>()
-        .without::<&Pregnant>()
-        .iter()
-    {
-        // Must be in fertile age range (16-33 for women)
-        if !birth.can_have_children(Sex::Female, cal) {
-            continue;
-        }
-        
-        // Check birth interval (18 months minimum since last birth)
-        if !fertility.can_give_birth(cal) {
-            continue;
-        }
-        
-        // Age factor: fertility declines after 28
-        let years = birth.age_years(cal);
-        let age_factor = if years > 28 {
-            (1.0 - ((years - 28) as f64 * 0.15)).max(0.1)  // -15% per year after 28
-        } else {
-            1.0
-        };
-        
-        // Children factor: -10% per existing child, min 20%
-        let children_factor = fertility.children_factor();
-        
-        let rate = BASE_CONCEPTION_RATE * age_factor * children_factor;
-        
-        if rng.gen::<f64>() < rate {
-            to_conceive.push(entity);
-        }
-    }
-    
-    let count = to_conceive.len() as u32;
-    
-    // Add Pregnant component to each
-    for entity in to_conceive {
-        let _ = world.insert_one(entity, Pregnant::new(cal)); // Entity validated by query above
-    }
-    
-    count
-}
-
-/// Process deliveries for pregnant women whose due date has arrived.
-/// Returns the number of births.
-fn delivery_system(world: &mut World, cal: &Calendar, next_person_id: &mut u64) -> u32 {
-    let mut rng = rand::thread_rng();
-    let mut deliveries: Vec<(hecs::Entity, TileId)> = Vec::new();
-    
-    // Find pregnant women whose due date has arrived
-    for (entity, (pregnant, tile)) in world
-        .query::<(&Pregnant, &TileId)>()
-        .iter()
-    {
-        if pregnant.is_due(cal) {
-            deliveries.push((entity, *tile));
-        }
-    }
-    
-    let count = deliveries.len() as u32;
-    
-    // Process each delivery
-    for (mother_entity, tile_id) in deliveries {
-        // Remove Pregnant component
-        let _ = world.remove_one::<Pregnant>(mother_entity); // Entity from query, safe to ignore
-        
-        // Update mother's fertility tracking
-        if let Ok(mut fertility) = world.get::<&mut Fertility>(mother_entity) {
-            fertility.record_birth(cal);
-        }
-        
-        // Create child
-        let child_id = PersonId(*next_person_id);
-        *next_person_id += 1;
-
-        let sex = if rng.gen::<bool>() { Sex::Male } else { Sex::Female };
-        let is_male = matches!(sex, Sex::Male);
-        let first_name = names::random_first_name(is_male).to_string();
-        // Inherit mother's last name
-        let last_name = world.get::<&Person>(mother_entity)
-            .map(|p| p.last_name.clone())
-            .unwrap_or_default();
-
-        let child = world.spawn((
-            Person {
-                id: child_id,
-                first_name,
-                last_name,
-            },
-            sex,
-            BirthDate::new(cal.year, cal.month, cal.day),
-            Mother(mother_entity),
-            tile_id,  // Inherit mother's tile
-        ));
-        
-        // Add fertility component if female child
-        if sex == Sex::Female {
-            let _ = world.insert_one(child, Fertility::default()); // Just spawned, always valid
-        }
-    }
-    
-    count
-}
-
-/// Clean up Partner components when a spouse has died.
-/// This runs after death_system to remove orphaned Partner references.
-/// Returns the number of dissolutions (widowed people).
-fn dissolution_system(world: &mut World) -> u32 {
-    let mut to_remove_partner: Vec<hecs::Entity> = Vec::new();
-    
-    // Find people whose partner no longer exists
-    for (entity, partner) in world.query::<&Partner>().iter() {
-        // Check if the partner entity still exists
-        if !world.contains(partner.0) {
-            to_remove_partner.push(entity);
-        }
-    }
-    
-    let count = to_remove_partner.len() as u32;
-    
-    // Remove Partner component from widowed people
-    for entity in to_remove_partner {
-        let _ = world.remove_one::<Partner>(entity); // Entity from query, safe to ignore
-    }
-    
-    count
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_pregnant_due_date() {
-        let cal = Calendar { year: 4000, month: 6, day: 1 };
-        let pregnant = Pregnant::new(&cal);
-        
-        // Due in month 3 of next year (6 + 9 = 15, wrap to 3)
-        assert_eq!(pregnant.due_year, 4001);
-        assert_eq!(pregnant.due_month, 3);
-        
-        // Not due yet
-        let cal_before = Calendar { year: 4001, month: 2, day: 8 };
-        assert!(!pregnant.is_due(&cal_before));
-        
-        // Due now
-        let cal_due = Calendar { year: 4001, month: 3, day: 1 };
-        assert!(pregnant.is_due(&cal_due));
-    }
-}
+//! Family System
+//! 
+//! Handles pregnancy, delivery, and family dissolution.
+
+use hecs::World;
+use rand::Rng;
+use crate::components::{
+    BirthDate, Calendar, DeathCause, Father, Fertility, Generation, Health, Household, MarriedSince, Mother, Occupation, Partner, Person, PersonId,
+    Pregnant, Sex, TileId
+};
+use crate::config::FertilityConfig;
+use crate::names;
+use crate::systems::death::DeathRecord;
+use crate::systems::DrawCount;
+
+/// Base conception rate per day for eligible partnered women
+/// Annual ~0.40 / 96 days ≈ 0.004 per day (slightly lower than instant birth)
+const BASE_CONCEPTION_RATE: f64 = 0.004;
+
+/// A child born this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BirthRecord {
+    pub person_id: u64,
+    /// Inherited from the mother
+    pub tile_id: u16,
+    pub entity: hecs::Entity,
+}
+
+/// Result of running family systems
+pub struct FamilyResult {
+    pub new_pregnancies: u32,
+    pub births: Vec<BirthRecord>,
+    pub dissolutions: u32,
+    /// PersonIds of mothers whose delivery produced no living child
+    pub stillbirths: Vec<u64>,
+    /// Mothers who died in childbirth (already despawned)
+    pub maternal_deaths: Vec<DeathRecord>,
+    /// Live births not counted in the mother's `children_born`, already at `Fertility::MAX_CHILDREN`
+    pub children_capped: u32,
+    /// RNG values drawn by conception and by deliveries (0 unless `rng` counts its draws)
+    pub pregnancy_draws: u64,
+    pub delivery_draws: u64,
+}
+
+/// Why a woman is or isn't conceiving, as `pregnancy_system` sees her today
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct FertilityStatus {
+    pub has_partner: bool,
+    /// Within the female fertile age range
+    pub in_age_window: bool,
+    /// At least `min_birth_interval_months` since her last birth
+    pub interval_ok: bool,
+    pub is_pregnant: bool,
+    /// Daily conception chance (0 when any of the above rules her out)
+    pub effective_rate: f64,
+}
+
+impl FertilityStatus {
+    /// Whether every rule allows her to conceive today
+    pub fn can_conceive(&self) -> bool {
+        self.has_partner && self.in_age_window && self.interval_ok && !self.is_pregnant
+    }
+}
+
+/// Outcome of this tick's deliveries
+struct Deliveries {
+    births: Vec<BirthRecord>,
+    stillbirths: Vec<u64>,
+    maternal_deaths: Vec<DeathRecord>,
+    children_capped: u32,
+}
+
+/// Run pregnancy then delivery. Widowing from ordinary deaths is done earlier in
+/// the tick by `dissolution_system`; this only widows husbands of mothers lost in childbirth.
+/// At most `conception_limit` women conceive (None = no limit; used for the population cap).
+/// Conception chances are compounded over `days` (1 for an ordinary tick).
+/// Returns counts of pregnancies and dissolutions, and delivery outcomes.
+pub fn family_system<R: Rng + DrawCount>(
+    world: &mut World,
+    cal: &Calendar,
+    next_person_id: &mut u64,
+    config: &FertilityConfig,
+    conception_limit: Option<usize>,
+    days: u32,
+    rng: &mut R,
+) -> FamilyResult {
+    let mut dissolutions = 0;
+    let start = rng.draws();
+    let new_pregnancies = pregnancy_system(world, cal, config, conception_limit, days, rng);
+    let pregnancy_draws = rng.draws() - start;
+    let deliveries = delivery_system(world, cal, next_person_id, config, rng);
+    let delivery_draws = rng.draws() - start - pregnancy_draws;
+    if !deliveries.maternal_deaths.is_empty() {
+        // Widow the husbands of mothers lost in childbirth this tick
+        dissolutions += dissolution_system(world);
+    }
+    
+    FamilyResult {
+        new_pregnancies,
+        births: deliveries.births,
+        dissolutions,
+        stillbirths: deliveries.stillbirths,
+        maternal_deaths: deliveries.maternal_deaths,
+        children_capped: deliveries.children_capped,
+        pregnancy_draws,
+        delivery_draws,
+    }
+}
+
+/// Daily conception chance for an eligible woman: base rate scaled by age,
+/// children already born, and the optional newlywed boost
+fn conception_rate(
+    birth: &BirthDate,
+    fertility: &Fertility,
+    married: Option<&MarriedSince>,
+    cal: &Calendar,
+    config: &FertilityConfig,
+) -> f64 {
+    // Age factor: fertility declines after config.fertility_decline_start (28 by default),
+    // by config.fertility_decline_per_year (15%) a year, to at least 10%
+    let years = birth.age_years(cal);
+    let age_factor = if years > config.fertility_decline_start {
+        let past = (years - config.fertility_decline_start) as f64;
+        (1.0 - past * config.fertility_decline_per_year).max(0.1)
+    } else {
+        1.0
+    };
+
+    // Children factor: -10% per existing child, min 20% by default
+    let children_factor = fertility.children_factor(config.children_penalty, config.children_factor_floor);
+
+    // Newlywed factor: optional boost early in the marriage
+    let newlywed = married.is_some_and(|m| m.months_married(cal) < config.newlywed_months);
+    let newlywed_factor = if newlywed { 1.0 + config.newlywed_boost } else { 1.0 };
+
+    BASE_CONCEPTION_RATE * age_factor * children_factor * newlywed_factor
+}
+
+/// Whether `config.post_marriage_delay_months` have passed since the wedding.
+/// Women without a recorded marriage date are not held back.
+fn past_marriage_delay(married: Option<&MarriedSince>, cal: &Calendar, config: &FertilityConfig) -> bool {
+    married.is_none_or(|m| m.months_married(cal) >= config.post_marriage_delay_months)
+}
+
+/// Whether a woman is in the fertile age window and has reached `config.min_motherhood_age`
+fn old_enough_to_conceive(birth: &BirthDate, cal: &Calendar, config: &FertilityConfig) -> bool {
+    birth.can_have_children(Sex::Female, cal) && birth.age_years(cal) >= config.min_motherhood_age
+}
+
+/// Recompute `pregnancy_system`'s checks for one person.
+/// None if the entity is not a living woman.
+pub fn fertility_status(world: &World, entity: hecs::Entity, cal: &Calendar, config: &FertilityConfig) -> Option<FertilityStatus> {
+    if *world.get::<&Sex>(entity).ok()? != Sex::Female {
+        return None;
+    }
+    let birth = *world.get::<&BirthDate>(entity).ok()?;
+    // Wives always carry Fertility; anyone else counts as never having given birth
+    let fertility = world.get::<&Fertility>(entity).map(|f| *f).ok();
+    let married = world.get::<&MarriedSince>(entity).map(|m| *m).ok();
+
+    let has_partner = world.get::<&Partner>(entity).is_ok();
+    let in_age_window = old_enough_to_conceive(&birth, cal, config);
+    let interval_ok = fertility.unwrap_or_default().can_give_birth(cal, config.min_birth_interval_months)
+        && past_marriage_delay(married.as_ref(), cal, config);
+    let is_pregnant = world.get::<&Pregnant>(entity).is_ok();
+
+    let effective_rate = match fertility {
+        Some(fertility) if has_partner && in_age_window && interval_ok && !is_pregnant => {
+            conception_rate(&birth, &fertility, married.as_ref(), cal, config)
+        }
+        _ => 0.0,
+    };
+
+    Some(FertilityStatus { has_partner, in_age_window, interval_ok, is_pregnant, effective_rate })
+}
+
+/// Initiate pregnancies for eligible partnered women, at most `limit` of them
+/// (lowest person_ids first), with each daily chance compounded over `days`.
+/// Returns the number of new pregnancies.
+fn pregnancy_system<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    config: &FertilityConfig,
+    limit: Option<usize>,
+    days: u32,
+    rng: &mut R,
+) -> u32 {
+    if limit == Some(0) {
+        return 0;
+    }
+    let mut candidates: Vec<(u64, hecs::Entity, hecs::Entity, f64)> = Vec::new();
+    
+    // Find eligible women: partnered, fertile age, not already pregnant, birth interval passed
+    for (entity, (person, birth, fertility, married, partner)) in world
+        .query::<(Option<&Person>, &BirthDate, &Fertility, Option<&MarriedSince>, &Partner)>()
+        .without::<&Pregnant>()
+        .iter()
+    {
+        // Must be in fertile age range (16-33 for women) and old enough for motherhood
+        if !old_enough_to_conceive(birth, cal, config) {
+            continue;
+        }
+        
+        // Check birth interval (18 months minimum since last birth by default)
+        // and the wait after marriage
+        if !fertility.can_give_birth(cal, config.min_birth_interval_months) || !past_marriage_delay(married, cal, config) {
+            continue;
+        }
+        
+        let rate = crate::systems::over_days(conception_rate(birth, fertility, married, cal, config), days);
+        candidates.push((person.map_or(0, |p| p.id.0), entity, partner.0, rate));
+    }
+    
+    super::sort_by_person_id(&mut candidates, |(person_id, ..)| *person_id);
+    let mut to_conceive: Vec<(hecs::Entity, hecs::Entity)> = candidates
+        .into_iter()
+        .filter_map(|(_, entity, father, rate)| (rng.gen::<f64>() < rate).then_some((entity, father)))
+        .collect();
+    if let Some(limit) = limit {
+        to_conceive.truncate(limit);
+    }
+    
+    let count = to_conceive.len() as u32;
+    
+    // Add Pregnant component to each, remembering the father's generation
+    for (entity, father) in to_conceive {
+        let father_generation = Some(world.get::<&Generation>(father).map_or(0, |g| g.0));
+        let _ = world.insert_one(entity, Pregnant { father_generation, ..Pregnant::new(cal) }); // Entity validated by query above
+    }
+    
+    count
+}
+
+/// Process deliveries for pregnant women whose due date has arrived.
+/// A delivery may be a stillbirth and may kill the mother, per `config`.
+fn delivery_system<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    next_person_id: &mut u64,
+    config: &FertilityConfig,
+    rng: &mut R,
+) -> Deliveries {
+    let mut deliveries: Vec<(u64, hecs::Entity, TileId, Option<u16>)> = Vec::new();
+    
+    // Find pregnant women whose due date has arrived
+    for (entity, (person, pregnant, tile)) in world
+        .query::<(Option<&Person>, &Pregnant, &TileId)>()
+        .iter()
+    {
+        if pregnant.is_due(cal) {
+            deliveries.push((person.map_or(0, |p| p.id.0), entity, *tile, pregnant.father_generation));
+        }
+    }
+    super::sort_by_person_id(&mut deliveries, |(mother_id, ..)| *mother_id);
+    
+    let mut births = Vec::with_capacity(deliveries.len());
+    let mut stillbirths = Vec::new();
+    let mut maternal_deaths = Vec::new();
+    let mut children_capped = 0;
+    
+    // Process each delivery
+    for (mother_id, mother_entity, tile_id, father_generation) in deliveries {
+        // Remove Pregnant component
+        let _ = world.remove_one::<Pregnant>(mother_entity); // Entity from query, safe to ignore
+
+        // Outcomes are only drawn when enabled, so the default config leaves the RNG stream untouched
+        let stillborn = config.stillbirth_chance > 0.0 && rng.gen::<f64>() < config.stillbirth_chance;
+        let mother_dies = config.maternal_mortality > 0.0 && rng.gen::<f64>() < config.maternal_mortality;
+
+        // Update mother's fertility tracking; only live births count as children born
+        if let Ok(mut fertility) = world.get::<&mut Fertility>(mother_entity) {
+            if stillborn {
+                fertility.record_delivery(cal);
+            } else if !fertility.record_birth(cal) {
+                children_capped += 1;
+            }
+        }
+
+        if mother_dies {
+            maternal_deaths.push(DeathRecord { person_id: mother_id, tile_id: tile_id.0, cause: DeathCause::Childbirth });
+        }
+        if stillborn {
+            stillbirths.push(mother_id);
+        } else {
+            let mother = Mother(mother_entity, PersonId(mother_id));
+            births.push(spawn_child(world, cal, mother, father_generation, tile_id, next_person_id, rng));
+        }
+        if mother_dies {
+            let _ = world.despawn(mother_entity); // Entity from query, safe to ignore
+        }
+    }
+    
+    Deliveries { births, stillbirths, maternal_deaths, children_capped }
+}
+
+/// Spawn a newborn on the mother's tile
+fn spawn_child<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    mother: Mother,
+    father_generation: Option<u16>,
+    tile_id: TileId,
+    next_person_id: &mut u64,
+    rng: &mut R,
+) -> BirthRecord {
+    // Create child
+    let child_id = PersonId(*next_person_id);
+    *next_person_id += 1;
+
+    let sex = if rng.gen::<bool>() { Sex::Male } else { Sex::Female };
+    let is_male = matches!(sex, Sex::Male);
+    let first_name = names::random_first_name(is_male, rng).to_string();
+    // Inherit mother's last name
+    let mother_entity = mother.0;
+    let last_name = world.get::<&Person>(mother_entity)
+        .map(|p| p.last_name.clone())
+        .unwrap_or_default();
+
+    let child = world.spawn((
+        Person {
+            id: child_id,
+            first_name,
+            last_name,
+        },
+        sex,
+        BirthDate::new(cal.year, cal.month, cal.day),
+        mother,
+        tile_id,  // Inherit mother's tile
+        Occupation::Child,
+        Health::random(rng),
+    ));
+    
+    // Add fertility component if female child
+    if sex == Sex::Female {
+        let _ = world.insert_one(child, Fertility::default()); // Just spawned, always valid
+    }
+
+    // Father is the mother's partner at delivery, if she has one
+    let father = world.get::<&Partner>(mother_entity).ok().map(|p| Father(p.0, p.1));
+    if let Some(father) = father {
+        let _ = world.insert_one(child, father); // Just spawned, always valid
+    }
+
+    // One generation past the later parent; anyone without a Generation is a founder.
+    // The father's comes from conception, as he may have died since.
+    let generation_of = |entity: hecs::Entity| world.get::<&Generation>(entity).map(|g| g.0).unwrap_or(0);
+    let father_generation = father_generation.unwrap_or_else(|| father.map_or(0, |f| generation_of(f.0)));
+    let parents = generation_of(mother_entity).max(father_generation);
+    let _ = world.insert_one(child, Generation(parents.saturating_add(1))); // Just spawned, always valid
+
+    // Born into the mother's household
+    let household = world.get::<&Household>(mother_entity).ok().map(|h| *h);
+    if let Some(household) = household {
+        let _ = world.insert_one(child, household); // Just spawned, always valid
+    }
+
+    BirthRecord { person_id: child_id.0, tile_id: tile_id.0, entity: child }
+}
+
+/// Clean up Partner components when a spouse has died.
+/// This runs right after death_system (before matchmaking) so the widowed are single again
+/// when partners are chosen. Returns the number of dissolutions (widowed people).
+pub fn dissolution_system(world: &mut World) -> u32 {
+    let mut to_remove_partner: Vec<hecs::Entity> = Vec::new();
+    
+    // Find people whose partner no longer exists
+    for (entity, partner) in world.query::<&Partner>().iter() {
+        // Check if the partner entity still exists
+        if !world.contains(partner.0) {
+            to_remove_partner.push(entity);
+        }
+    }
+    
+    let count = to_remove_partner.len() as u32;
+    
+    // Remove Partner component from widowed people
+    for entity in to_remove_partner {
+        let _ = world.remove_one::<Partner>(entity); // Entity from query, safe to ignore
+        let _ = world.remove_one::<MarriedSince>(entity); // May be absent, safe to ignore
+    }
+    
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pregnant_due_date() {
+        let cal = Calendar { year: 4000, month: 6, day: 1 };
+        let pregnant = Pregnant::new(&cal);
+        
+        // Due in month 3 of next year (6 + 9 = 15, wrap to 3)
+        assert_eq!(pregnant.due_year, 4001);
+        assert_eq!(pregnant.due_month, 3);
+        
+        // Not due yet
+        let cal_before = Calendar { year: 4001, month: 2, day: 8 };
+        assert!(!pregnant.is_due(&cal_before));
+        
+        // Due now
+        let cal_due = Calendar { year: 4001, month: 3, day: 1 };
+        assert!(pregnant.is_due(&cal_due));
+    }
+
+    /// Couples on tile 1 with every wife due today; returns the husbands
+    fn due_couples(world: &mut World, cal: &Calendar, count: u64) -> Vec<hecs::Entity> {
+        (0..count)
+            .map(|i| {
+                let husband = world.spawn((Person::unnamed(i * 2), BirthDate::from_age(25, cal), Sex::Male, TileId(1)));
+                let due = Pregnant { due_year: cal.year, due_month: cal.month, father_generation: None };
+                let wife = world.spawn((
+                    Person::unnamed(i * 2 + 1),
+                    BirthDate::from_age(22, cal),
+                    Sex::Female,
+                    TileId(1),
+                    Fertility::default(),
+                    Partner(husband, PersonId(i * 2)),
+                    due,
+                ));
+                world.insert_one(husband, Partner(wife, PersonId(i * 2 + 1))).unwrap();
+                husband
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_child_of_father_dead_before_birth_keeps_his_generation() {
+        use rand::SeedableRng;
+
+        let cal = Calendar::new(4000, 1, 1);
+        let mut world = World::new();
+        let husband = world.spawn((Person::unnamed(1), BirthDate::from_age(25, &cal), Sex::Male, TileId(1), Generation(3)));
+        let wife = world.spawn((
+            Person::unnamed(2),
+            BirthDate::from_age(22, &cal),
+            Sex::Female,
+            TileId(1),
+            Fertility::default(),
+            Generation(1),
+            Partner(husband, PersonId(1)),
+        ));
+        world.insert_one(husband, Partner(wife, PersonId(2))).unwrap();
+
+        let config = FertilityConfig::default();
+        let mut rng = crate::world::SimRng::seed_from_u64(3);
+        let year = Calendar::DAYS_PER_YEAR as u32;
+        assert!((0..20).any(|_| pregnancy_system(&mut world, &cal, &config, None, year, &mut rng) == 1));
+        assert_eq!(world.get::<&Pregnant>(wife).unwrap().father_generation, Some(3));
+
+        // He dies and she is widowed before the birth
+        world.despawn(husband).unwrap();
+        world.remove_one::<Partner>(wife).unwrap();
+        let due = *world.get::<&Pregnant>(wife).unwrap();
+        let due_cal = Calendar::new(due.due_year, due.due_month, 1);
+        let mut next_id = 100;
+        let births = delivery_system(&mut world, &due_cal, &mut next_id, &config, &mut rng).births;
+        assert_eq!(births.len(), 1);
+
+        let child = world.query::<(&Person, &Generation)>().iter()
+            .find(|(_, (p, _))| p.id.0 == births[0].person_id)
+            .map(|(_, (_, g))| g.0);
+        assert_eq!(child, Some(4));
+    }
+
+    #[test]
+    fn test_birth_past_children_cap_is_counted() {
+        use rand::SeedableRng;
+
+        let cal = Calendar::new(4000, 1, 1);
+        let mut world = World::new();
+        let mother = world.spawn((
+            Person::unnamed(1),
+            BirthDate::from_age(30, &cal),
+            Sex::Female,
+            TileId(1),
+            Fertility { children_born: Fertility::MAX_CHILDREN, ..Fertility::default() },
+            Pregnant::new(&cal),
+        ));
+
+        let due = *world.get::<&Pregnant>(mother).unwrap();
+        let due_cal = Calendar::new(due.due_year, due.due_month, 1);
+        let mut rng = crate::world::SimRng::seed_from_u64(3);
+        let deliveries = delivery_system(&mut world, &due_cal, &mut 100, &FertilityConfig::default(), &mut rng);
+        assert_eq!((deliveries.births.len(), deliveries.children_capped), (1, 1));
+        assert_eq!(world.get::<&Fertility>(mother).unwrap().children_born, Fertility::MAX_CHILDREN);
+    }
+
+    #[test]
+    fn test_certain_stillbirth() {
+        use rand::SeedableRng;
+
+        let cal = Calendar::new(4000, 6, 1);
+        let mut world = World::new();
+        due_couples(&mut world, &cal, 5);
+        let config = FertilityConfig { stillbirth_chance: 1.0, ..FertilityConfig::default() };
+        let mut rng = crate::world::SimRng::seed_from_u64(1);
+        let mut next_id = 100;
+
+        let result = family_system(&mut world, &cal, &mut next_id, &config, None, 1, &mut rng);
+        assert!(result.births.is_empty());
+        assert_eq!(result.stillbirths.len(), 5);
+        assert!(result.maternal_deaths.is_empty());
+        assert_eq!(next_id, 100);
+        // Mothers survive, are no longer pregnant, and the birth interval restarted,
+        // but a stillbirth doesn't count as a child born
+        for (_, fertility) in world.query::<&Fertility>().without::<&Pregnant>().iter() {
+            assert_eq!(fertility.children_born, 0);
+            assert!(!fertility.can_give_birth(&cal, config.min_birth_interval_months));
+        }
+        assert_eq!(world.query::<&Pregnant>().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_certain_maternal_death() {
+        use rand::SeedableRng;
+
+        let cal = Calendar::new(4000, 6, 1);
+        let mut world = World::new();
+        let husbands = due_couples(&mut world, &cal, 5);
+        let config = FertilityConfig { maternal_mortality: 1.0, ..FertilityConfig::default() };
+        let mut rng = crate::world::SimRng::seed_from_u64(1);
+        let mut next_id = 100;
+
+        let result = family_system(&mut world, &cal, &mut next_id, &config, None, 1, &mut rng);
+        // Children survive their mothers
+        assert_eq!(result.births.len(), 5);
+        assert_eq!(result.maternal_deaths.len(), 5);
+        assert!(result.maternal_deaths.iter().all(|d| d.person_id % 2 == 1 && d.tile_id == 1));
+        assert!(result.maternal_deaths.iter().all(|d| d.cause == DeathCause::Childbirth));
+        // Husbands are widowed in the same tick
+        assert_eq!(result.dissolutions, 5);
+        assert!(husbands.iter().all(|h| world.get::<&Partner>(*h).is_err()));
+        assert_eq!(world.query::<&Pregnant>().iter().count(), 0);
+        assert_eq!(world.query::<&Person>().iter().count(), 10);
+
+        // Both delivery outcomes are probabilities
+        assert!(config.validate().is_ok());
+        for chance in [-0.1, 5.0, f64::NAN] {
+            assert!(FertilityConfig { maternal_mortality: chance, ..FertilityConfig::default() }.validate().is_err());
+            assert!(FertilityConfig { stillbirth_chance: chance, ..FertilityConfig::default() }.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_children_penalty_configurable() {
+        let cal = Calendar::new(4000, 6, 1);
+        let birth = BirthDate::from_age(22, &cal);
+        let fertility = Fertility { last_birth_year: 3990, last_birth_month: 1, children_born: 8 };
+
+        let default = FertilityConfig::default();
+        let rate = conception_rate(&birth, &fertility, None, &cal, &default);
+        assert!((rate - BASE_CONCEPTION_RATE * default.children_factor_floor).abs() < 1e-12);
+
+        // No penalty: eight children conceive as readily as none
+        let pro_natalist = FertilityConfig { children_penalty: 0.0, ..FertilityConfig::default() };
+        assert_eq!(conception_rate(&birth, &fertility, None, &cal, &pro_natalist), BASE_CONCEPTION_RATE);
+
+        let steep = FertilityConfig { children_penalty: 0.5, children_factor_floor: 0.0, ..FertilityConfig::default() };
+        assert_eq!(conception_rate(&birth, &fertility, None, &cal, &steep), 0.0);
+
+        // Penalty and floor are both fractions of the rate
+        assert!(steep.validate().is_ok());
+        for value in [-0.1, 1.5, f64::NAN] {
+            assert!(FertilityConfig { children_penalty: value, ..FertilityConfig::default() }.validate().is_err());
+            assert!(FertilityConfig { children_factor_floor: value, ..FertilityConfig::default() }.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_fertility_decline_configurable() {
+        let cal = Calendar::new(4000, 6, 1);
+        let birth = BirthDate::from_age(35, &cal);
+        let fertility = Fertility::default();
+
+        let default = FertilityConfig::default();
+        let declined = conception_rate(&birth, &fertility, None, &cal, &default);
+        assert!(declined < BASE_CONCEPTION_RATE);
+
+        let late = FertilityConfig { fertility_decline_start: 40, ..FertilityConfig::default() };
+        assert_eq!(conception_rate(&birth, &fertility, None, &cal, &late), BASE_CONCEPTION_RATE);
+
+        // A gentler slope keeps more of the rate at the same age
+        let gentle = FertilityConfig { fertility_decline_per_year: 0.05, ..FertilityConfig::default() };
+        let rate = conception_rate(&birth, &fertility, None, &cal, &gentle);
+        assert!((rate - BASE_CONCEPTION_RATE * 0.65).abs() < 1e-12);
+        assert!(rate > declined);
+
+        // The slope must be a fraction of the rate
+        assert!(gentle.validate().is_ok());
+        for slope in [-0.1, 1.5, f64::NAN] {
+            assert!(FertilityConfig { fertility_decline_per_year: slope, ..FertilityConfig::default() }.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_no_conception_during_post_marriage_delay() {
+        use rand::SeedableRng;
+
+        let wedding = Calendar::new(4000, 6, 1);
+        let mut world = World::new();
+        let husband = world.spawn((BirthDate::from_age(25, &wedding), Sex::Male));
+        for _ in 0..200 {
+            let married = MarriedSince::new(&wedding);
+            world.spawn((BirthDate::from_age(20, &wedding), Sex::Female, Fertility::default(), married, Partner(husband, PersonId(1))));
+        }
+
+        let config = FertilityConfig { post_marriage_delay_months: 6, ..FertilityConfig::default() };
+        let mut rng = crate::world::SimRng::seed_from_u64(3);
+        let mut day = wedding.clone();
+        for _ in 0..Calendar::DAYS_PER_MONTH as u32 * 6 {
+            assert_eq!(pregnancy_system(&mut world, &day, &config, None, 1, &mut rng), 0);
+            day.advance();
+        }
+        assert_eq!(day.month, 12);
+
+        let mut conceived = 0;
+        for _ in 0..Calendar::DAYS_PER_MONTH {
+            conceived += pregnancy_system(&mut world, &day, &config, None, 1, &mut rng);
+            day.advance();
+        }
+        assert!(conceived > 0);
+    }
+
+    #[test]
+    fn test_motherhood_age_gates_married_women() {
+        use crate::config::MarriageConfig;
+        use rand::SeedableRng;
+
+        // Brides may marry at 16, but first births wait until 18
+        let marriage = MarriageConfig { min_age_female: 16, ..MarriageConfig::default() };
+        let config = FertilityConfig { min_motherhood_age: 18, ..FertilityConfig::default() };
+        let cal = Calendar::new(4000, 1, 1);
+        let wedding = Calendar::new(3999, 1, 1);
+
+        let mut world = World::new();
+        let husband = world.spawn((BirthDate::from_age(25, &cal), Sex::Male));
+        let mut wives = Vec::new();
+        for age in [17, 18] {
+            for _ in 0..100 {
+                let birth = BirthDate::from_age(age, &cal);
+                assert!(birth.age_years(&wedding) >= marriage.min_age_female);
+                let married = MarriedSince::new(&wedding);
+                let wife = world.spawn((birth, Sex::Female, Fertility::default(), married, Partner(husband, PersonId(1))));
+                wives.push((age, wife));
+            }
+        }
+
+        let mut rng = crate::world::SimRng::seed_from_u64(8);
+        let mut day = cal.clone();
+        for _ in 0..Calendar::DAYS_PER_MONTH * 6 {
+            pregnancy_system(&mut world, &day, &config, None, 1, &mut rng);
+            day.advance();
+        }
+        assert_eq!(day.year, cal.year);
+
+        let pregnant = |age: u16| wives.iter().filter(|(a, e)| *a == age && world.get::<&Pregnant>(*e).is_ok()).count();
+        assert_eq!(pregnant(17), 0);
+        assert!(pregnant(18) > 0);
+        assert!(!fertility_status(&world, wives[0].1, &day, &config).unwrap().in_age_window);
+    }
+
+    #[test]
+    fn test_zero_birth_interval_allows_back_to_back_pregnancy() {
+        use rand::SeedableRng;
+
+        let cal = Calendar::new(4000, 6, 1);
+        let mut world = World::new();
+        let husband = world.spawn((BirthDate::from_age(25, &cal), Sex::Male));
+        for _ in 0..200 {
+            let mut fertility = Fertility::default();
+            fertility.record_birth(&cal); // Just delivered
+            world.spawn((BirthDate::from_age(20, &cal), Sex::Female, fertility, Partner(husband, PersonId(1))));
+        }
+
+        let mut rng = crate::world::SimRng::seed_from_u64(7);
+        let strict = FertilityConfig::default();
+        let relaxed = FertilityConfig { min_birth_interval_months: 0, ..FertilityConfig::default() };
+
+        let mut day = cal.clone();
+        for _ in 0..Calendar::DAYS_PER_MONTH {
+            assert_eq!(pregnancy_system(&mut world, &day, &strict, None, 1, &mut rng), 0);
+            day.advance();
+        }
+
+        let mut day = cal.clone();
+        let mut conceived = 0;
+        for _ in 0..Calendar::DAYS_PER_MONTH {
+            conceived += pregnancy_system(&mut world, &day, &relaxed, None, 1, &mut rng);
+            day.advance();
+        }
+        assert!(conceived > 0);
+    }
+}
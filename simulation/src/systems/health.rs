@@ -0,0 +1,61 @@
+//! Health System
+//!
+//! Applies slow age-related health decline once per year.
+
+use hecs::World;
+use crate::components::{BirthDate, Calendar, Health};
+
+/// Age after which health starts to decline
+const DECLINE_START_AGE: u16 = 30;
+
+/// Health points lost per year after DECLINE_START_AGE
+const DECLINE_PER_YEAR: u8 = 1;
+
+/// Apply yearly health decline on the first day of each year. `days` is how far
+/// the calendar just moved (1 for an ordinary tick); every new year begun within
+/// that span applies one year's decline.
+pub fn health_system(world: &mut World, cal: &Calendar, days: u32) {
+    let per_year = Calendar::DAYS_PER_YEAR as u64;
+    let today = cal.to_absolute_days();
+    let first_day = today.saturating_sub(days.saturating_sub(1) as u64);
+    // Year starts (day-of-year 0) falling within [first_day, today]
+    let new_years = (today / per_year + 1).saturating_sub(first_day.div_ceil(per_year));
+    if new_years == 0 {
+        return;
+    }
+
+    let decline = DECLINE_PER_YEAR.saturating_mul(new_years.min(u8::MAX as u64) as u8);
+    for (_, (birth, health)) in world.query_mut::<(&BirthDate, &mut Health)>() {
+        if birth.age_years(cal) > DECLINE_START_AGE {
+            health.0 = health.0.saturating_sub(decline);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_declines_yearly_for_adults() {
+        let mut world = World::new();
+        let cal = Calendar::new(4001, 1, 1);
+        let adult = world.spawn((BirthDate::new(3950, 1, 1), Health(80)));
+        let young = world.spawn((BirthDate::new(3990, 1, 1), Health(80)));
+
+        health_system(&mut world, &cal, 1);
+        assert_eq!(world.get::<&Health>(adult).unwrap().0, 79);
+        assert_eq!(world.get::<&Health>(young).unwrap().0, 80);
+
+        // Only applied on the first day of the year
+        health_system(&mut world, &Calendar::new(4001, 1, 2), 1);
+        assert_eq!(world.get::<&Health>(adult).unwrap().0, 79);
+
+        // A multi-day step applies every new year it spans
+        let two_years_later = Calendar::new(4003, 1, 2);
+        health_system(&mut world, &two_years_later, Calendar::DAYS_PER_YEAR as u32 * 2);
+        assert_eq!(world.get::<&Health>(adult).unwrap().0, 77);
+        health_system(&mut world, &Calendar::new(4003, 2, 1), 8);
+        assert_eq!(world.get::<&Health>(adult).unwrap().0, 77);
+    }
+}
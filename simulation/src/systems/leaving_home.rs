@@ -0,0 +1,100 @@
+//! Leaving Home System
+//!
+//! Moves unmarried young adults off the tile they grew up on as they come of age.
+
+use hecs::World;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::components::{BirthDate, Calendar, Partner, Person, TileId};
+use crate::config::HomeLeavingConfig;
+use std::collections::HashMap;
+
+/// Give each unmarried person who reached `config.age` within the last `days` days a
+/// `config.fraction` chance of moving to another tile: a neighbor of their own, or with
+/// `adjacent_only` off, any tile in the adjacency graph. People with nowhere to go stay.
+/// Returns the number of people who moved.
+pub fn leaving_home_system<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    config: &HomeLeavingConfig,
+    tile_neighbors: &HashMap<u16, Vec<u16>>,
+    days: u32,
+    rng: &mut R,
+) -> u32 {
+    if config.fraction <= 0.0 || tile_neighbors.is_empty() {
+        return 0;
+    }
+    let threshold = config.age as u32 * Calendar::DAYS_PER_YEAR as u32;
+
+    let mut candidates: Vec<(u64, hecs::Entity, u16)> = world
+        .query::<(&Person, &BirthDate, &TileId)>()
+        .without::<&Partner>()
+        .iter()
+        .filter(|(_, (_, birth, _))| {
+            let age = birth.age_days(cal);
+            age >= threshold && age.saturating_sub(days) < threshold
+        })
+        .map(|(entity, (person, _, tile))| (person.id.0, entity, tile.0))
+        .collect();
+    if candidates.is_empty() {
+        return 0;
+    }
+
+    super::sort_by_person_id(&mut candidates, |(person_id, _, _)| *person_id);
+    let mut all_tiles: Vec<u16> = Vec::new();
+    if !config.adjacent_only {
+        all_tiles = tile_neighbors.keys().copied().collect();
+        all_tiles.sort_unstable();
+    }
+
+    let mut moved = 0u32;
+    for (_, entity, home) in candidates {
+        if !rng.gen_bool(config.fraction) {
+            continue;
+        }
+        let destination = if config.adjacent_only {
+            tile_neighbors.get(&home).and_then(|neighbors| neighbors.choose(rng)).copied()
+        } else {
+            let others: Vec<u16> = all_tiles.iter().copied().filter(|t| *t != home).collect();
+            others.choose(rng).copied()
+        };
+        if let Some(tile) = destination {
+            if world.insert_one(entity, TileId(tile)).is_ok() {
+                moved += 1;
+            }
+        }
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::SimRng;
+    use rand::SeedableRng;
+
+    fn spawn(world: &mut World, id: u64, age_days: u32, cal: &Calendar, tile: u16) -> hecs::Entity {
+        let born = Calendar::from_absolute_days(cal.to_absolute_days() - age_days as u64);
+        let person = Person::unnamed(id);
+        world.spawn((person, BirthDate::new(born.year, born.month, born.day), TileId(tile)))
+    }
+
+    #[test]
+    fn test_only_those_coming_of_age_leave() {
+        let cal = Calendar::new(100, 1, 1);
+        let config = HomeLeavingConfig { age: 18, fraction: 1.0, adjacent_only: true };
+        let neighbors = HashMap::from([(0, vec![1]), (1, vec![0])]);
+        let threshold = 18 * Calendar::DAYS_PER_YEAR as u32;
+
+        let mut world = World::new();
+        let of_age = spawn(&mut world, 1, threshold, &cal, 0);
+        let minor = spawn(&mut world, 2, threshold - 1, &cal, 0);
+        let long_grown = spawn(&mut world, 3, threshold + 1, &cal, 0);
+
+        let mut rng = SimRng::seed_from_u64(7);
+        assert_eq!(leaving_home_system(&mut world, &cal, &config, &neighbors, 1, &mut rng), 1);
+        assert_eq!(world.get::<&TileId>(of_age).unwrap().0, 1);
+        assert_eq!(world.get::<&TileId>(minor).unwrap().0, 0);
+        assert_eq!(world.get::<&TileId>(long_grown).unwrap().0, 0);
+    }
+}
@@ -1,98 +1,367 @@
-//! Matchmaking System
-//! 
-//! Pairs single adults into partnerships.
-
-use hecs::World;
-use rand::seq::SliceRandom;
-use crate::components::{
-    BirthDate, Calendar, Fertility, Partner, Sex, TileId
-};
-use std::collections::HashMap;
-
-/// Minimum age for marriage
-const MARRIAGE_AGE: u32 = 16;
-
-/// Maximum age difference for marriage
-const MAX_AGE_DIFF: u32 = 15;
-
-/// Process matchmaking - pair eligible singles.
-/// Returns the number of marriages formed this tick.
-pub fn matchmaking_system(world: &mut World, cal: &Calendar) -> u32 {
-    let mut rng = rand::thread_rng();
-    
-    // Collect eligible singles by tile (people without Partner)
-    let mut single_men: HashMap<u16, Vec<(hecs::Entity, u16)>> = HashMap::new();
-    let mut single_women: HashMap<u16, Vec<(hecs::Entity, u16)>> = HashMap::new();
-    
-    for (entity, (birth, sex, tile)) in world
-        .query::<(&BirthDate, &Sex, &TileId)>()
-        .without::<&Partner>()
-        .iter()
-    {
-        let years = birth.age_years(cal);
-        if years < MARRIAGE_AGE as u16 {
-            continue;
-        }
-        
-        let entry = (entity, years);
-        
-        match sex {
-            Sex::Male => single_men.entry(tile.0).or_default().push(entry),
-            Sex::Female => single_women.entry(tile.0).or_default().push(entry),
-        }
-    }
-    
-    // Match within each tile
-    let mut marriages = Vec::new();
-    
-    for (tile, mut men) in single_men {
-        if let Some(women) = single_women.get_mut(&tile) {
-            men.shuffle(&mut rng);
-            women.shuffle(&mut rng);
-            
-            for (man_entity, man_age) in men {
-                // Find compatible woman
-                let woman_pos = women.iter().position(|(_, woman_age)| {
-                    let diff = (*woman_age as i32 - man_age as i32).unsigned_abs();
-                    diff <= MAX_AGE_DIFF
-                });
-                
-                if let Some(pos) = woman_pos {
-                    let (woman_entity, _) = women.remove(pos);
-                    marriages.push((man_entity, woman_entity));
-                }
-            }
-        }
-    }
-    
-    let count = marriages.len() as u32;
-    
-    // Process marriages - just add Partner component to both
-    for (husband_entity, wife_entity) in marriages {
-        let _ = world.insert_one(husband_entity, Partner(wife_entity)); // Entity from query, safe to ignore
-        let _ = world.insert_one(wife_entity, Partner(husband_entity)); // Entity from query, safe to ignore
-
-        // Ensure wife has fertility component
-        if world.get::<&Fertility>(wife_entity).is_err() {
-            let _ = world.insert_one(wife_entity, Fertility::default()); // Entity from query, safe to ignore
-        }
-    }
-    
-    count
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_marriage_age_diff() {
-        // 25 and 30 should be compatible
-        let diff = (30i32 - 25i32).unsigned_abs();
-        assert!(diff <= MAX_AGE_DIFF);
-        
-        // 20 and 50 should not be compatible
-        let diff = (50i32 - 20i32).unsigned_abs();
-        assert!(diff > MAX_AGE_DIFF);
-    }
-}
+//! Matchmaking System
+//! 
+//! Pairs single adults into partnerships, within a tile or across nearby tiles.
+
+use hecs::World;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::components::{
+    BirthDate, Calendar, Father, Fertility, Household, MarriedSince, Mother, Partner, Person, PersonId, Sex, TileId
+};
+use crate::config::{MarriageConfig, MarriageRelocation};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Tiles within `radius` hops of `tile`, nearest first (ties by tile id), starting with `tile` itself
+fn tiles_within(tile: u16, radius: u8, neighbors: &HashMap<u16, Vec<u16>>) -> Vec<u16> {
+    let mut seen = BTreeSet::from([tile]);
+    let mut result = vec![tile];
+    let mut frontier = vec![tile];
+    for _ in 0..radius {
+        let mut next: Vec<u16> = frontier
+            .iter()
+            .flat_map(|t| neighbors.get(t).into_iter().flatten().copied())
+            .filter(|n| seen.insert(*n))
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        next.sort_unstable();
+        result.extend_from_slice(&next);
+        frontier = next;
+    }
+    result
+}
+
+/// Recorded [mother, father] of a person as (entity, id)
+fn parents(world: &World, person: hecs::Entity) -> [Option<(hecs::Entity, PersonId)>; 2] {
+    [
+        world.get::<&Mother>(person).ok().map(|m| (m.0, m.1)),
+        world.get::<&Father>(person).ok().map(|f| (f.0, f.1)),
+    ]
+}
+
+/// Whether kinship rules forbid `a` and `b` from marrying: parent and child or full
+/// siblings always, half-siblings and first cousins when `config` says so.
+/// Children with the same mother and no recorded fathers count as full siblings.
+/// Siblings are matched by parent id, which stays valid after the parent dies.
+pub(crate) fn too_closely_related(world: &World, a: hecs::Entity, b: hecs::Entity, config: &MarriageConfig) -> bool {
+    let a_parents = parents(world, a);
+    let b_parents = parents(world, b);
+
+    // Parent and child (both alive, so their entities are current)
+    let is_parent_of = |parent: hecs::Entity, known: &[Option<(hecs::Entity, PersonId)>; 2]| {
+        known.iter().flatten().any(|(entity, _)| *entity == parent)
+    };
+    if is_parent_of(b, &a_parents) || is_parent_of(a, &b_parents) {
+        return true;
+    }
+
+    let [a_mother, a_father] = a_parents.map(|p| p.map(|(_, id)| id));
+    let [b_mother, b_father] = b_parents.map(|p| p.map(|(_, id)| id));
+    let same_mother = a_mother.is_some() && a_mother == b_mother;
+    let same_father = a_father.is_some() && a_father == b_father;
+    if (same_mother && a_father == b_father) || (same_father && a_mother == b_mother) {
+        return true;
+    }
+    if config.forbid_half_siblings && (same_mother || same_father) {
+        return true;
+    }
+
+    if config.forbid_first_cousins {
+        // Only living parents still carry their own Mother/Father
+        let grandparents = |known: &[Option<(hecs::Entity, PersonId)>; 2]| -> Vec<PersonId> {
+            known
+                .iter()
+                .flatten()
+                .flat_map(|(parent, _)| parents(world, *parent))
+                .flatten()
+                .map(|(_, id)| id)
+                .collect()
+        };
+        let a_grandparents = grandparents(&a_parents);
+        if grandparents(&b_parents).iter().any(|g| a_grandparents.contains(g)) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Partner a couple from today: each gets `Partner` and `MarriedSince`, they share a
+/// new household numbered from `next_household_id`, and the wife gets `Fertility` if missing
+pub(crate) fn wed(
+    world: &mut World,
+    cal: &Calendar,
+    (husband_entity, husband_id): (hecs::Entity, PersonId),
+    (wife_entity, wife_id): (hecs::Entity, PersonId),
+    next_household_id: &mut u64,
+) {
+    let _ = world.insert_one(husband_entity, Partner(wife_entity, wife_id)); // Entity from query, safe to ignore
+    let _ = world.insert_one(wife_entity, Partner(husband_entity, husband_id)); // Entity from query, safe to ignore
+    let _ = world.insert_one(husband_entity, MarriedSince::new(cal)); // Entity from query, safe to ignore
+    let _ = world.insert_one(wife_entity, MarriedSince::new(cal)); // Entity from query, safe to ignore
+
+    let household = Household(*next_household_id);
+    *next_household_id += 1;
+    let _ = world.insert_one(husband_entity, household); // Entity from query, safe to ignore
+    let _ = world.insert_one(wife_entity, household); // Entity from query, safe to ignore
+
+    if world.get::<&Fertility>(wife_entity).is_err() {
+        let _ = world.insert_one(wife_entity, Fertility::default()); // Entity from query, safe to ignore
+    }
+}
+
+/// A couple married this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarriageRecord {
+    pub husband_id: u64,
+    pub wife_id: u64,
+    /// Where the couple settled
+    pub tile_id: u16,
+}
+
+/// Process matchmaking - pair eligible singles.
+/// Partners are searched for up to `config.search_radius` hops away in the `neighbors`
+/// adjacency graph; a couple from different tiles settles per `config.relocation`.
+/// Close relatives are never paired (see `too_closely_related`).
+/// Each new couple starts a household, numbered from `next_household_id`.
+/// Returns the marriages formed this tick.
+pub fn matchmaking_system<R: Rng>(
+    world: &mut World,
+    cal: &Calendar,
+    config: &MarriageConfig,
+    neighbors: &HashMap<u16, Vec<u16>>,
+    next_household_id: &mut u64,
+    rng: &mut R,
+) -> Vec<MarriageRecord> {
+    // Collect eligible singles (people without Partner)
+    let mut singles: Vec<(PersonId, hecs::Entity, u16, Sex, u16)> = Vec::new();
+    for (entity, (person, birth, sex, tile)) in world
+        .query::<(Option<&Person>, &BirthDate, &Sex, &TileId)>()
+        .without::<&Partner>()
+        .iter()
+    {
+        let years = birth.age_years(cal);
+        let min_age = match sex {
+            Sex::Male => config.min_age_male,
+            Sex::Female => config.min_age_female,
+        };
+        if years >= min_age {
+            singles.push((person.map_or(PersonId(0), |p| p.id), entity, years, *sex, tile.0));
+        }
+    }
+    super::sort_by_person_id(&mut singles, |(person_id, ..)| person_id.0);
+
+    // Group by tile. BTreeMap keeps tile iteration order (and thus RNG draw order) deterministic.
+    let mut single_men: BTreeMap<u16, Vec<(hecs::Entity, PersonId, u16)>> = BTreeMap::new();
+    let mut single_women: BTreeMap<u16, Vec<(hecs::Entity, PersonId, u16)>> = BTreeMap::new();
+    for (person_id, entity, years, sex, tile) in singles {
+        match sex {
+            Sex::Male => single_men.entry(tile).or_default().push((entity, person_id, years)),
+            Sex::Female => single_women.entry(tile).or_default().push((entity, person_id, years)),
+        }
+    }
+    
+    // Match each tile's men against women on their own tile first, then nearby tiles
+    let mut marriages = Vec::new();
+    // Each women's pool is shuffled once, when first searched
+    let mut shuffled: BTreeSet<u16> = BTreeSet::new();
+    
+    for (tile, mut men) in single_men {
+        let reachable: Vec<u16> = tiles_within(tile, config.search_radius, neighbors)
+            .into_iter()
+            .filter(|t| single_women.contains_key(t))
+            .collect();
+        if reachable.is_empty() {
+            continue;
+        }
+
+        men.shuffle(rng);
+        for t in &reachable {
+            if shuffled.insert(*t) {
+                if let Some(women) = single_women.get_mut(t) {
+                    women.shuffle(rng);
+                }
+            }
+        }
+        
+        for (man_entity, man_id, man_age) in men {
+            // Find compatible woman
+            let compatible = |woman: hecs::Entity, woman_age: u16| {
+                woman_age.abs_diff(man_age) <= config.max_age_gap
+                    && !too_closely_related(world, man_entity, woman, config)
+            };
+            for woman_tile in &reachable {
+                let Some(women) = single_women.get_mut(woman_tile) else { continue };
+                let preferred = if config.prefer_older_male {
+                    women.iter().position(|(woman, _, woman_age)| compatible(*woman, *woman_age) && *woman_age <= man_age)
+                } else {
+                    None
+                };
+                let woman_pos = preferred
+                    .or_else(|| women.iter().position(|(woman, _, woman_age)| compatible(*woman, *woman_age)));
+                
+                if let Some(pos) = woman_pos {
+                    let (woman_entity, woman_id, _) = women.remove(pos);
+                    marriages.push(((man_entity, man_id), tile, (woman_entity, woman_id), *woman_tile));
+                    break;
+                }
+            }
+        }
+    }
+    
+    let mut records = Vec::with_capacity(marriages.len());
+    
+    // Process marriages - partner both and move couples from different tiles together
+    for ((husband_entity, husband_id), husband_tile, (wife_entity, wife_id), wife_tile) in marriages {
+        wed(world, cal, (husband_entity, husband_id), (wife_entity, wife_id), next_household_id);
+
+        let (mover, home) = match config.relocation {
+            MarriageRelocation::WifeMoves => (wife_entity, husband_tile),
+            MarriageRelocation::HusbandMoves => (husband_entity, wife_tile),
+        };
+        if husband_tile != wife_tile {
+            let _ = world.insert_one(mover, TileId(home)); // Entity from query, safe to ignore
+        }
+        records.push(MarriageRecord { husband_id: husband_id.0, wife_id: wife_id.0, tile_id: home });
+    }
+    
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::world::SimRng;
+    use rand::SeedableRng;
+
+    fn spawn_singles(world: &mut World, cal: &Calendar, sex: Sex, ages: &[u16]) {
+        for &age in ages {
+            world.spawn((BirthDate::from_age(age, cal), sex, TileId(1)));
+        }
+    }
+
+    #[test]
+    fn test_marriage_age_diff() {
+        let config = MarriageConfig::default();
+
+        // 25 and 30 should be compatible
+        assert!(30u16.abs_diff(25) <= config.max_age_gap);
+        
+        // 20 and 50 should not be compatible
+        assert!(50u16.abs_diff(20) > config.max_age_gap);
+    }
+
+    #[test]
+    fn test_marriage_across_adjacent_tiles_needs_radius() {
+        let cal = Calendar::default();
+        let neighbors = HashMap::from([(1, vec![2]), (2, vec![1])]);
+        let setup = || {
+            let mut world = World::new();
+            spawn_singles(&mut world, &cal, Sex::Male, &[25, 26, 27]);
+            for age in [25, 26, 27] {
+                world.spawn((BirthDate::from_age(age, &cal), Sex::Female, TileId(2)));
+            }
+            world
+        };
+        let mut rng = SimRng::seed_from_u64(3);
+
+        let mut world = setup();
+        let config = MarriageConfig::default();
+        assert_eq!(matchmaking_system(&mut world, &cal, &config, &neighbors, &mut 1, &mut rng).len(), 0);
+
+        let mut world = setup();
+        let config = MarriageConfig { search_radius: 1, ..MarriageConfig::default() };
+        assert_eq!(matchmaking_system(&mut world, &cal, &config, &neighbors, &mut 1, &mut rng).len(), 3);
+        // Wives moved to their husbands' tile
+        assert!(world.query::<&TileId>().iter().all(|(_, tile)| tile.0 == 1));
+
+        let mut world = setup();
+        let config = MarriageConfig {
+            search_radius: 1,
+            relocation: MarriageRelocation::HusbandMoves,
+            ..MarriageConfig::default()
+        };
+        assert_eq!(matchmaking_system(&mut world, &cal, &config, &neighbors, &mut 1, &mut rng).len(), 3);
+        assert!(world.query::<&TileId>().iter().all(|(_, tile)| tile.0 == 2));
+    }
+
+    #[test]
+    fn test_tiles_within_radius() {
+        let neighbors = HashMap::from([(1, vec![2]), (2, vec![1, 3]), (3, vec![2, 4]), (4, vec![3])]);
+        assert_eq!(tiles_within(2, 0, &neighbors), vec![2]);
+        assert_eq!(tiles_within(2, 1, &neighbors), vec![2, 1, 3]);
+        assert_eq!(tiles_within(1, 5, &neighbors), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_zero_age_gap_only_pairs_same_age() {
+        let cal = Calendar::default();
+        let mut world = World::new();
+        spawn_singles(&mut world, &cal, Sex::Male, &[20, 22, 25, 30, 30]);
+        spawn_singles(&mut world, &cal, Sex::Female, &[20, 21, 25, 29, 30, 35]);
+
+        let config = MarriageConfig { max_age_gap: 0, ..MarriageConfig::default() };
+        let mut rng = SimRng::seed_from_u64(3);
+        let marriages = matchmaking_system(&mut world, &cal, &config, &HashMap::new(), &mut 1, &mut rng);
+        assert_eq!(marriages.len(), 3);
+
+        for (_, (birth, partner)) in world.query::<(&BirthDate, &Partner)>().iter() {
+            let spouse_birth = world.get::<&BirthDate>(partner.0).unwrap();
+            assert_eq!(birth.age_years(&cal), spouse_birth.age_years(&cal));
+        }
+    }
+
+    #[test]
+    fn test_no_parent_child_or_sibling_marriages() {
+        let cal = Calendar::default();
+        let config = MarriageConfig { max_age_gap: 30, ..MarriageConfig::default() };
+        for seed in 0..20 {
+            let mut world = World::new();
+            let father = world.spawn((BirthDate::from_age(42, &cal), Sex::Male, TileId(1)));
+            let mother = world.spawn((BirthDate::from_age(40, &cal), Sex::Female, TileId(1)));
+            let son = world.spawn((BirthDate::from_age(20, &cal), Sex::Male, TileId(1), Mother(mother, PersonId(2)), Father(father, PersonId(1))));
+            let daughter = world.spawn((BirthDate::from_age(20, &cal), Sex::Female, TileId(1), Mother(mother, PersonId(2)), Father(father, PersonId(1))));
+
+            let mut rng = SimRng::seed_from_u64(seed);
+            assert_eq!(matchmaking_system(&mut world, &cal, &config, &HashMap::new(), &mut 1, &mut rng).len(), 1);
+            assert_eq!(world.get::<&Partner>(father).unwrap().0, mother);
+            assert!(world.get::<&Partner>(son).is_err());
+            assert!(world.get::<&Partner>(daughter).is_err());
+        }
+    }
+
+    #[test]
+    fn test_half_siblings_and_cousins_optional() {
+        let cal = Calendar::default();
+        let mut world = World::new();
+        let birth = BirthDate::from_age(20, &cal);
+        let grandma = world.spawn((Sex::Female,));
+        let (aunt, uncle) = (world.spawn((Sex::Female, Mother(grandma, PersonId(1)))), world.spawn((Sex::Male, Mother(grandma, PersonId(1)))));
+        let (father_a, father_b) = (world.spawn((Sex::Male,)), world.spawn((Sex::Male,)));
+        let half_brother = world.spawn((birth, Sex::Male, Mother(aunt, PersonId(2)), Father(father_a, PersonId(4))));
+        let half_sister = world.spawn((birth, Sex::Female, Mother(aunt, PersonId(2)), Father(father_b, PersonId(5))));
+        let cousin = world.spawn((birth, Sex::Female, Father(uncle, PersonId(3))));
+
+        let default = MarriageConfig::default();
+        assert!(!too_closely_related(&world, half_brother, half_sister, &default));
+        assert!(!too_closely_related(&world, half_brother, cousin, &default));
+
+        let strict = MarriageConfig { forbid_half_siblings: true, forbid_first_cousins: true, ..default };
+        assert!(too_closely_related(&world, half_brother, half_sister, &strict));
+        assert!(too_closely_related(&world, half_brother, cousin, &strict));
+        assert!(!too_closely_related(&world, father_a, cousin, &strict));
+    }
+
+    #[test]
+    fn test_min_age_per_sex() {
+        let cal = Calendar::default();
+        let mut world = World::new();
+        spawn_singles(&mut world, &cal, Sex::Male, &[18]);
+        spawn_singles(&mut world, &cal, Sex::Female, &[18]);
+
+        let config = MarriageConfig { min_age_male: 20, ..MarriageConfig::default() };
+        let mut rng = SimRng::seed_from_u64(3);
+        assert_eq!(matchmaking_system(&mut world, &cal, &config, &HashMap::new(), &mut 1, &mut rng).len(), 0);
+    }
+}
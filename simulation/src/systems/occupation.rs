@@ -0,0 +1,81 @@
+//! Occupation System
+//!
+//! Promotes children into the workforce and retires workers as they age.
+
+use hecs::World;
+use rand::Rng;
+use crate::components::{BirthDate, Calendar, Occupation, Person};
+
+/// Reassign occupations for people who crossed an age threshold.
+/// Returns the number of people whose occupation changed.
+pub fn occupation_system<R: Rng>(world: &mut World, cal: &Calendar, rng: &mut R) -> u32 {
+    let mut changed = 0u32;
+    let mut new_workers: Vec<(u64, hecs::Entity)> = Vec::new();
+
+    for (entity, (person, birth, occupation)) in world.query_mut::<(Option<&Person>, &BirthDate, &mut Occupation)>() {
+        let years = birth.age_years(cal);
+        match *occupation {
+            Occupation::Child if years >= Occupation::WORKING_AGE => {
+                new_workers.push((person.map_or(0, |p| p.id.0), entity));
+            }
+            Occupation::Child | Occupation::Elder => {}
+            _ if years >= Occupation::ELDER_AGE => {
+                *occupation = Occupation::Elder;
+                changed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    super::sort_by_person_id(&mut new_workers, |(person_id, _)| *person_id);
+    for (_, entity) in new_workers {
+        if let Ok(mut occupation) = world.get::<&mut Occupation>(entity) {
+            *occupation = Occupation::random_worker(rng);
+            changed += 1;
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::world::SimRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_child_joins_workforce() {
+        let mut world = World::new();
+        let mut cal = Calendar::default();
+        let child = world.spawn((
+            BirthDate::from_age(Occupation::WORKING_AGE - 1, &cal),
+            Occupation::Child,
+        ));
+
+        let mut rng = SimRng::seed_from_u64(1);
+        occupation_system(&mut world, &cal, &mut rng);
+        assert_eq!(*world.get::<&Occupation>(child).unwrap(), Occupation::Child);
+
+        for _ in 0..Calendar::DAYS_PER_YEAR {
+            cal.advance();
+            occupation_system(&mut world, &cal, &mut rng);
+        }
+        let occupation = *world.get::<&Occupation>(child).unwrap();
+        assert!(matches!(occupation, Occupation::Farmer | Occupation::Laborer));
+    }
+
+    #[test]
+    fn test_worker_retires() {
+        let mut world = World::new();
+        let cal = Calendar::default();
+        let worker = world.spawn((
+            BirthDate::from_age(Occupation::ELDER_AGE, &cal),
+            Occupation::Laborer,
+        ));
+
+        assert_eq!(occupation_system(&mut world, &cal, &mut SimRng::seed_from_u64(1)), 1);
+        assert_eq!(*world.get::<&Occupation>(worker).unwrap(), Occupation::Elder);
+    }
+}
@@ -1,325 +1,3528 @@
-//! Simulation World - main orchestrator
-
-use hecs::World;
-use rand::Rng;
-use std::collections::HashMap;
-use crate::components::*;
-use crate::systems;
-
-/// Result of a single simulation tick
-#[derive(serde::Serialize, Clone)]
-pub struct TickResult {
-    pub births: u32,
-    pub deaths: u32,
-    pub marriages: u32,
-    pub pregnancies: u32,
-    pub dissolutions: u32,
-    pub population: u32,
-}
-
-pub struct SimulationWorld {
-    pub world: World,
-    pub calendar: Calendar,
-    pub next_person_id: u64,
-    pub event_log: EventLog,
-}
-
-impl SimulationWorld {
-    pub fn new() -> Self {
-        Self {
-            world: World::new(),
-            calendar: Calendar::default(),  // Year 4000, month 1, day 1
-            next_person_id: 1,
-            event_log: EventLog::default(), // 10k event capacity
-        }
-    }
-
-    /// Seed initial population (no tile assignment)
-    pub fn seed_population(&mut self, count: usize) {
-        self.seed_population_on_tile(count, 0);
-    }
-    
-    /// Seed population on a specific tile
-    /// Uses realistic age distribution and random names
-    pub fn seed_population_on_tile(&mut self, count: usize, tile_id: u16) {
-        let mut rng = rand::thread_rng();
-
-        for _ in 0..count {
-            let id = PersonId(self.next_person_id);
-            self.next_person_id += 1;
-
-            // 51% male, 49% female
-            let sex = if rng.gen::<f64>() < 0.51 { Sex::Male } else { Sex::Female };
-
-            // Age distribution: 0-80, skewed toward young, average ~25
-            // 55% young (0-20), 23% adult (21-40), 17% middle (41-60), 5% elderly (61-80)
-            let rand_val = rng.gen::<f64>();
-            let age_years: u16 = if rand_val < 0.55 {
-                // 55% young (0-20), avg 10
-                rng.gen_range(0..21)
-            } else if rand_val < 0.78 {
-                // 23% adult (21-40), avg 30.5
-                rng.gen_range(21..41)
-            } else if rand_val < 0.95 {
-                // 17% middle-aged (41-60), avg 50.5
-                rng.gen_range(41..61)
-            } else {
-                // 5% elderly (61-80), avg 70.5
-                rng.gen_range(61..81)
-            };
-
-            let birth_date = BirthDate::from_age(age_years, &self.calendar);
-
-            // Generate realistic names
-            let is_male = matches!(sex, Sex::Male);
-            let first_name = crate::names::random_first_name(is_male).to_string();
-            let last_name = crate::names::random_last_name().to_string();
-
-            self.world.spawn((
-                Person {
-                    id,
-                    first_name,
-                    last_name,
-                },
-                sex,
-                birth_date,
-                TileId(tile_id),
-            ));
-        }
-    }
-
-    /// Seed population on a tile with a random count within [min, max].
-    /// Returns the actual count seeded.
-    pub fn seed_population_on_tile_range(&mut self, min: usize, max: usize, tile_id: u16) -> usize {
-        let mut rng = rand::thread_rng();
-        let count = rng.gen_range(min..=max);
-        self.seed_population_on_tile(count, tile_id);
-        count
-    }
-
-    /// Run one simulation tick (advances 1 day).
-    /// Returns a TickResult with births, deaths, marriages, pregnancies, dissolutions, and population.
-    pub fn tick(&mut self) -> TickResult {
-        self.calendar.advance();
-
-        // Run all systems
-        let deaths = systems::death_system(&mut self.world, &self.calendar);
-        let marriages = systems::matchmaking_system(&mut self.world, &self.calendar);
-        let family = systems::family_system(&mut self.world, &self.calendar, &mut self.next_person_id);
-        let population = self.entity_count() as u32;
-
-        // Log events to event log (Phase 2)
-        for _ in 0..family.deliveries {
-            self.event_log.push(Event::new(EventType::Birth, &self.calendar));
-        }
-        for _ in 0..deaths {
-            self.event_log.push(Event::new(EventType::Death, &self.calendar));
-        }
-        for _ in 0..marriages {
-            self.event_log.push(Event::new(EventType::Marriage, &self.calendar));
-        }
-        for _ in 0..family.new_pregnancies {
-            self.event_log.push(Event::new(EventType::PregnancyStarted, &self.calendar));
-        }
-        for _ in 0..family.dissolutions {
-            self.event_log.push(Event::new(EventType::Dissolution, &self.calendar));
-        }
-
-        TickResult {
-            births: family.deliveries,
-            deaths,
-            marriages,
-            pregnancies: family.new_pregnancies,
-            dissolutions: family.dissolutions,
-            population,
-        }
-    }
-
-    /// Get entity count (all entities with BirthDate component = people)
-    pub fn entity_count(&self) -> usize {
-        self.world.query::<&BirthDate>().iter().count()
-    }
-    
-    /// Get current calendar year
-    pub fn current_year(&self) -> u16 {
-        self.calendar.year
-    }
-
-    // ========================================================================
-    // Statistics queries (Phase 2)
-    // ========================================================================
-
-    /// Population count for a specific tile
-    pub fn tile_population(&self, tile_id: u16) -> u32 {
-        self.world.query::<&TileId>()
-            .iter()
-            .filter(|(_, t)| t.0 == tile_id)
-            .count() as u32
-    }
-
-    /// Population count per tile
-    pub fn population_by_tile(&self) -> HashMap<u16, u32> {
-        let mut map: HashMap<u16, u32> = HashMap::new();
-        for (_, tile) in self.world.query::<&TileId>().iter() {
-            *map.entry(tile.0).or_insert(0) += 1;
-        }
-        map
-    }
-
-    /// Full demographics snapshot in a single pass
-    pub fn demographics(&self) -> Demographics {
-        let mut males: u32 = 0;
-        let mut females: u32 = 0;
-        let mut partnered: u32 = 0;
-        let mut single: u32 = 0;
-        let mut pregnant: u32 = 0;
-        let mut age_brackets = [0u32; 7]; // 0-4, 5-14, 15-29, 30-49, 50-69, 70-89, 90+
-        let mut total_age: u64 = 0;
-        let mut count: u32 = 0;
-
-        for (entity, (birth, sex)) in self.world.query::<(&BirthDate, &Sex)>().iter() {
-            count += 1;
-            let years = birth.age_years(&self.calendar);
-            total_age += years as u64;
-
-            match sex {
-                Sex::Male => males += 1,
-                Sex::Female => females += 1,
-            }
-
-            // Age bracket
-            let bracket = match years {
-                0..=4 => 0,
-                5..=14 => 1,
-                15..=29 => 2,
-                30..=49 => 3,
-                50..=69 => 4,
-                70..=89 => 5,
-                _ => 6,
-            };
-            age_brackets[bracket] += 1;
-
-            // Partnership
-            if self.world.get::<&Partner>(entity).is_ok() {
-                partnered += 1;
-            } else {
-                single += 1;
-            }
-            
-            // Pregnancy
-            if self.world.get::<&Pregnant>(entity).is_ok() {
-                pregnant += 1;
-            }
-        }
-
-        let average_age = if count > 0 { total_age as f64 / count as f64 } else { 0.0 };
-
-        Demographics {
-            population: count,
-            males,
-            females,
-            partnered,
-            single,
-            pregnant,
-            average_age,
-            age_brackets,
-        }
-    }
-}
-
-/// Full demographics snapshot
-#[derive(serde::Serialize, Clone)]
-pub struct Demographics {
-    pub population: u32,
-    pub males: u32,
-    pub females: u32,
-    pub partnered: u32,
-    pub single: u32,
-    pub pregnant: u32,
-    pub average_age: f64,
-    /// [0-4, 5-14, 15-29, 30-49, 50-69, 70-89, 90+]
-    pub age_brackets: [u32; 7],
-}
-
-/// Vital statistics (Phase 3) - calculated from event log
-#[derive(serde::Serialize, Clone)]
-pub struct VitalStatistics {
-    /// Births per 1000 population per year
-    pub birth_rate: f64,
-    /// Deaths per 1000 population per year
-    pub death_rate: f64,
-    /// Marriages per 1000 population per year
-    pub marriage_rate: f64,
-    /// Natural increase rate (births - deaths) per 1000 population per year
-    pub natural_increase_rate: f64,
-    /// Total births in period
-    pub total_births: u32,
-    /// Total deaths in period
-    pub total_deaths: u32,
-    /// Total marriages in period
-    pub total_marriages: u32,
-    /// Population (average over period)
-    pub population: u32,
-    /// Period length in years
-    pub period_years: f64,
-}
-
-impl SimulationWorld {
-    /// Calculate vital statistics from event log for a date range
-    ///
-    /// # Arguments
-    /// * `start_year` - Start year (inclusive)
-    /// * `end_year` - End year (inclusive)
-    ///
-    /// # Returns
-    /// VitalStatistics for the specified period
-    pub fn calculate_vital_statistics(&self, start_year: u16, end_year: u16) -> VitalStatistics {
-        use crate::components::EventType;
-
-        let births = self.event_log.count_by_type(EventType::Birth, start_year, end_year) as u32;
-        let deaths = self.event_log.count_by_type(EventType::Death, start_year, end_year) as u32;
-        let marriages = self.event_log.count_by_type(EventType::Marriage, start_year, end_year) as u32;
-
-        let population = self.entity_count() as u32;
-        let period_years = (end_year.saturating_sub(start_year) + 1) as f64;
-
-        // Calculate rates per 1000 population per year
-        let pop_factor = if population > 0 { 1000.0 / (population as f64 * period_years) } else { 0.0 };
-
-        let birth_rate = births as f64 * pop_factor * period_years;
-        let death_rate = deaths as f64 * pop_factor * period_years;
-        let marriage_rate = marriages as f64 * pop_factor * period_years;
-        let natural_increase_rate = birth_rate - death_rate;
-
-        VitalStatistics {
-            birth_rate,
-            death_rate,
-            marriage_rate,
-            natural_increase_rate,
-            total_births: births,
-            total_deaths: deaths,
-            total_marriages: marriages,
-            population,
-            period_years,
-        }
-    }
-
-    /// Calculate vital statistics for the current year only
-    pub fn calculate_current_year_statistics(&self) -> VitalStatistics {
-        let year = self.calendar.year;
-        self.calculate_vital_statistics(year, year)
-    }
-
-    /// Calculate vital statistics for the last N years
-    pub fn calculate_recent_statistics(&self, years: u16) -> VitalStatistics {
-        let end_year = self.calendar.year;
-        let start_year = end_year.saturating_sub(years.saturating_sub(1));
-        self.calculate_vital_statistics(start_year, end_year)
-    }
-}
-
-impl Default for SimulationWorld {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+//! Simulation World - main orchestrator
+
+use hecs::World;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::components::*;
+use crate::config::{AgeProfile, FertilityConfig, HomeLeavingConfig, MarriageConfig, MortalityConfig, SimConfig};
+use crate::persistence::ExportData;
+use crate::systems;
+
+/// Minimum age considered adult for migration and orphanhood
+const ADULT_AGE: u16 = 16;
+
+/// Seedable RNG owned by the world; every stochastic system draws from it
+/// so a seed fully determines a run.
+pub type SimRng = rand_chacha::ChaCha8Rng;
+
+/// Result of a single simulation tick
+#[derive(serde::Serialize, Clone)]
+pub struct TickResult {
+    pub births: u32,
+    pub deaths: u32,
+    pub marriages: u32,
+    pub pregnancies: u32,
+    pub dissolutions: u32,
+    pub stillbirths: u32,
+    pub population: u32,
+    /// Person ids of this tick's newborns, in birth order
+    pub birth_ids: Vec<u64>,
+    /// Person ids of everyone who died this tick, including mothers lost in childbirth
+    pub death_ids: Vec<u64>,
+    /// Births per tile (empty unless `track_tile_events` is set)
+    pub births_by_tile: HashMap<u16, u32>,
+    /// Deaths per tile (empty unless `track_tile_events` is set)
+    pub deaths_by_tile: HashMap<u16, u32>,
+    /// Deaths per cause; sums to `deaths`
+    pub deaths_by_cause: HashMap<DeathCause, u32>,
+}
+
+/// How far a `tick_many_with_progress` run has got
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickProgress {
+    /// Ticks run so far
+    pub ticks: u32,
+    /// Ticks requested
+    pub total: u32,
+    /// Date after the latest tick
+    pub calendar: Calendar,
+}
+
+/// Longest window (in days) `growth_rate` can look back over
+pub const GROWTH_WINDOW_MAX_DAYS: usize = Calendar::DAYS_PER_YEAR as usize * 5;
+
+/// Vital event counts for one calendar year, accumulated as the world ticks
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct YearStats {
+    pub births: u32,
+    pub deaths: u32,
+    pub marriages: u32,
+    /// Population after the year's last tick so far (None in version 1 exports)
+    #[serde(default)]
+    pub population: Option<u32>,
+}
+
+/// Totals for one year of ticks from `tick_until_year`
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct YearSummary {
+    /// Year the ticks started in
+    pub year: u16,
+    pub births: u32,
+    pub deaths: u32,
+    pub marriages: u32,
+    /// Population after the year's last tick
+    pub population: u32,
+}
+
+/// Ordering for `people_page`; ties always fall back to person id
+#[derive(serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonSort {
+    #[default]
+    PersonId,
+    /// Youngest first
+    Age,
+    Tile,
+    /// Last name, then first name
+    Name,
+}
+
+/// The components most callers want for one living person, read without touching
+/// hecs. Family links are given as person ids, which outlive the people they name.
+#[derive(Debug, Clone, Copy)]
+pub struct PersonView<'a> {
+    pub person: &'a Person,
+    pub sex: Sex,
+    pub birth: BirthDate,
+    pub tile_id: u16,
+    pub partner_id: Option<u64>,
+    pub mother_id: Option<u64>,
+    pub father_id: Option<u64>,
+    pub pregnant: bool,
+}
+
+impl PersonView<'_> {
+    pub fn id(&self) -> u64 {
+        self.person.id.0
+    }
+}
+
+/// Components read to build a `PersonView`
+type PersonViewQuery<'a> = (
+    &'a Person,
+    &'a Sex,
+    &'a BirthDate,
+    &'a TileId,
+    Option<&'a Partner>,
+    Option<&'a Mother>,
+    Option<&'a Father>,
+    Option<&'a Pregnant>,
+);
+
+fn person_view<'a>(
+    (person, sex, birth, tile, partner, mother, father, pregnant): <PersonViewQuery<'a> as hecs::Query>::Item<'a>,
+) -> PersonView<'a> {
+    PersonView {
+        person,
+        sex: *sex,
+        birth: *birth,
+        tile_id: tile.0,
+        partner_id: partner.map(|p| p.1.0),
+        mother_id: mother.map(|m| m.1.0),
+        father_id: father.map(|f| f.1.0),
+        pregnant: pregnant.is_some(),
+    }
+}
+
+/// Edit to one person's Node-owned fields; `None` leaves a field unchanged
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct PersonPatch {
+    pub person_id: u64,
+    #[serde(default)]
+    pub first_name: Option<String>,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub tile_id: Option<u16>,
+}
+
+/// Outcome of `apply_person_patches`
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct PatchResult {
+    /// Patches whose person was found and updated
+    pub applied: u32,
+    /// Ids with no living person, in patch order
+    pub unknown_ids: Vec<u64>,
+}
+
+/// Natural increase over a recent window of ticks
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct GrowthRate {
+    /// Births minus deaths over the window
+    pub natural_increase: i64,
+    /// Natural increase relative to the population at the window's start, scaled to a year
+    pub annual_growth_percent: f64,
+    /// Days actually covered (less than requested early in a run)
+    pub window_days: u32,
+}
+
+/// Founder age for `AgeProfile::Standard`: 0-80, skewed toward young, average ~25
+fn standard_founder_age<R: Rng>(rng: &mut R) -> u16 {
+    // 55% young (0-20), 23% adult (21-40), 17% middle (41-60), 5% elderly (61-80)
+    let rand_val = rng.gen::<f64>();
+    if rand_val < 0.55 {
+        // 55% young (0-20), avg 10
+        rng.gen_range(0..21)
+    } else if rand_val < 0.78 {
+        // 23% adult (21-40), avg 30.5
+        rng.gen_range(21..41)
+    } else if rand_val < 0.95 {
+        // 17% middle-aged (41-60), avg 50.5
+        rng.gen_range(41..61)
+    } else {
+        // 5% elderly (61-80), avg 70.5
+        rng.gen_range(61..81)
+    }
+}
+
+/// Outcome of `seed_relationships`
+#[derive(serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SeededRelationships {
+    pub couples: u32,
+    /// Children given a mother and father
+    pub children_placed: u32,
+}
+
+/// One designed founder for `seed_from_records`
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct SeedPerson {
+    pub first_name: String,
+    pub last_name: String,
+    pub sex: Sex,
+    pub age: u16,
+    pub tile_id: u16,
+    /// Index of this person's spouse within the same batch
+    #[serde(default)]
+    pub partner: Option<usize>,
+}
+
+/// Starting state captured by `mark_genesis`, used to replay a run
+#[derive(Clone)]
+pub struct Genesis {
+    pub data: ExportData,
+    pub rng: SimRng,
+}
+
+pub struct SimulationWorld {
+    /// The ECS. People spawned or despawned here directly are picked up by the next
+    /// tick; lookups by id before then need `rebuild_person_index`.
+    pub world: World,
+    pub calendar: Calendar,
+    /// Date the world began; `days_since_start` counts from here
+    pub start_date: Calendar,
+    pub next_person_id: u64,
+    /// Ticks run over the world's whole history, across saves; a multi-day
+    /// `tick_days` step counts once
+    pub ticks_elapsed: u64,
+    /// Next Household id handed out at marriage
+    pub next_household_id: u64,
+    pub event_log: EventLog,
+    /// Tile adjacency graph (tile -> neighboring tiles), supplied by the map.
+    /// Derived from the hexasphere geometry, so not persisted in saves.
+    pub tile_neighbors: HashMap<u16, Vec<u16>>,
+    pub fertility_config: FertilityConfig,
+    /// Marriage age and age-gap rules
+    pub marriage_config: MarriageConfig,
+    /// Mortality tables, optionally per sex
+    pub mortality_config: MortalityConfig,
+    /// Unmarried young adults moving to a tile of their own (off by default)
+    pub home_leaving_config: HomeLeavingConfig,
+    /// Populate per-tile birth/death breakdowns in TickResult (off by default)
+    pub track_tile_events: bool,
+    /// Interpolate mortality between age brackets instead of stepping (off by default)
+    pub smooth_mortality: bool,
+    /// Keep per-system RNG draw counts for `last_tick_rng_audit` (off by default)
+    pub rng_audit: bool,
+    last_rng_audit: Option<systems::RngAudit>,
+    /// Each tick, drop events from before `calendar.year - retention_years`, on top of
+    /// the event log's size cap (None = keep events until the cap pushes them out)
+    pub retention_years: Option<u16>,
+    /// Hard population cap (None = unlimited). Living people plus pregnancies under way
+    /// count toward it; at the cap no one conceives and immigration is turned away.
+    pub max_population: Option<u32>,
+    /// Whether the cap was hit on the last check, so CapacityReached is logged once per hit
+    pub(crate) capacity_reached: bool,
+    /// Seed the RNG was created from
+    pub seed: u64,
+    pub rng: SimRng,
+    /// Replay base captured after seeding (in memory only, not persisted)
+    pub genesis: Option<Genesis>,
+    /// (births, deaths) for the most recent ticks, newest last, for `growth_rate`
+    recent_vitals: VecDeque<(u32, u32)>,
+    /// Births/deaths/marriages per year, kept by `tick` so they outlive the event log
+    pub(crate) year_stats: HashMap<u16, YearStats>,
+    /// PersonId -> entity for everyone spawned or despawned through SimulationWorld
+    person_index: HashMap<u64, hecs::Entity>,
+    /// Living people, kept in step with the person index so `entity_count` is O(1)
+    living_count: usize,
+    /// `world.len()` when the person index and count were last known to be right. A
+    /// different length means people were spawned or despawned directly through `world`.
+    synced_len: u32,
+}
+
+impl SimulationWorld {
+    /// Create an empty world with a random seed
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    /// Create an empty world whose RNG is seeded from `seed`, starting on `Calendar::default()`
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_start(seed, Calendar::default())
+    }
+
+    /// Create an empty world whose RNG is seeded from `seed`, starting on `start`
+    pub fn with_start(seed: u64, start: Calendar) -> Self {
+        Self {
+            world: World::new(),
+            calendar: start.clone(),
+            start_date: start,
+            next_person_id: 1,
+            ticks_elapsed: 0,
+            next_household_id: 1,
+            event_log: EventLog::default(), // 10k event capacity
+            tile_neighbors: HashMap::new(),
+            fertility_config: FertilityConfig::default(),
+            marriage_config: MarriageConfig::default(),
+            mortality_config: MortalityConfig::default(),
+            home_leaving_config: HomeLeavingConfig::default(),
+            track_tile_events: false,
+            smooth_mortality: false,
+            rng_audit: false,
+            last_rng_audit: None,
+            retention_years: None,
+            max_population: None,
+            capacity_reached: false,
+            recent_vitals: VecDeque::with_capacity(GROWTH_WINDOW_MAX_DAYS),
+            year_stats: HashMap::new(),
+            person_index: HashMap::new(),
+            living_count: 0,
+            synced_len: 0,
+            seed,
+            rng: SimRng::seed_from_u64(seed),
+            genesis: None,
+        }
+    }
+
+    /// All tunables currently in effect
+    pub fn sim_config(&self) -> SimConfig {
+        SimConfig {
+            fertility: self.fertility_config.clone(),
+            marriage: self.marriage_config.clone(),
+            mortality: self.mortality_config.clone(),
+            home_leaving: self.home_leaving_config.clone(),
+            smooth_mortality: self.smooth_mortality,
+            max_population: self.max_population,
+        }
+    }
+
+    /// Replace every tunable at once; nothing changes if `config` is invalid
+    pub fn set_sim_config(&mut self, config: SimConfig) -> Result<(), String> {
+        config.validate()?;
+        self.fertility_config = config.fertility;
+        self.marriage_config = config.marriage;
+        self.mortality_config = config.mortality;
+        self.home_leaving_config = config.home_leaving;
+        self.smooth_mortality = config.smooth_mortality;
+        self.max_population = config.max_population;
+        Ok(())
+    }
+
+    /// Seed initial population (no tile assignment)
+    pub fn seed_population(&mut self, count: usize) {
+        self.seed_population_on_tile(count, 0);
+    }
+    
+    /// Seed population on a specific tile
+    /// Uses realistic age distribution and random names
+    pub fn seed_population_on_tile(&mut self, count: usize, tile_id: u16) {
+        let _ = self.seed_population_with_profile(count, tile_id, &AgeProfile::Standard); // Standard is always valid
+    }
+
+    /// Seed population on a tile with founder ages drawn from `profile`.
+    /// Fails without seeding anyone if the profile is invalid.
+    pub fn seed_population_with_profile(&mut self, count: usize, tile_id: u16, profile: &AgeProfile) -> Result<(), String> {
+        profile.validate()?;
+        let weighted = match profile {
+            AgeProfile::Weighted(weights) => Some(
+                WeightedIndex::new(weights.iter().map(|(_, w)| *w)).map_err(|e| e.to_string())?,
+            ),
+            _ => None,
+        };
+        let rng = &mut self.rng;
+
+        for _ in 0..count {
+            let id = PersonId(self.next_person_id);
+            self.next_person_id += 1;
+
+            // 51% male, 49% female
+            let sex = if rng.gen::<f64>() < 0.51 { Sex::Male } else { Sex::Female };
+
+            let age_years: u16 = match (profile, &weighted) {
+                (AgeProfile::Uniform { min, max }, _) => rng.gen_range(*min..=*max),
+                (AgeProfile::Weighted(weights), Some(index)) => weights[index.sample(rng)].0,
+                (AgeProfile::Exponential { mean, max }, _) => {
+                    // Inverse CDF of the exponential truncated to [0, max + 1)
+                    let cap = 1.0 - (-(*max as f64 + 1.0) / mean).exp();
+                    let u = rng.gen::<f64>() * cap;
+                    ((-mean * (1.0 - u).ln()) as u16).min(*max)
+                }
+                _ => standard_founder_age(rng),
+            };
+
+            let birth_date = BirthDate::from_age(age_years, &self.calendar);
+
+            // Generate realistic names
+            let is_male = matches!(sex, Sex::Male);
+            let first_name = crate::names::random_first_name(is_male, rng).to_string();
+            let last_name = crate::names::random_last_name(rng).to_string();
+
+            let entity = self.world.spawn((
+                Person {
+                    id,
+                    first_name,
+                    last_name,
+                },
+                sex,
+                birth_date,
+                TileId(tile_id),
+                Occupation::for_age(age_years, rng),
+                Health::random(rng),
+                Generation(0),
+            ));
+            self.person_index.insert(id.0, entity);
+            self.living_count += 1;
+        }
+        self.mark_caches_synced();
+        Ok(())
+    }
+
+    /// Seed population on a tile with a random count within [min, max].
+    /// Returns the actual count seeded.
+    pub fn seed_population_on_tile_range(&mut self, min: usize, max: usize, tile_id: u16) -> usize {
+        let count = self.rng.gen_range(min..=max);
+        self.seed_population_on_tile(count, tile_id);
+        count
+    }
+
+    /// Pair founders into couples so a freshly seeded world doesn't start out all single.
+    /// On each tile, singles old enough to marry are shuffled and paired within
+    /// `marriage_config`'s age gap and kinship rules until about `marriage_fraction` of them
+    /// are partnered (fewer if the tile runs short of compatible men or women).
+    /// With `place_children`, children without a recorded mother are then given to a random
+    /// couple on their tile who were of childbearing age when the child was born.
+    /// Couples count as married from today; no events are logged.
+    pub fn seed_relationships(&mut self, marriage_fraction: f64, place_children: bool) -> Result<SeededRelationships, String> {
+        if !(0.0..=1.0).contains(&marriage_fraction) {
+            return Err(format!("marriage_fraction must be between 0 and 1, got {}", marriage_fraction));
+        }
+        let cal = self.calendar.clone();
+        let config = &self.marriage_config;
+
+        // Singles old enough to marry, by tile, in person_id order
+        type Single = (hecs::Entity, PersonId, u16); // (entity, id, age)
+        let mut singles: Vec<(PersonId, hecs::Entity, u16, Sex, u16)> = self.world
+            .query::<(&Person, &BirthDate, &Sex, &TileId)>()
+            .without::<&Partner>()
+            .iter()
+            .map(|(entity, (person, birth, sex, tile))| (person.id, entity, birth.age_years(&cal), *sex, tile.0))
+            .filter(|(_, _, years, sex, _)| {
+                *years >= match sex {
+                    Sex::Male => config.min_age_male,
+                    Sex::Female => config.min_age_female,
+                }
+            })
+            .collect();
+        singles.sort_by_key(|(id, ..)| id.0);
+        let mut by_tile: BTreeMap<u16, [Vec<Single>; 2]> = BTreeMap::new();
+        for (id, entity, years, sex, tile) in singles {
+            by_tile.entry(tile).or_default()[(sex == Sex::Female) as usize].push((entity, id, years));
+        }
+
+        // (husband, wife) with ages, by tile
+        let mut couples: BTreeMap<u16, Vec<(Single, Single)>> = BTreeMap::new();
+        for (tile, [mut men, mut women]) in by_tile {
+            let target = ((men.len() + women.len()) as f64 * marriage_fraction / 2.0).round() as usize;
+            men.shuffle(&mut self.rng);
+            women.shuffle(&mut self.rng);
+            let paired = couples.entry(tile).or_default();
+            for man in men {
+                if paired.len() >= target {
+                    break;
+                }
+                let pos = women.iter().position(|woman| {
+                    woman.2.abs_diff(man.2) <= config.max_age_gap
+                        && !systems::matchmaking::too_closely_related(&self.world, man.0, woman.0, config)
+                });
+                if let Some(pos) = pos {
+                    paired.push((man, women.remove(pos)));
+                }
+            }
+        }
+
+        let mut result = SeededRelationships::default();
+        for ((husband, husband_id, _), (wife, wife_id, _)) in couples.values().flatten() {
+            systems::matchmaking::wed(&mut self.world, &cal, (*husband, *husband_id), (*wife, *wife_id), &mut self.next_household_id);
+            result.couples += 1;
+        }
+
+        if place_children {
+            let mut children: Vec<(PersonId, hecs::Entity, BirthDate, u16)> = self.world
+                .query::<(&Person, &BirthDate, &TileId)>()
+                .without::<&Mother>()
+                .without::<&Partner>()
+                .iter()
+                .map(|(entity, (person, birth, tile))| (person.id, entity, *birth, tile.0))
+                .filter(|(_, _, birth, _)| birth.age_years(&cal) < ADULT_AGE)
+                .collect();
+            children.sort_by_key(|(id, ..)| id.0);
+
+            for (_, child, birth, tile) in children {
+                let age = birth.age_years(&cal);
+                // Parents' ages when the child was born
+                let could_parent = |parent_age: u16, sex: Sex| {
+                    parent_age.checked_sub(age).is_some_and(|at_birth| BirthDate::fertile_ages(sex).contains(&at_birth))
+                };
+                let candidates: Vec<_> = couples
+                    .get(&tile)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(husband, wife)| could_parent(wife.2, Sex::Female) && could_parent(husband.2, Sex::Male))
+                    .collect();
+                let Some(((husband, husband_id, _), (wife, wife_id, _))) = candidates.choose(&mut self.rng) else { continue };
+
+                let household = self.world.get::<&Household>(*wife).ok().map(|h| *h);
+                let _ = self.world.insert(child, (Mother(*wife, *wife_id), Father(*husband, *husband_id), Generation(1))); // Entity from query above
+                if let Some(household) = household {
+                    let _ = self.world.insert_one(child, household); // Entity from query above
+                }
+                if let Ok(mut fertility) = self.world.get::<&mut Fertility>(*wife) {
+                    fertility.children_born = fertility.children_born.saturating_add(1);
+                    if (birth.year, birth.month) > (fertility.last_birth_year, fertility.last_birth_month) {
+                        fertility.last_birth_year = birth.year;
+                        fertility.last_birth_month = birth.month;
+                    }
+                }
+                result.children_placed += 1;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Spawn a designed set of founders, married to each other where a record names a
+    /// `partner` in the batch. Returns the new person ids in record order. Partners must
+    /// be a man and a woman on the same tile, both of the configured marriage age, and
+    /// naming one from either side is enough. On any invalid record, or if the batch
+    /// would go over `max_population`, nothing is spawned.
+    pub fn seed_from_records(&mut self, records: &[SeedPerson]) -> Result<Vec<u64>, String> {
+        if let Some(room) = self.remaining_capacity().filter(|room| records.len() > *room) {
+            return Err(format!("Seeding {} people would exceed max_population; {} more fit", records.len(), room));
+        }
+        let min_marriage_age = |sex: Sex| match sex {
+            Sex::Male => self.marriage_config.min_age_male,
+            Sex::Female => self.marriage_config.min_age_female,
+        };
+        let mut spouse: Vec<Option<usize>> = vec![None; records.len()];
+        for (i, record) in records.iter().enumerate() {
+            let Some(j) = record.partner else { continue };
+            let other = records.get(j).ok_or_else(|| format!("Record {} names partner {}, which is not in the batch", i, j))?;
+            if j == i || other.sex == record.sex {
+                return Err(format!("Records {} and {} can't be partners: they must be a man and a woman", i, j));
+            }
+            if other.tile_id != record.tile_id {
+                return Err(format!("Records {} and {} can't be partners: they are on different tiles", i, j));
+            }
+            for (k, person) in [(i, record), (j, other)] {
+                if person.age < min_marriage_age(person.sex) {
+                    return Err(format!("Record {} is {}, under the minimum marriage age of {}", k, person.age, min_marriage_age(person.sex)));
+                }
+            }
+            for (a, b) in [(i, j), (j, i)] {
+                if spouse[a].is_some_and(|existing| existing != b) {
+                    return Err(format!("Record {} is named as partner of more than one person", a));
+                }
+                spouse[a] = Some(b);
+            }
+        }
+
+        let mut people = Vec::with_capacity(records.len());
+        for record in records {
+            let id = PersonId(self.next_person_id);
+            self.next_person_id += 1;
+            let entity = self.world.spawn((
+                Person { id, first_name: record.first_name.clone(), last_name: record.last_name.clone() },
+                record.sex,
+                BirthDate::from_age(record.age, &self.calendar),
+                TileId(record.tile_id),
+                Occupation::for_age(record.age, &mut self.rng),
+                Health::random(&mut self.rng),
+                Generation(0),
+            ));
+            self.person_index.insert(id.0, entity);
+            self.living_count += 1;
+            people.push((entity, id));
+        }
+        self.mark_caches_synced();
+
+        for (i, j) in spouse.iter().enumerate().filter_map(|(i, j)| j.map(|j| (i, j))) {
+            if i < j {
+                let (husband, wife) = if records[i].sex == Sex::Male { (i, j) } else { (j, i) };
+                systems::matchmaking::wed(&mut self.world, &self.calendar, people[husband], people[wife], &mut self.next_household_id);
+            }
+        }
+        Ok(people.into_iter().map(|(_, id)| id.0).collect())
+    }
+
+    /// Spawn `count` single adult newcomers on a tile with ages in [age_min, age_max].
+    /// Logs an Immigration event per person and returns their person ids.
+    /// Fewer than `count` arrive if that would exceed `max_population`.
+    pub fn immigrate(&mut self, tile_id: u16, count: usize, age_min: u16, age_max: u16) -> Vec<u64> {
+        let count = self.remaining_capacity().map_or(count, |room| count.min(room));
+        let rng = &mut self.rng;
+        let age_min = age_min.max(ADULT_AGE);
+        let age_max = age_max.max(age_min);
+        let mut ids = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let id = PersonId(self.next_person_id);
+            self.next_person_id += 1;
+
+            let sex = if rng.gen::<f64>() < 0.51 { Sex::Male } else { Sex::Female };
+            let age_years = rng.gen_range(age_min..=age_max);
+            let is_male = matches!(sex, Sex::Male);
+
+            let entity = self.world.spawn((
+                Person {
+                    id,
+                    first_name: crate::names::random_first_name(is_male, rng).to_string(),
+                    last_name: crate::names::random_last_name(rng).to_string(),
+                },
+                sex,
+                BirthDate::from_age(age_years, &self.calendar),
+                TileId(tile_id),
+                Occupation::for_age(age_years, rng),
+                Health::random(rng),
+                Generation(0),
+            ));
+            self.person_index.insert(id.0, entity);
+            self.living_count += 1;
+
+            self.event_log.push(Event::with_person(EventType::Immigration, &self.calendar, id.0).on_tile(tile_id));
+            ids.push(id.0);
+        }
+        self.mark_caches_synced();
+
+        ids
+    }
+
+    /// Remove up to `count` random single adults from a tile.
+    /// Logs an Emigration event per person and returns how many left.
+    pub fn emigrate(&mut self, tile_id: u16, count: usize) -> u32 {
+        let mut candidates: Vec<(hecs::Entity, u64)> = self.world
+            .query::<(&Person, &BirthDate, &TileId)>()
+            .without::<&Partner>()
+            .iter()
+            .filter(|(_, (_, birth, tile))| tile.0 == tile_id && birth.age_years(&self.calendar) >= ADULT_AGE)
+            .map(|(entity, (person, _, _))| (entity, person.id.0))
+            .collect();
+
+        candidates.shuffle(&mut self.rng);
+        candidates.truncate(count);
+
+        let leaving: std::collections::HashSet<hecs::Entity> =
+            candidates.iter().map(|(entity, _)| *entity).collect();
+
+        for (entity, person_id) in &candidates {
+            let _ = self.world.despawn(*entity); // Entity from query above
+            self.person_index.remove(person_id);
+            self.living_count -= 1;
+            self.event_log.push(Event::with_person(EventType::Emigration, &self.calendar, *person_id).on_tile(tile_id));
+        }
+        self.mark_caches_synced();
+
+        // Clear any partner links that pointed at an emigrant
+        let dangling: Vec<hecs::Entity> = self.world
+            .query::<&Partner>()
+            .iter()
+            .filter(|(_, partner)| leaving.contains(&partner.0))
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in dangling {
+            let _ = self.world.remove_one::<Partner>(entity); // Entity from query above
+            let _ = self.world.remove_one::<MarriedSince>(entity); // May be absent, safe to ignore
+        }
+
+        candidates.len() as u32
+    }
+
+    /// Ids of living people `offset..offset + limit` in `sort_by` order, plus the total
+    /// number of people. The order depends only on the people themselves, never on ECS
+    /// layout, so repeated calls page consistently while the world is unchanged.
+    pub fn people_page(&self, offset: usize, limit: usize, sort_by: PersonSort) -> (Vec<u64>, usize) {
+        let mut ids: Vec<u64> = match sort_by {
+            PersonSort::PersonId => self.person_index.keys().copied().filter(|id| self.find_person(*id).is_some()).collect(),
+            PersonSort::Age => self.sorted_ids(|_, birth: &BirthDate| std::cmp::Reverse((birth.year, birth.month, birth.day))),
+            PersonSort::Tile => self.sorted_ids(|_, tile: &TileId| tile.0),
+            PersonSort::Name => self.sorted_ids(|person, _: &Person| (person.last_name.clone(), person.first_name.clone())),
+        };
+        if sort_by == PersonSort::PersonId {
+            ids.sort_unstable();
+        }
+        let total = ids.len();
+        (ids.into_iter().skip(offset).take(limit).collect(), total)
+    }
+
+    /// Person ids ordered by `key` of one component, then by id
+    fn sorted_ids<C: hecs::Component, K: Ord>(&self, key: impl Fn(&Person, &C) -> K) -> Vec<u64> {
+        let mut keyed: Vec<(K, u64)> = self.world
+            .query::<(&Person, &C)>()
+            .iter()
+            .map(|(_, (person, component))| (key(person, component), person.id.0))
+            .collect();
+        keyed.sort_unstable();
+        keyed.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Call `f` with a view of every living person, in no particular order
+    pub fn for_each_person<F: FnMut(PersonView)>(&self, mut f: F) {
+        for (_, item) in self.world.query::<PersonViewQuery>().iter() {
+            f(person_view(item));
+        }
+    }
+
+    /// Call `f` with a view of the living person `person_id`; None if there is no such person
+    pub fn with_person<R>(&self, person_id: u64, f: impl FnOnce(PersonView) -> R) -> Option<R> {
+        let entity = self.find_person(person_id)?;
+        let mut query = self.world.query_one::<PersonViewQuery>(entity).ok()?;
+        query.get().map(|item| f(person_view(item)))
+    }
+
+    /// Apply name and tile edits to living people, looked up through the person index.
+    /// Patches for unknown or dead ids are skipped and reported.
+    pub fn apply_person_patches(&mut self, patches: Vec<PersonPatch>) -> PatchResult {
+        let mut result = PatchResult { applied: 0, unknown_ids: Vec::new() };
+        for patch in patches {
+            let Some(entity) = self.find_person(patch.person_id) else {
+                result.unknown_ids.push(patch.person_id);
+                continue;
+            };
+            if let Ok(mut person) = self.world.get::<&mut Person>(entity) {
+                if let Some(first_name) = patch.first_name {
+                    person.first_name = first_name;
+                }
+                if let Some(last_name) = patch.last_name {
+                    person.last_name = last_name;
+                }
+            }
+            if let Some(tile_id) = patch.tile_id {
+                if let Ok(mut tile) = self.world.get::<&mut TileId>(entity) {
+                    tile.0 = tile_id;
+                }
+            }
+            result.applied += 1;
+        }
+        result
+    }
+
+    /// Capture the current state and RNG position as the replay base.
+    /// Call right after seeding, before the first tick.
+    pub fn mark_genesis(&mut self) {
+        self.genesis = Some(Genesis {
+            data: self.build_export_data(),
+            rng: self.rng.clone(),
+        });
+    }
+
+    /// Replace the RNG with a fresh stream seeded from `seed`, e.g. to branch several
+    /// futures from one saved state. This breaks continuity with the original stream:
+    /// the run no longer matches what its old seed would have produced, and the replay
+    /// base is dropped because `replay_to` could not reach the present any more.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = SimRng::seed_from_u64(seed);
+        self.genesis = None;
+    }
+
+    /// Rebuild the world as it was on the target date by re-ticking from genesis.
+    ///
+    /// This relies on the whole run being reproducible: every stochastic decision since
+    /// `mark_genesis` must have come from the world RNG, and nothing may have mutated the
+    /// world outside `tick()` (imports, migration, edits) since genesis.
+    pub fn replay_to(&self, target_year: u16, target_month: u8, target_day: u8) -> Result<SimulationWorld, String> {
+        let genesis = self.genesis.as_ref().ok_or("No genesis state recorded for this world")?;
+        // In range, no target can lie past `Calendar::last()`, where ticking stops advancing
+        if !(1..=Calendar::MONTHS_PER_YEAR).contains(&target_month)
+            || !(1..=Calendar::DAYS_PER_MONTH).contains(&target_day)
+        {
+            return Err(format!(
+                "Invalid target date {}-{}-{}: month must be 1-{} and day 1-{}",
+                target_year, target_month, target_day, Calendar::MONTHS_PER_YEAR, Calendar::DAYS_PER_MONTH
+            ));
+        }
+        let target = Calendar::new(target_year, target_month, target_day);
+        let start = Calendar::new(genesis.data.calendar.year, genesis.data.calendar.month, genesis.data.calendar.day);
+        if target < start {
+            return Err(format!(
+                "Target date {}-{}-{} is before genesis {}-{}-{}",
+                target.year, target.month, target.day, start.year, start.month, start.day
+            ));
+        }
+
+        let mut replay = SimulationWorld::with_seed(self.seed);
+        replay.import_from_export_data(genesis.data.clone())?;
+        replay.rng = genesis.rng.clone();
+        replay.tile_neighbors = self.tile_neighbors.clone();
+        replay.genesis = Some(genesis.clone());
+
+        while replay.calendar < target {
+            replay.tick();
+        }
+        Ok(replay)
+    }
+
+    /// What the next tick would report, without changing this world or its event log.
+    /// Runs the tick on a scratch copy (a full snapshot and import, so O(population))
+    /// with a fork of the RNG. Nothing is drawn from the real RNG, so a preview followed
+    /// by a real tick usually agrees, but the outcome is stochastic and only indicative:
+    /// any change to the world in between can send the real tick elsewhere.
+    pub fn simulate_tick_preview(&self) -> Result<TickResult, String> {
+        let mut scratch = SimulationWorld::with_seed(self.seed);
+        scratch.import_from_export_data(self.build_export_data())?;
+        scratch.rng = self.rng.clone();
+        scratch.tile_neighbors = self.tile_neighbors.clone();
+        scratch.track_tile_events = self.track_tile_events;
+        Ok(scratch.tick())
+    }
+
+    /// Run one simulation tick (advances 1 day).
+    /// Returns a TickResult with births, deaths, marriages, pregnancies, dissolutions, and population.
+    ///
+    /// Canonical system order:
+    /// 1. advance the calendar
+    /// 2. death (despawns the dead)
+    /// 3. dissolution (widows their partners, so no one is married to the dead)
+    /// 4. matchmaking (only living singles, including the newly widowed)
+    /// 5. family: pregnancy, then delivery (mothers lost in childbirth are widowed here)
+    /// 6. occupation and health updates
+    pub fn tick(&mut self) -> TickResult {
+        self.tick_days(1)
+    }
+
+    /// Advance `days` days (at least 1) in a single pass of the systems, for cheap
+    /// fast-forwarding. `tick_days(1)` is exactly `tick()`.
+    ///
+    /// This is an approximation: each daily death and conception chance is compounded
+    /// to the whole step (`1 - (1 - p)^days`) and drawn once, using ages as of the last
+    /// day, so no one can die and conceive, or conceive twice, within a step. Matchmaking,
+    /// deliveries, and occupation changes happen once, on the last day, and every event is
+    /// dated then. Keep steps well under the 9-month gestation to stay close to daily ticking.
+    /// Once the calendar reaches `Calendar::last()` (logging CalendarExhausted the tick it
+    /// gets there) it stays on that day, so ages stop advancing.
+    pub fn tick_days(&mut self, days: u32) -> TickResult {
+        let days = days.max(1);
+        // Pick up anyone spawned or despawned directly through `world` since the last update
+        if self.caches_stale() {
+            self.rebuild_person_index();
+        }
+        let was_last = self.calendar.is_last();
+        self.calendar.advance_days(days);
+        self.ticks_elapsed += 1;
+        if self.calendar.is_last() && !was_last {
+            self.event_log.push(Event::new(EventType::CalendarExhausted, &self.calendar));
+        }
+
+        // Run all systems. With the RNG audit on, each gets the RNG wrapped to count its
+        // draws; otherwise they draw from it directly. Evaluates to (result, draws).
+        macro_rules! audited {
+            ($audit:expr, $source:expr, |$rng:ident| $call:expr) => {
+                if $audit {
+                    let mut counting = systems::CountingRng::new(&mut $source);
+                    let $rng = &mut counting;
+                    let result = $call;
+                    (result, counting.draws)
+                } else {
+                    let $rng = &mut $source;
+                    ($call, 0)
+                }
+            };
+        }
+        let mut audit = systems::RngAudit::default();
+        let (mut deaths, draws) = audited!(self.rng_audit, self.rng, |rng| systems::death_system(
+            &mut self.world,
+            &self.calendar,
+            &self.mortality_config,
+            self.smooth_mortality,
+            days,
+            rng,
+        ));
+        audit.death = draws;
+        self.living_count -= deaths.len();
+        let conception_limit = self.check_capacity();
+        let widowed = systems::dissolution_system(&mut self.world);
+        let (_, draws) = audited!(self.rng_audit, self.rng, |rng| systems::leaving_home_system(
+            &mut self.world,
+            &self.calendar,
+            &self.home_leaving_config,
+            &self.tile_neighbors,
+            days,
+            rng,
+        ));
+        audit.leaving_home = draws;
+        let (marriages, draws) = audited!(self.rng_audit, self.rng, |rng| systems::matchmaking_system(
+            &mut self.world,
+            &self.calendar,
+            &self.marriage_config,
+            &self.tile_neighbors,
+            &mut self.next_household_id,
+            rng,
+        ));
+        audit.matchmaking = draws;
+        let marriage_count = marriages.len() as u32;
+        let (family, _) = audited!(self.rng_audit, self.rng, |rng| systems::family_system(
+            &mut self.world,
+            &self.calendar,
+            &mut self.next_person_id,
+            &self.fertility_config,
+            conception_limit,
+            days,
+            rng,
+        ));
+        self.living_count = self.living_count + family.births.len() - family.maternal_deaths.len();
+        // Mothers lost in childbirth are ordinary deaths from here on
+        deaths.extend_from_slice(&family.maternal_deaths);
+        for death in &deaths {
+            self.person_index.remove(&death.person_id);
+        }
+        for birth in &family.births {
+            self.person_index.insert(birth.person_id, birth.entity);
+        }
+        self.record_vitals(family.births.len() as u32, deaths.len() as u32);
+        for _ in 1..days.min(GROWTH_WINDOW_MAX_DAYS as u32) {
+            self.record_vitals(0, 0); // Keep one growth-window entry per day
+        }
+        let year = self.year_stats.entry(self.calendar.year).or_default();
+        year.births += family.births.len() as u32;
+        year.deaths += deaths.len() as u32;
+        year.marriages += marriage_count;
+        audit.pregnancy = family.pregnancy_draws;
+        audit.delivery = family.delivery_draws;
+        let (_, draws) = audited!(self.rng_audit, self.rng, |rng| {
+            systems::occupation_system(&mut self.world, &self.calendar, rng)
+        });
+        audit.occupation = draws;
+        self.last_rng_audit = self.rng_audit.then_some(audit);
+        systems::health_system(&mut self.world, &self.calendar, days);
+        debug_assert_eq!(
+            self.living_count,
+            self.scan_living_count(),
+            "a system spawned or despawned people without updating the cached population"
+        );
+        self.mark_caches_synced();
+        let population = self.entity_count() as u32;
+        self.year_stats.entry(self.calendar.year).or_default().population = Some(population);
+
+        // Log events to event log (Phase 2)
+        for birth in &family.births {
+            self.event_log.push(Event::with_person(EventType::Birth, &self.calendar, birth.person_id).on_tile(birth.tile_id));
+        }
+        for death in &deaths {
+            self.event_log.push(
+                Event::with_person(EventType::Death, &self.calendar, death.person_id)
+                    .on_tile(death.tile_id)
+                    .with_cause(death.cause),
+            );
+        }
+        for marriage in &marriages {
+            self.event_log.push(Event::new(EventType::Marriage, &self.calendar).on_tile(marriage.tile_id));
+        }
+        for _ in 0..family.new_pregnancies {
+            self.event_log.push(Event::new(EventType::PregnancyStarted, &self.calendar));
+        }
+        let dissolutions = widowed + family.dissolutions;
+        for _ in 0..dissolutions {
+            self.event_log.push(Event::new(EventType::Dissolution, &self.calendar));
+        }
+        for mother_id in &family.stillbirths {
+            self.event_log.push(Event::with_person(EventType::Stillbirth, &self.calendar, *mother_id));
+        }
+        if let Some(years) = self.retention_years {
+            self.event_log.prune_before(self.calendar.year.saturating_sub(years));
+        }
+
+        let mut deaths_by_cause: HashMap<DeathCause, u32> = HashMap::new();
+        for death in &deaths {
+            *deaths_by_cause.entry(death.cause).or_insert(0) += 1;
+        }
+        let mut births_by_tile: HashMap<u16, u32> = HashMap::new();
+        let mut deaths_by_tile: HashMap<u16, u32> = HashMap::new();
+        if self.track_tile_events {
+            for birth in &family.births {
+                *births_by_tile.entry(birth.tile_id).or_insert(0) += 1;
+            }
+            for death in &deaths {
+                *deaths_by_tile.entry(death.tile_id).or_insert(0) += 1;
+            }
+        }
+
+        TickResult {
+            births: family.births.len() as u32,
+            deaths: deaths.len() as u32,
+            marriages: marriage_count,
+            pregnancies: family.new_pregnancies,
+            dissolutions,
+            stillbirths: family.stillbirths.len() as u32,
+            population,
+            birth_ids: family.births.iter().map(|b| b.person_id).collect(),
+            death_ids: deaths.iter().map(|d| d.person_id).collect(),
+            births_by_tile,
+            deaths_by_tile,
+            deaths_by_cause,
+        }
+    }
+
+    /// Run up to `count` ticks, calling `on_tick` after each one. `cancel` is checked
+    /// before every tick so another thread can stop a long run early; the flag is
+    /// left set for the caller to reset. Returns the number of ticks actually run.
+    pub fn tick_many<F: FnMut(&TickResult)>(&mut self, count: u32, cancel: &AtomicBool, on_tick: F) -> u32 {
+        self.tick_many_with_progress(count, cancel, 0, on_tick, |_| {})
+    }
+
+    /// `tick_many` that also calls `on_progress` after every `every` ticks, for progress
+    /// bars on long fast-forwards. `every` = 0 never reports progress.
+    pub fn tick_many_with_progress<F, P>(
+        &mut self,
+        count: u32,
+        cancel: &AtomicBool,
+        every: u32,
+        mut on_tick: F,
+        mut on_progress: P,
+    ) -> u32
+    where
+        F: FnMut(&TickResult),
+        P: FnMut(TickProgress),
+    {
+        let mut ran = 0;
+        while ran < count && !cancel.load(Ordering::Relaxed) {
+            let result = self.tick();
+            on_tick(&result);
+            ran += 1;
+            if every > 0 && ran % every == 0 {
+                on_progress(TickProgress { ticks: ran, total: count, calendar: self.calendar.clone() });
+            }
+        }
+        ran
+    }
+
+    /// Room left under `max_population`, counting pregnancies under way as people.
+    /// None when there is no cap.
+    pub(crate) fn remaining_capacity(&self) -> Option<usize> {
+        let cap = self.max_population? as usize;
+        let expecting = self.world.query::<&Pregnant>().iter().count();
+        Some(cap.saturating_sub(self.entity_count() + expecting))
+    }
+
+    /// How many conceptions may start this tick (None = no cap). Logs CapacityReached
+    /// when the cap is first hit, and again only after the population has dropped back below it.
+    fn check_capacity(&mut self) -> Option<usize> {
+        let room = self.remaining_capacity();
+        let full = room == Some(0);
+        if full && !self.capacity_reached {
+            self.event_log.push(Event::new(EventType::CapacityReached, &self.calendar));
+        }
+        self.capacity_reached = full;
+        room
+    }
+
+    /// Tick until the calendar reaches the first day of `target_year`, returning one
+    /// summary per year ticked through. Ticks are grouped by the year they start in, so
+    /// the tick onto New Year's Day closes out the old year. Stops early (with the years
+    /// so far, the last one partial) if `cancel` is set. Errors if `target_year` has
+    /// already begun; an empty list means the calendar is already there.
+    pub fn tick_until_year(&mut self, target_year: u16, cancel: &AtomicBool) -> Result<Vec<YearSummary>, String> {
+        let start = self.calendar.to_absolute_days();
+        let target = Calendar::new(target_year, 1, 1).to_absolute_days();
+        if start > target {
+            return Err(format!("Already past the start of year {} ({})", target_year, self.calendar.year));
+        }
+
+        let mut summaries: Vec<YearSummary> = Vec::new();
+        let mut day = start;
+        self.tick_many((target - start) as u32, cancel, |result| {
+            let year = Calendar::from_absolute_days(day).year;
+            day += 1;
+            if summaries.last().is_none_or(|s| s.year != year) {
+                summaries.push(YearSummary { year, births: 0, deaths: 0, marriages: 0, population: 0 });
+            }
+            let summary = summaries.last_mut().expect("pushed above");
+            summary.births += result.births;
+            summary.deaths += result.deaths;
+            summary.marriages += result.marriages;
+            summary.population = result.population;
+        });
+        Ok(summaries)
+    }
+
+    fn record_vitals(&mut self, births: u32, deaths: u32) {
+        if self.recent_vitals.len() == GROWTH_WINDOW_MAX_DAYS {
+            self.recent_vitals.pop_front();
+        }
+        self.recent_vitals.push_back((births, deaths));
+    }
+
+    /// Forget recorded births/deaths (e.g. after the world is replaced by a load)
+    pub(crate) fn clear_vitals(&mut self) {
+        self.recent_vitals.clear();
+    }
+
+    /// Replace the person index (e.g. after the world is replaced by a load)
+    pub(crate) fn set_person_index(&mut self, index: HashMap<u64, hecs::Entity>) {
+        self.person_index = index;
+        self.living_count = self.scan_living_count();
+        self.mark_caches_synced();
+    }
+
+    /// Rebuild the person index and population count from the ECS. Ticks do this on
+    /// their own when `world` has gained or lost entities since the caches were last
+    /// updated; call it directly to look people up by id before the next tick.
+    pub fn rebuild_person_index(&mut self) {
+        self.person_index = self.world
+            .query::<&Person>()
+            .iter()
+            .map(|(entity, person)| (person.id.0, entity))
+            .collect();
+        self.living_count = self.scan_living_count();
+        self.mark_caches_synced();
+    }
+
+    /// Record that the person index and count match the ECS as it is now
+    fn mark_caches_synced(&mut self) {
+        self.synced_len = self.world.len();
+    }
+
+    /// Whether people were spawned or despawned directly through `world` since the
+    /// caches were last updated. Misses a despawn and spawn that cancel out.
+    fn caches_stale(&self) -> bool {
+        self.world.len() != self.synced_len
+    }
+
+    /// Maintenance pass for natural breakpoints (e.g. before saving): despawns leftover
+    /// entities that aren't people, clears partners pointing at despawned spouses, and
+    /// rebuilds the person index and population count from a fresh scan.
+    pub fn compact(&mut self) -> CompactResult {
+        let leftovers: Vec<hecs::Entity> = self.world
+            .query::<()>()
+            .without::<&BirthDate>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in &leftovers {
+            let _ = self.world.despawn(*entity); // Entity from query above
+        }
+        let dissolved = systems::dissolution_system(&mut self.world);
+
+        let before = std::mem::take(&mut self.person_index);
+        self.rebuild_person_index();
+        let stale_index_entries = before
+            .iter()
+            .filter(|(id, entity)| self.person_index.get(id) != Some(entity))
+            .count()
+            + self.person_index.keys().filter(|id| !before.contains_key(id)).count();
+
+        CompactResult {
+            despawned: leftovers.len() as u32,
+            stale_index_entries: stale_index_entries as u32,
+            dissolved,
+        }
+    }
+
+    /// Entity of the living person with this id, via the person index
+    pub fn find_person(&self, person_id: u64) -> Option<hecs::Entity> {
+        self.person_index
+            .get(&person_id)
+            .copied()
+            .filter(|entity| self.world.contains(*entity))
+    }
+
+    /// Log a game-specific event (battle, festival, ...) dated today. With a living
+    /// `person_id` the event is also placed on their tile. Labels must be non-empty and
+    /// at most `Event::MAX_LABEL_LEN` bytes.
+    pub fn log_custom_event(&mut self, label: &str, person_id: Option<u64>) -> Result<(), String> {
+        if label.is_empty() || label.len() > Event::MAX_LABEL_LEN {
+            return Err(format!("Event label must be 1 to {} bytes long", Event::MAX_LABEL_LEN));
+        }
+        let mut event = Event::custom(label, &self.calendar);
+        if let Some(id) = person_id {
+            let entity = self.find_person(id).ok_or_else(|| format!("Person {} not found", id))?;
+            event.person_id = Some(id);
+            event.tile_id = self.world.get::<&TileId>(entity).ok().map(|tile| tile.0);
+        }
+        self.event_log.push(event);
+        Ok(())
+    }
+
+    /// Living descendants of a person (children, grandchildren, ...), nearest
+    /// generation first and by person id within a generation. Walks Mother/Father
+    /// links breadth-first without recursion, so deep family trees are safe.
+    /// Lines through a descendant who has died are lost with them, since the
+    /// dead are despawned. Empty if the person is not alive.
+    pub fn living_lineage(&self, person_id: u64) -> Vec<u64> {
+        let Some(root) = self.find_person(person_id) else {
+            return Vec::new();
+        };
+
+        let mut children: HashMap<hecs::Entity, Vec<(u64, hecs::Entity)>> = HashMap::new();
+        for (entity, (person, mother, father)) in self.world
+            .query::<(&Person, Option<&Mother>, Option<&Father>)>()
+            .iter()
+        {
+            for parent in mother.map(|m| m.0).into_iter().chain(father.map(|f| f.0)) {
+                children.entry(parent).or_default().push((person.id.0, entity));
+            }
+        }
+
+        let mut seen = std::collections::HashSet::from([root]);
+        let mut lineage = Vec::new();
+        let mut generation = vec![root];
+        while !generation.is_empty() {
+            let mut next: Vec<(u64, hecs::Entity)> = generation
+                .iter()
+                .flat_map(|parent| children.get(parent).into_iter().flatten().copied())
+                .filter(|(_, child)| seen.insert(*child))
+                .collect();
+            next.sort_unstable_by_key(|(id, _)| *id);
+            lineage.extend(next.iter().map(|(id, _)| *id));
+            generation = next.into_iter().map(|(_, child)| child).collect();
+        }
+        lineage
+    }
+
+    /// Number of living descendants of a person (see `living_lineage`)
+    pub fn descendant_count(&self, person_id: u64) -> u32 {
+        self.living_lineage(person_id).len() as u32
+    }
+
+    /// Generation of a living person (founders are 0); None if they are not alive
+    pub fn generation_of(&self, person_id: u64) -> Option<u16> {
+        let entity = self.find_person(person_id)?;
+        Some(self.world.get::<&Generation>(entity).map(|g| g.0).unwrap_or(0))
+    }
+
+    /// Highest generation among the living (0 if only founders, or no one, are alive)
+    pub fn max_generation(&self) -> u16 {
+        self.world.query::<&Generation>().iter().map(|(_, g)| g.0).max().unwrap_or(0)
+    }
+
+    /// Whole years a living person has been married to their current, living partner.
+    /// None if they are not alive or not married.
+    pub fn marriage_duration_years(&self, person_id: u64) -> Option<u16> {
+        let entity = self.find_person(person_id)?;
+        let partner = self.world.get::<&Partner>(entity).ok()?;
+        if !self.world.contains(partner.0) {
+            return None;
+        }
+        let since = self.world.get::<&MarriedSince>(entity).ok()?;
+        Some(since.years_married(&self.calendar) as u16)
+    }
+
+    /// Mean length in years (to the month) of current marriages, each couple counted
+    /// once. Marriages ended by death are not included. None if no one is married.
+    pub fn average_marriage_duration(&self) -> Option<f64> {
+        let mut total_months: u64 = 0;
+        let mut couples: u64 = 0;
+        for (_, (person, partner, since)) in self.world.query::<(&Person, &Partner, &MarriedSince)>().iter() {
+            if person.id.0 < partner.1.0 && self.world.contains(partner.0) {
+                total_months += since.months_married(&self.calendar) as u64;
+                couples += 1;
+            }
+        }
+        (couples > 0).then(|| total_months as f64 / couples as f64 / Calendar::MONTHS_PER_YEAR as f64)
+    }
+
+    /// Conception diagnostics for a living woman (see `systems::fertility_status`).
+    /// None if the person is not alive or not a woman.
+    pub fn fertility_status(&self, person_id: u64) -> Option<systems::FertilityStatus> {
+        let entity = self.find_person(person_id)?;
+        systems::fertility_status(&self.world, entity, &self.calendar, &self.fertility_config)
+    }
+
+    /// Days elapsed since `start_date` (0 at genesis)
+    pub fn days_since_start(&self) -> u64 {
+        self.calendar.to_absolute_days().saturating_sub(self.start_date.to_absolute_days())
+    }
+
+    /// Date `days` days after `start_date`; the inverse of `days_since_start`
+    pub fn date_after_start(&self, days: u64) -> Calendar {
+        Calendar::from_absolute_days(self.start_date.to_absolute_days().saturating_add(days))
+    }
+
+    /// Partnered women who could conceive today: in the age window, past the birth
+    /// interval and any post-marriage delay, and not already pregnant
+    pub fn fertile_partnered_women(&self) -> u32 {
+        self.world
+            .query::<()>()
+            .with::<&Partner>()
+            .iter()
+            .filter(|(entity, _)| self.can_conceive(*entity))
+            .count() as u32
+    }
+
+    fn can_conceive(&self, entity: hecs::Entity) -> bool {
+        systems::fertility_status(&self.world, entity, &self.calendar, &self.fertility_config)
+            .is_some_and(|status| status.can_conceive())
+    }
+
+    /// RNG draws per system in the last tick, if `rng_audit` was on for it.
+    /// Identically seeded runs match tick for tick until they diverge.
+    pub fn last_tick_rng_audit(&self) -> Option<systems::RngAudit> {
+        self.last_rng_audit
+    }
+
+    /// Natural increase over the last `window_days` ticks (capped at GROWTH_WINDOW_MAX_DAYS),
+    /// annualized against the population at the start of the window. Migration is ignored.
+    pub fn growth_rate(&self, window_days: u32) -> GrowthRate {
+        let window = (window_days as usize).min(self.recent_vitals.len());
+        let natural_increase: i64 = self.recent_vitals
+            .iter()
+            .rev()
+            .take(window)
+            .map(|(births, deaths)| *births as i64 - *deaths as i64)
+            .sum();
+
+        let start_population = self.entity_count() as i64 - natural_increase;
+        let annual_growth_percent = if window == 0 || start_population <= 0 {
+            0.0
+        } else {
+            let years = window as f64 / Calendar::DAYS_PER_YEAR as f64;
+            natural_increase as f64 / start_population as f64 / years * 100.0
+        };
+
+        GrowthRate {
+            natural_increase,
+            annual_growth_percent,
+            window_days: window as u32,
+        }
+    }
+
+    /// Get entity count (all entities with BirthDate component = people).
+    /// Cached, falling back to a scan after `world` was edited directly.
+    pub fn entity_count(&self) -> usize {
+        if self.caches_stale() {
+            return self.scan_living_count();
+        }
+        self.living_count
+    }
+
+    /// Count people with a full ECS scan, for reconciling the cached count
+    fn scan_living_count(&self) -> usize {
+        self.world.query::<&BirthDate>().iter().count()
+    }
+    
+    /// Approximate memory breakdown of the ECS and event log, for profiling
+    pub fn memory_report(&self) -> MemoryReport {
+        use std::any::TypeId;
+        use std::mem::size_of;
+
+        // Known component sizes; unknown component types count as 0 bytes
+        let component_size = |id: TypeId| -> usize {
+            let sizes = [
+                (TypeId::of::<Person>(), size_of::<Person>()),
+                (TypeId::of::<Sex>(), size_of::<Sex>()),
+                (TypeId::of::<BirthDate>(), size_of::<BirthDate>()),
+                (TypeId::of::<TileId>(), size_of::<TileId>()),
+                (TypeId::of::<Partner>(), size_of::<Partner>()),
+                (TypeId::of::<Mother>(), size_of::<Mother>()),
+                (TypeId::of::<Father>(), size_of::<Father>()),
+                (TypeId::of::<MarriedSince>(), size_of::<MarriedSince>()),
+                (TypeId::of::<Household>(), size_of::<Household>()),
+                (TypeId::of::<Pregnant>(), size_of::<Pregnant>()),
+                (TypeId::of::<Fertility>(), size_of::<Fertility>()),
+                (TypeId::of::<Occupation>(), size_of::<Occupation>()),
+                (TypeId::of::<Health>(), size_of::<Health>()),
+                (TypeId::of::<Generation>(), size_of::<Generation>()),
+            ];
+            sizes.iter().find(|(t, _)| *t == id).map(|(_, s)| *s).unwrap_or(0)
+        };
+
+        let mut archetypes = 0u32;
+        let mut component_bytes = 0u64;
+        for archetype in self.world.archetypes() {
+            if archetype.is_empty() {
+                continue;
+            }
+            archetypes += 1;
+            let row_bytes: usize = archetype.component_types().map(component_size).sum();
+            component_bytes += archetype.len() as u64 * row_bytes as u64;
+        }
+
+        // Names live on the heap outside the archetype columns
+        let name_bytes: u64 = self.world
+            .query::<&Person>()
+            .iter()
+            .map(|(_, p)| (p.first_name.capacity() + p.last_name.capacity()) as u64)
+            .sum();
+
+        let entities = self.world.len();
+        let entity_meta_bytes = entities as u64 * size_of::<hecs::Entity>() as u64;
+
+        MemoryReport {
+            entities,
+            archetypes,
+            approx_ecs_bytes: component_bytes + name_bytes + entity_meta_bytes,
+            event_log_bytes: (self.event_log.len() * size_of::<Event>()) as u64,
+        }
+    }
+
+    /// Get current calendar year
+    pub fn current_year(&self) -> u16 {
+        self.calendar.year
+    }
+
+    // ========================================================================
+    // Statistics queries (Phase 2)
+    // ========================================================================
+
+    /// Population count for a specific tile
+    pub fn tile_population(&self, tile_id: u16) -> u32 {
+        self.world.query::<&TileId>()
+            .iter()
+            .filter(|(_, t)| t.0 == tile_id)
+            .count() as u32
+    }
+
+    /// Population count per tile
+    pub fn population_by_tile(&self) -> HashMap<u16, u32> {
+        let mut map: HashMap<u16, u32> = HashMap::new();
+        for (_, tile) in self.world.query::<&TileId>().iter() {
+            *map.entry(tile.0).or_insert(0) += 1;
+        }
+        map
+    }
+
+    /// (males, females) per occupied tile
+    pub fn sex_ratio_by_tile(&self) -> HashMap<u16, (u32, u32)> {
+        let mut map: HashMap<u16, (u32, u32)> = HashMap::new();
+        for (_, (tile, sex)) in self.world.query::<(&TileId, &Sex)>().iter() {
+            let counts = map.entry(tile.0).or_insert((0, 0));
+            match sex {
+                Sex::Male => counts.0 += 1,
+                Sex::Female => counts.1 += 1,
+            }
+        }
+        map
+    }
+
+    /// Number of tiles falling into each population bin. `bins` are inclusive upper
+    /// counts in ascending order, so `[0, 10, 50]` counts tiles with 0, 1–10, 11–50 and
+    /// over 50 people; there are `bins.len() + 1` counts. Unsorted or repeated bins are
+    /// sorted and deduplicated first. Empty tiles are only known through the adjacency graph.
+    pub fn tile_population_distribution(&self, bins: &[u32]) -> Vec<u32> {
+        let mut bins = bins.to_vec();
+        bins.sort_unstable();
+        bins.dedup();
+
+        let mut populations = self.population_by_tile();
+        for tile in self.tile_neighbors.keys() {
+            populations.entry(*tile).or_insert(0);
+        }
+
+        let mut counts = vec![0u32; bins.len() + 1];
+        for count in populations.into_values() {
+            counts[bins.partition_point(|bin| *bin < count)] += 1;
+        }
+        counts
+    }
+
+    /// The `n` most populous tiles as (tile, population), largest first; ties go to the lower tile id
+    pub fn largest_tiles(&self, n: usize) -> Vec<(u16, u32)> {
+        let mut tiles: Vec<(u16, u32)> = self.population_by_tile().into_iter().collect();
+        tiles.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        tiles.truncate(n);
+        tiles
+    }
+
+    /// Minors (under ADULT_AGE) whose mother has died, as has their father if one is
+    /// recorded. Founders without a Mother are never orphans. Sorted by person id.
+    pub fn orphans(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = self.orphans_with_tile().into_iter().map(|(id, _)| id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Orphan count per tile (see `orphans`); tiles without orphans are absent
+    pub fn orphan_count_by_tile(&self) -> HashMap<u16, u32> {
+        let mut map: HashMap<u16, u32> = HashMap::new();
+        for (_, tile) in self.orphans_with_tile() {
+            *map.entry(tile).or_insert(0) += 1;
+        }
+        map
+    }
+
+    fn orphans_with_tile(&self) -> Vec<(u64, u16)> {
+        self.world
+            .query::<(&Person, &BirthDate, &TileId, &Mother, Option<&Father>)>()
+            .iter()
+            .filter(|(_, (_, birth, _, mother, father))| {
+                birth.age_years(&self.calendar) < ADULT_AGE
+                    && !self.world.contains(mother.0)
+                    && father.is_none_or(|f| !self.world.contains(f.0))
+            })
+            .map(|(_, (person, _, tile, _, _))| (person.id.0, tile.0))
+            .collect()
+    }
+
+    /// Replace the tile adjacency graph
+    pub fn set_tile_adjacency(&mut self, adjacency: HashMap<u16, Vec<u16>>) {
+        self.tile_neighbors = adjacency;
+    }
+
+    /// Move everyone on a mapped tile to its target tile in a single pass.
+    /// Tiles not in the mapping stay put. The adjacency graph is remapped too: merged
+    /// tiles share their neighbor lists and links that collapse onto themselves are dropped.
+    /// Returns the number of people moved.
+    pub fn remap_tiles(&mut self, mapping: &HashMap<u16, u16>) -> u32 {
+        let mut moved = 0;
+        for (_, tile) in self.world.query_mut::<&mut TileId>() {
+            if let Some(&target) = mapping.get(&tile.0) {
+                if target != tile.0 {
+                    tile.0 = target;
+                    moved += 1;
+                }
+            }
+        }
+
+        if !self.tile_neighbors.is_empty() {
+            let remap = |tile: u16| mapping.get(&tile).copied().unwrap_or(tile);
+            let mut adjacency: HashMap<u16, Vec<u16>> = HashMap::new();
+            for (tile, neighbors) in self.tile_neighbors.drain() {
+                let tile = remap(tile);
+                let merged = adjacency.entry(tile).or_default();
+                merged.extend(neighbors.into_iter().map(remap).filter(|&n| n != tile));
+            }
+            for neighbors in adjacency.values_mut() {
+                neighbors.sort_unstable();
+                neighbors.dedup();
+            }
+            self.tile_neighbors = adjacency;
+        }
+
+        moved
+    }
+
+    /// Crowding of a tile relative to its neighbors: this tile's population divided by
+    /// the average population of its neighbors (1.0 = balanced).
+    /// Tiles with no neighbors return 1.0.
+    pub fn tile_pressure(&self, tile_id: u16) -> f64 {
+        let populations = self.population_by_tile();
+        Self::pressure_from(&populations, tile_id, self.neighbors_of(tile_id))
+    }
+
+    /// Pressure for every tile that is populated or present in the adjacency graph
+    pub fn all_tile_pressures(&self) -> HashMap<u16, f64> {
+        let populations = self.population_by_tile();
+        let tiles: std::collections::HashSet<u16> = populations
+            .keys()
+            .chain(self.tile_neighbors.keys())
+            .copied()
+            .collect();
+
+        tiles
+            .into_iter()
+            .map(|tile| (tile, Self::pressure_from(&populations, tile, self.neighbors_of(tile))))
+            .collect()
+    }
+
+    /// Neighbors of a tile in the adjacency graph (empty if unknown)
+    pub fn neighbors_of(&self, tile_id: u16) -> &[u16] {
+        self.tile_neighbors.get(&tile_id).map(|n| n.as_slice()).unwrap_or(&[])
+    }
+
+    fn pressure_from(populations: &HashMap<u16, u32>, tile_id: u16, neighbors: &[u16]) -> f64 {
+        if neighbors.is_empty() {
+            return 1.0;
+        }
+        let own = populations.get(&tile_id).copied().unwrap_or(0) as f64;
+        let neighbor_total: u32 = neighbors
+            .iter()
+            .map(|n| populations.get(n).copied().unwrap_or(0))
+            .sum();
+        let neighbor_avg = neighbor_total as f64 / neighbors.len() as f64;
+
+        if neighbor_avg == 0.0 {
+            // Empty surroundings: balanced if also empty, otherwise as crowded as its own headcount
+            return if own == 0.0 { 1.0 } else { own };
+        }
+        own / neighbor_avg
+    }
+
+    /// Occupation counts per tile
+    pub fn workforce_by_tile(&self) -> HashMap<u16, HashMap<Occupation, u32>> {
+        let mut map: HashMap<u16, HashMap<Occupation, u32>> = HashMap::new();
+        for (_, (tile, occupation)) in self.world.query::<(&TileId, &Occupation)>().iter() {
+            *map.entry(tile.0).or_default().entry(*occupation).or_insert(0) += 1;
+        }
+        map
+    }
+
+    /// Single men and women old enough to marry under `marriage_config`, by person id
+    pub fn eligible_singles(&self) -> (Vec<u64>, Vec<u64>) {
+        self.eligible_singles_where(|_| true)
+    }
+
+    /// Like `eligible_singles`, limited to people living on `tile_id`
+    pub fn eligible_singles_on_tile(&self, tile_id: u16) -> (Vec<u64>, Vec<u64>) {
+        self.eligible_singles_where(|tile| tile == tile_id)
+    }
+
+    fn eligible_singles_where<F: Fn(u16) -> bool>(&self, include_tile: F) -> (Vec<u64>, Vec<u64>) {
+        let mut men = Vec::new();
+        let mut women = Vec::new();
+        for (_, (person, birth, sex, tile)) in self.world
+            .query::<(&Person, &BirthDate, &Sex, &TileId)>()
+            .without::<&Partner>()
+            .iter()
+        {
+            if !include_tile(tile.0) {
+                continue;
+            }
+            let (min_age, list) = match sex {
+                Sex::Male => (self.marriage_config.min_age_male, &mut men),
+                Sex::Female => (self.marriage_config.min_age_female, &mut women),
+            };
+            if birth.age_years(&self.calendar) >= min_age {
+                list.push(person.id.0);
+            }
+        }
+        men.sort_unstable();
+        women.sort_unstable();
+        (men, women)
+    }
+
+    /// Number of women at each parity (children born), at any age.
+    /// Women without a Fertility component count as parity 0.
+    pub fn women_by_parity(&self) -> HashMap<u8, u32> {
+        let mut map: HashMap<u8, u32> = HashMap::new();
+        for (_, (sex, fertility)) in self.world.query::<(&Sex, Option<&Fertility>)>().iter() {
+            if *sex == Sex::Female {
+                let parity = fertility.map(|f| f.children_born).unwrap_or(0);
+                *map.entry(parity).or_insert(0) += 1;
+            }
+        }
+        map
+    }
+
+    /// Number of women at each completed family size: children born to women past
+    /// `BirthDate::MAX_FERTILE_AGE_FEMALE`, whose families can no longer grow. Women
+    /// still carrying a child conceived in time are left out until they deliver.
+    /// Women without a Fertility component count as having had none.
+    pub fn completed_family_sizes(&self) -> HashMap<u8, u32> {
+        let mut map: HashMap<u8, u32> = HashMap::new();
+        for (_, (sex, birth, fertility)) in self.world
+            .query::<(&Sex, &BirthDate, Option<&Fertility>)>()
+            .without::<&Pregnant>()
+            .iter()
+        {
+            if *sex == Sex::Female && birth.age_years(&self.calendar) > BirthDate::MAX_FERTILE_AGE_FEMALE {
+                let children = fertility.map(|f| f.children_born).unwrap_or(0);
+                *map.entry(children).or_insert(0) += 1;
+            }
+        }
+        map
+    }
+
+    /// Mean of `completed_family_sizes`; None if no woman has left the fertile window
+    pub fn mean_completed_family_size(&self) -> Option<f64> {
+        let (women, children) = self.completed_family_sizes()
+            .into_iter()
+            .fold((0u64, 0u64), |(w, c), (size, count)| (w + count as u64, c + size as u64 * count as u64));
+        (women > 0).then(|| children as f64 / women as f64)
+    }
+
+    /// Full demographics snapshot in a single pass
+    pub fn demographics(&self) -> Demographics {
+        Self::fixed_brackets(self.demographics_pass(&DEMOGRAPHIC_BRACKETS, None))
+    }
+
+    /// `demographics()` for the people on one tile (all zeros if it's empty)
+    pub fn tile_demographics(&self, tile_id: u16) -> Demographics {
+        Self::fixed_brackets(self.demographics_pass(&DEMOGRAPHIC_BRACKETS, Some(tile_id)))
+    }
+
+    fn fixed_brackets(custom: CustomDemographics) -> Demographics {
+        let mut age_brackets = [0u32; 7];
+        age_brackets.copy_from_slice(&custom.age_brackets);
+
+        Demographics {
+            population: custom.population,
+            males: custom.males,
+            females: custom.females,
+            partnered: custom.partnered,
+            single: custom.single,
+            pregnant: custom.pregnant,
+            fertile_women: custom.fertile_women,
+            average_age: custom.average_age,
+            age_brackets,
+        }
+    }
+
+    /// Demographics snapshot bucketed by caller-supplied age brackets.
+    /// `bounds` are inclusive upper ages in ascending order; ages above the last
+    /// bound fall into one final bracket, so there are `bounds.len() + 1` counts.
+    /// Unsorted or repeated bounds are sorted and deduplicated first.
+    pub fn demographics_with_brackets(&self, bounds: &[u16]) -> CustomDemographics {
+        self.demographics_pass(bounds, None)
+    }
+
+    /// Single pass behind the demographics queries, over one tile or (None) everyone
+    fn demographics_pass(&self, bounds: &[u16], tile_id: Option<u16>) -> CustomDemographics {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut males: u32 = 0;
+        let mut females: u32 = 0;
+        let mut partnered: u32 = 0;
+        let mut single: u32 = 0;
+        let mut pregnant: u32 = 0;
+        let mut fertile_women: u32 = 0;
+        let mut age_brackets = vec![0u32; bounds.len() + 1];
+        let mut total_age: u64 = 0;
+        let mut count: u32 = 0;
+
+        for (entity, (birth, sex, tile)) in self.world.query::<(&BirthDate, &Sex, Option<&TileId>)>().iter() {
+            if tile_id.is_some_and(|wanted| tile.is_none_or(|t| t.0 != wanted)) {
+                continue;
+            }
+            count += 1;
+            let years = birth.age_years(&self.calendar);
+            total_age += years as u64;
+
+            match sex {
+                Sex::Male => males += 1,
+                Sex::Female => females += 1,
+            }
+
+            // Age bracket: first bound at or above this age
+            let bracket = bounds.partition_point(|bound| *bound < years);
+            age_brackets[bracket] += 1;
+
+            // Partnership
+            if self.world.get::<&Partner>(entity).is_ok() {
+                partnered += 1;
+                if *sex == Sex::Female && self.can_conceive(entity) {
+                    fertile_women += 1;
+                }
+            } else {
+                single += 1;
+            }
+            
+            // Pregnancy
+            if self.world.get::<&Pregnant>(entity).is_ok() {
+                pregnant += 1;
+            }
+        }
+
+        let average_age = if count > 0 { total_age as f64 / count as f64 } else { 0.0 };
+
+        CustomDemographics {
+            population: count,
+            males,
+            females,
+            partnered,
+            single,
+            pregnant,
+            fertile_women,
+            average_age,
+            bounds,
+            age_brackets,
+        }
+    }
+}
+
+/// What `compact` cleaned up
+#[derive(serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompactResult {
+    /// Entities despawned for no longer being people (no BirthDate)
+    pub despawned: u32,
+    /// Person index entries dropped or repointed
+    pub stale_index_entries: u32,
+    /// Partners cleared because their spouse no longer exists
+    pub dissolved: u32,
+}
+
+/// Approximate memory usage breakdown
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct MemoryReport {
+    /// Live entities in the ECS
+    pub entities: u32,
+    /// Non-empty archetypes
+    pub archetypes: u32,
+    /// Estimated component storage, name strings and entity metadata
+    pub approx_ecs_bytes: u64,
+    /// Estimated event log storage
+    pub event_log_bytes: u64,
+}
+
+/// Full demographics snapshot
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Demographics {
+    pub population: u32,
+    pub males: u32,
+    pub females: u32,
+    pub partnered: u32,
+    pub single: u32,
+    pub pregnant: u32,
+    /// Partnered women able to conceive today (see `fertile_partnered_women`)
+    pub fertile_women: u32,
+    pub average_age: f64,
+    /// [0-4, 5-14, 15-29, 30-49, 50-69, 70-89, 90+]
+    pub age_brackets: [u32; 7],
+}
+
+/// Upper ages of the fixed `Demographics` brackets: 0-4, 5-14, 15-29, 30-49, 50-69, 70-89, 90+
+const DEMOGRAPHIC_BRACKETS: [u16; 6] = [4, 14, 29, 49, 69, 89];
+
+/// Demographics snapshot with caller-chosen age brackets
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct CustomDemographics {
+    pub population: u32,
+    pub males: u32,
+    pub females: u32,
+    pub partnered: u32,
+    pub single: u32,
+    pub pregnant: u32,
+    /// Partnered women able to conceive today (see `fertile_partnered_women`)
+    pub fertile_women: u32,
+    pub average_age: f64,
+    /// Inclusive upper age of each bracket but the last, ascending
+    pub bounds: Vec<u16>,
+    /// Count per bracket: `bounds.len() + 1` entries, the last open-ended
+    pub age_brackets: Vec<u32>,
+}
+
+/// Vital statistics (Phase 3) - calculated from event log
+#[derive(serde::Serialize, Clone)]
+pub struct VitalStatistics {
+    /// Births per 1000 population per year
+    pub birth_rate: f64,
+    /// Deaths per 1000 population per year
+    pub death_rate: f64,
+    /// Marriages per 1000 population per year
+    pub marriage_rate: f64,
+    /// Natural increase rate (births - deaths) per 1000 population per year
+    pub natural_increase_rate: f64,
+    /// Total births in period
+    pub total_births: u32,
+    /// Total deaths in period
+    pub total_deaths: u32,
+    /// Total marriages in period
+    pub total_marriages: u32,
+    /// Population (average over period)
+    pub population: u32,
+    /// Period length in years
+    pub period_years: f64,
+}
+
+impl SimulationWorld {
+    /// Calculate vital statistics from event log for a date range
+    ///
+    /// # Arguments
+    /// * `start_year` - Start year (inclusive)
+    /// * `end_year` - End year (inclusive)
+    ///
+    /// # Returns
+    /// VitalStatistics for the specified period
+    pub fn calculate_vital_statistics(&self, start_year: u16, end_year: u16) -> VitalStatistics {
+        let totals = self.period_totals(start_year, end_year);
+        let (births, deaths, marriages) = (totals.births, totals.deaths, totals.marriages);
+
+        let population = self.entity_count() as u32;
+        let period_years = end_year.saturating_sub(start_year) as f64 + 1.0;
+
+        // Calculate rates per 1000 population per year
+        let pop_factor = if population > 0 { 1000.0 / (population as f64 * period_years) } else { 0.0 };
+
+        let birth_rate = births as f64 * pop_factor * period_years;
+        let death_rate = deaths as f64 * pop_factor * period_years;
+        let marriage_rate = marriages as f64 * pop_factor * period_years;
+        let natural_increase_rate = birth_rate - death_rate;
+
+        VitalStatistics {
+            birth_rate,
+            death_rate,
+            marriage_rate,
+            natural_increase_rate,
+            total_births: births,
+            total_deaths: deaths,
+            total_marriages: marriages,
+            population,
+            period_years,
+        }
+    }
+
+    /// Counts for one year as recorded by `tick`, or None if no tick ran in it
+    /// (before this world started, or in a version 1 save)
+    pub fn stats_for_year(&self, year: u16) -> Option<YearStats> {
+        self.year_stats.get(&year).copied()
+    }
+
+    /// End-of-year population for each year in `start_year..=end_year`, oldest first; the
+    /// current year reports its latest tick. Years with no recorded population (never
+    /// ticked, or from a version 1 save) are left out rather than zero-filled.
+    pub fn population_series(&self, start_year: u16, end_year: u16) -> Vec<(u16, u32)> {
+        let mut series: Vec<(u16, u32)> = self.year_stats
+            .iter()
+            .filter(|(year, _)| (start_year..=end_year).contains(*year))
+            .filter_map(|(year, stats)| Some((*year, stats.population?)))
+            .collect();
+        series.sort_unstable();
+        series
+    }
+
+    /// Summed counts for a range of years: materialized where recorded, otherwise
+    /// counted from whatever the event log still holds for that year
+    fn period_totals(&self, start_year: u16, end_year: u16) -> YearStats {
+        let mut totals = YearStats::default();
+        for (_, stats) in self.year_stats.iter().filter(|(year, _)| (start_year..=end_year).contains(*year)) {
+            totals.births += stats.births;
+            totals.deaths += stats.deaths;
+            totals.marriages += stats.marriages;
+        }
+        for event in self.event_log
+            .iter_by_date_range(start_year, end_year)
+            .filter(|event| !self.year_stats.contains_key(&event.year))
+        {
+            match event.event_type {
+                EventType::Birth => totals.births += 1,
+                EventType::Death => totals.deaths += 1,
+                EventType::Marriage => totals.marriages += 1,
+                _ => {}
+            }
+        }
+        totals
+    }
+
+    /// Calculate vital statistics for the current year only
+    pub fn calculate_current_year_statistics(&self) -> VitalStatistics {
+        let year = self.calendar.year;
+        self.calculate_vital_statistics(year, year)
+    }
+
+    /// Calculate vital statistics for the last N years
+    pub fn calculate_recent_statistics(&self, years: u16) -> VitalStatistics {
+        let end_year = self.calendar.year;
+        let start_year = end_year.saturating_sub(years.saturating_sub(1));
+        self.calculate_vital_statistics(start_year, end_year)
+    }
+}
+
+impl Default for SimulationWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_with_age_profile() {
+        let mut world = SimulationWorld::with_seed(8);
+        world.seed_population_with_profile(50, 1, &AgeProfile::Weighted(vec![(20, 1.0)])).unwrap();
+        let demographics = world.demographics();
+        assert_eq!(demographics.population, 50);
+        assert_eq!(demographics.average_age, 20.0);
+        assert_eq!(demographics.age_brackets, [0, 0, 50, 0, 0, 0, 0]);
+
+        world.seed_population_with_profile(200, 2, &AgeProfile::Exponential { mean: 10.0, max: 40 }).unwrap();
+        let ages: Vec<u16> = world.world
+            .query::<(&BirthDate, &TileId)>()
+            .iter()
+            .filter(|(_, (_, tile))| tile.0 == 2)
+            .map(|(_, (birth, _))| birth.age_years(&world.calendar))
+            .collect();
+        assert!(ages.iter().all(|age| *age <= 40));
+        assert!(ages.iter().filter(|age| **age < 20).count() > ages.len() / 2);
+
+        // Invalid profiles seed no one
+        assert!(world.seed_population_with_profile(5, 3, &AgeProfile::Weighted(vec![(20, 0.0)])).is_err());
+        assert!(world.seed_population_with_profile(5, 3, &AgeProfile::Uniform { min: 30, max: 20 }).is_err());
+        assert_eq!(world.tile_population(3), 0);
+    }
+
+    #[test]
+    fn test_eligible_singles() {
+        let mut world = SimulationWorld::with_seed(4);
+        world.marriage_config.min_age_female = 18;
+        let cal = world.calendar.clone();
+        let w = &mut world.world;
+        let mut spawn = |id: u64, sex: Sex, age: u16, tile: u16| {
+            let person = Person::unnamed(id);
+            w.spawn((person, sex, BirthDate::from_age(age, &cal), TileId(tile)))
+        };
+        spawn(1, Sex::Male, 30, 1);
+        spawn(2, Sex::Male, 16, 1);
+        spawn(3, Sex::Male, 15, 1); // too young
+        spawn(4, Sex::Female, 17, 1); // too young under the raised female age
+        spawn(5, Sex::Female, 25, 2);
+        let husband = spawn(6, Sex::Male, 40, 2);
+        let wife = spawn(7, Sex::Female, 38, 2);
+        world.world.insert_one(husband, Partner(wife, PersonId(7))).unwrap();
+        world.world.insert_one(wife, Partner(husband, PersonId(6))).unwrap();
+
+        assert_eq!(world.eligible_singles(), (vec![1, 2], vec![5]));
+        assert_eq!(world.eligible_singles_on_tile(1), (vec![1, 2], vec![]));
+        assert_eq!(world.eligible_singles_on_tile(2), (vec![], vec![5]));
+    }
+
+    #[test]
+    fn test_demographics_with_decade_brackets() {
+        let mut world = SimulationWorld::with_seed(12);
+        world.seed_population_on_tile(300, 1);
+
+        let decades: Vec<u16> = (1..10).map(|d| d * 10 - 1).collect();
+        let custom = world.demographics_with_brackets(&decades);
+        assert_eq!(custom.age_brackets.len(), 10);
+        assert_eq!(custom.age_brackets.iter().sum::<u32>(), custom.population);
+        assert_eq!(custom.population, 300);
+
+        // The fixed brackets agree with the equivalent custom ones
+        let fixed = world.demographics();
+        let same = world.demographics_with_brackets(&[89, 4, 14, 29, 49, 69, 14]);
+        assert_eq!(same.age_brackets, fixed.age_brackets.to_vec());
+        assert_eq!(same.average_age, fixed.average_age);
+
+        // No bounds means one bracket holding everyone
+        assert_eq!(world.demographics_with_brackets(&[]).age_brackets, vec![300]);
+    }
+
+    #[test]
+    fn test_immigrate_adds_adults_and_logs() {
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(10, 1);
+
+        let ids = world.immigrate(2, 5, 20, 30);
+        assert_eq!(ids.len(), 5);
+        assert_eq!(world.entity_count(), 15);
+        assert_eq!(world.tile_population(2), 5);
+        assert_eq!(world.event_log.iter_by_type(EventType::Immigration).count(), 5);
+
+        for (_, (birth, tile)) in world.world.query::<(&BirthDate, &TileId)>().iter() {
+            if tile.0 == 2 {
+                let age = birth.age_years(&world.calendar);
+                assert!((20..=30).contains(&age));
+            }
+        }
+    }
+
+    #[test]
+    fn test_emigrate_removes_single_adults_and_logs() {
+        let mut world = SimulationWorld::new();
+        world.immigrate(3, 8, 20, 40);
+        world.seed_population_on_tile(4, 4);
+
+        let left = world.emigrate(3, 5);
+        assert_eq!(left, 5);
+        assert_eq!(world.tile_population(3), 3);
+        assert_eq!(world.tile_population(4), 4);
+        assert_eq!(world.event_log.iter_by_type(EventType::Emigration).count(), 5);
+
+        // Asking for more than are available only removes what exists
+        assert_eq!(world.emigrate(3, 10), 3);
+        assert_eq!(world.tile_population(3), 0);
+    }
+
+    #[test]
+    fn test_remap_tiles_merges_tile() {
+        let mut world = SimulationWorld::with_seed(5);
+        world.seed_population_on_tile(10, 3);
+        world.seed_population_on_tile(7, 5);
+        world.seed_population_on_tile(4, 9);
+        world.set_tile_adjacency(HashMap::from([
+            (3, vec![5, 9]),
+            (5, vec![3, 12]),
+            (9, vec![3]),
+            (12, vec![5]),
+        ]));
+
+        let moved = world.remap_tiles(&HashMap::from([(5, 3)]));
+        assert_eq!(moved, 7);
+        assert_eq!(world.tile_population(5), 0);
+        assert_eq!(world.tile_population(3), 17);
+        assert_eq!(world.tile_population(9), 4);
+        assert_eq!(world.neighbors_of(3), &[9, 12]);
+        assert_eq!(world.neighbors_of(12), &[3]);
+        assert!(world.neighbors_of(5).is_empty());
+    }
+
+    #[test]
+    fn test_women_by_parity() {
+        let mut world = SimulationWorld::new();
+        let cal = world.calendar.clone();
+        let with_children = |children_born: u8| Fertility { children_born, ..Fertility::default() };
+        world.world.spawn((Sex::Female, BirthDate::from_age(25, &cal), with_children(0)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(30, &cal), with_children(2)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(31, &cal), with_children(2)));
+        // Past fertile age, still counted
+        world.world.spawn((Sex::Female, BirthDate::from_age(70, &cal), with_children(5)));
+        // No Fertility component
+        world.world.spawn((Sex::Female, BirthDate::from_age(8, &cal)));
+        world.world.spawn((Sex::Male, BirthDate::from_age(30, &cal)));
+
+        let parity = world.women_by_parity();
+        assert_eq!(parity, HashMap::from([(0, 2), (2, 2), (5, 1)]));
+    }
+
+    #[test]
+    fn test_completed_family_sizes() {
+        let mut world = SimulationWorld::new();
+        assert_eq!(world.mean_completed_family_size(), None);
+
+        let cal = world.calendar.clone();
+        let with_children = |children_born: u8| Fertility { children_born, ..Fertility::default() };
+        let past = BirthDate::MAX_FERTILE_AGE_FEMALE + 1;
+        world.world.spawn((Sex::Female, BirthDate::from_age(past, &cal), with_children(3)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(50, &cal), with_children(3)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(72, &cal), with_children(6)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(60, &cal)));
+        // Still fertile, or not a woman: not counted
+        world.world.spawn((Sex::Female, BirthDate::from_age(BirthDate::MAX_FERTILE_AGE_FEMALE, &cal), with_children(4)));
+        world.world.spawn((Sex::Female, BirthDate::from_age(20, &cal), with_children(1)));
+        world.world.spawn((Sex::Male, BirthDate::from_age(60, &cal)));
+        // Past the window but still to deliver: her family isn't complete yet
+        let expecting = world.world.spawn((Sex::Female, BirthDate::from_age(past, &cal), with_children(2), Pregnant::new(&cal)));
+
+        assert_eq!(world.completed_family_sizes(), HashMap::from([(0, 1), (3, 2), (6, 1)]));
+        assert_eq!(world.mean_completed_family_size(), Some(3.0));
+
+        world.world.remove_one::<Pregnant>(expecting).unwrap();
+        world.world.get::<&mut Fertility>(expecting).unwrap().record_birth(&cal);
+        assert_eq!(world.completed_family_sizes(), HashMap::from([(0, 1), (3, 3), (6, 1)]));
+    }
+
+    #[test]
+    fn test_cached_count_tracks_births_deaths_and_imports() {
+        let mut world = SimulationWorld::with_seed(17);
+        world.seed_population_on_tile(400, 1);
+        assert_eq!(world.entity_count(), 400);
+
+        let (mut births, mut deaths) = (0, 0);
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 3 {
+            let result = world.tick();
+            births += result.births;
+            deaths += result.deaths;
+            assert_eq!(world.entity_count(), world.scan_living_count());
+            assert_eq!(result.population as usize, world.entity_count());
+        }
+        assert!(births > 0 && deaths > 0);
+        assert_eq!(world.entity_count(), (400 + births - deaths) as usize);
+
+        world.immigrate(2, 10, 20, 30);
+        world.emigrate(1, 5);
+        assert_eq!(world.entity_count(), world.scan_living_count());
+
+        // Edits made directly on the ECS are picked up without a manual rebuild
+        let gone = world.world.query::<&Person>().iter().next().map(|(entity, _)| entity).unwrap();
+        world.world.despawn(gone).unwrap();
+        assert_eq!(world.entity_count(), world.scan_living_count());
+        let result = world.tick();
+        assert_eq!(result.population as usize, world.scan_living_count());
+
+        let mut imported = SimulationWorld::new();
+        imported.seed_population_on_tile(3, 9);
+        imported.import_world(&world.export_world()).unwrap();
+        assert_eq!(imported.entity_count(), world.entity_count());
+        assert_eq!(imported.entity_count(), imported.scan_living_count());
+    }
+
+    #[test]
+    fn test_compact_after_churn() {
+        let mut world = SimulationWorld::with_seed(23);
+        world.seed_population_on_tile(300, 1);
+        world.seed_relationships(0.6, false).unwrap();
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 2 {
+            world.tick();
+        }
+        assert_eq!(world.compact(), CompactResult::default());
+
+        // Churn behind the world's back: despawn some people, strip others to shells
+        let people: Vec<hecs::Entity> = world.world.query::<&Person>().iter().map(|(e, _)| e).take(40).collect();
+        for entity in &people[..30] {
+            world.world.despawn(*entity).unwrap();
+        }
+        for entity in &people[30..] {
+            world.world.remove_one::<BirthDate>(*entity).unwrap();
+        }
+        world.world.spawn((TileId(4),));
+
+        let result = world.compact();
+        assert_eq!(result.despawned, 11);
+        assert_eq!(result.stale_index_entries, 40);
+        assert_eq!(world.entity_count(), world.scan_living_count());
+        assert_eq!(world.world.len() as usize, world.entity_count());
+        assert!(world.world.query::<&Partner>().iter().all(|(_, p)| world.world.contains(p.0)));
+
+        let fresh: HashMap<u64, hecs::Entity> = world.world.query::<&Person>().iter().map(|(e, p)| (p.id.0, e)).collect();
+        assert_eq!(world.person_index, fresh);
+        for (id, entity) in fresh {
+            assert_eq!(world.find_person(id), Some(entity));
+        }
+    }
+
+    #[test]
+    fn test_fertility_status_just_under_birth_interval() {
+        let mut world = SimulationWorld::with_seed(5);
+        let cal = world.calendar.clone();
+        let w = &mut world.world;
+        let husband = w.spawn((Person::unnamed(1), Sex::Male, BirthDate::from_age(25, &cal)));
+        let mut fertility = Fertility::default();
+        fertility.record_birth(&cal);
+        let wife = w.spawn((Person::unnamed(2), Sex::Female, BirthDate::from_age(22, &cal), fertility, Partner(husband, PersonId(1))));
+        w.insert_one(husband, Partner(wife, PersonId(2))).unwrap();
+        world.rebuild_person_index();
+
+        // One month short of the 18-month interval
+        for _ in 0..Calendar::DAYS_PER_MONTH as u32 * 17 {
+            world.calendar.advance();
+        }
+        let status = world.fertility_status(2).unwrap();
+        assert!(status.has_partner && status.in_age_window && !status.is_pregnant);
+        assert!(!status.interval_ok);
+        assert_eq!(status.effective_rate, 0.0);
+
+        for _ in 0..Calendar::DAYS_PER_MONTH {
+            world.calendar.advance();
+        }
+        let status = world.fertility_status(2).unwrap();
+        assert!(status.interval_ok);
+        assert!(status.effective_rate > 0.0);
+
+        assert_eq!(world.fertility_status(1), None);
+        assert_eq!(world.fertility_status(99), None);
+    }
+
+    #[test]
+    fn test_fertile_partnered_women() {
+        let mut world = SimulationWorld::new();
+        let cal = world.calendar.clone();
+        let mut recent_birth = Fertility::default();
+        recent_birth.record_birth(&cal);
+        let wives = [
+            (12, Fertility::default(), false), // too young
+            (40, Fertility::default(), false), // too old
+            (20, Fertility::default(), false),
+            (30, Fertility::default(), false),
+            (25, recent_birth, false),          // birth interval not yet passed
+            (25, Fertility::default(), true),   // already pregnant
+        ];
+        for (i, (age, fertility, pregnant)) in wives.into_iter().enumerate() {
+            let id = i as u64 * 2;
+            let husband = world.world.spawn((Person::unnamed(id + 1), Sex::Male, BirthDate::from_age(age + 2, &cal)));
+            let wife = world.world.spawn((Person::unnamed(id), Sex::Female, BirthDate::from_age(age, &cal), fertility, Partner(husband, PersonId(id + 1))));
+            world.world.insert_one(husband, Partner(wife, PersonId(id))).unwrap();
+            if pregnant {
+                world.world.insert_one(wife, Pregnant::new(&cal)).unwrap();
+            }
+        }
+        // A single woman in the window doesn't count
+        world.world.spawn((Person::unnamed(50), Sex::Female, BirthDate::from_age(22, &cal), Fertility::default()));
+        world.rebuild_person_index();
+
+        assert_eq!(world.fertile_partnered_women(), 2);
+        let demographics = world.demographics();
+        assert_eq!(demographics.fertile_women, 2);
+        assert_eq!(demographics.partnered, 12);
+        assert_eq!(world.demographics_with_brackets(&[30]).fertile_women, 2);
+    }
+
+    #[test]
+    fn test_population_cap_halts_growth() {
+        let mut world = SimulationWorld::with_seed(31);
+        world.seed_population_on_tile(300, 1);
+        let cap = world.entity_count() as u32 + 5;
+        world.max_population = Some(cap);
+
+        let mut births = 0;
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 10 {
+            births += world.tick().births;
+            assert!(world.entity_count() as u32 <= cap);
+        }
+        assert!(births >= 5);
+        let reached = world.event_log.iter_by_type(EventType::CapacityReached).count();
+        assert!(reached >= 1);
+
+        // Immigration is turned away at the cap too
+        let room = cap as usize - world.entity_count() - world.world.query::<&Pregnant>().iter().count();
+        assert_eq!(world.immigrate(1, 50, 20, 30).len(), room);
+
+        // The cap survives a save and load, and a world saved at it isn't reported reaching it again
+        let dir = std::env::temp_dir().join(format!("gridworld_cap_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+        world.save_to_file("{}", 1, &path).unwrap();
+        let mut loaded = SimulationWorld::new();
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(loaded.max_population, Some(cap));
+        assert_eq!(loaded.remaining_capacity(), Some(0));
+        let logged = loaded.event_log.iter_by_type(EventType::CapacityReached).count();
+        loaded.tick();
+        assert_eq!(loaded.event_log.iter_by_type(EventType::CapacityReached).count(), logged);
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 {
+            loaded.tick();
+            assert!(loaded.entity_count() as u32 <= cap);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_minor_orphaned_when_parents_die() {
+        fn spawn(world: &mut World, id: u64, age: u16, tile: u16, cal: &Calendar) -> hecs::Entity {
+            let person = Person::unnamed(id);
+            world.spawn((person, BirthDate::from_age(age, cal), TileId(tile)))
+        }
+
+        let mut world = SimulationWorld::with_seed(2);
+        let cal = world.calendar.clone();
+        let w = &mut world.world;
+        let mother = spawn(w, 1, 35, 4, &cal);
+        spawn(w, 2, 8, 4, &cal); // Founder, no Mother
+        let child = spawn(w, 3, 8, 4, &cal);
+        w.insert_one(child, Mother(mother, PersonId(1))).unwrap();
+        let grown = spawn(w, 4, 20, 4, &cal);
+        w.insert_one(grown, Mother(mother, PersonId(1))).unwrap();
+        let father = spawn(w, 5, 40, 6, &cal);
+        let fathered = spawn(w, 6, 5, 6, &cal);
+        w.insert(fathered, (Mother(mother, PersonId(1)), Father(father, PersonId(5)))).unwrap();
+        world.rebuild_person_index();
+        assert!(world.orphans().is_empty());
+
+        world.world.despawn(mother).unwrap();
+        world.rebuild_person_index();
+        // Founders, adults, and children with a living father are not orphans
+        assert_eq!(world.orphans(), vec![3]);
+
+        world.world.despawn(father).unwrap();
+        world.rebuild_person_index();
+        assert_eq!(world.orphans(), vec![3, 6]);
+        assert_eq!(world.orphan_count_by_tile(), HashMap::from([(4, 1), (6, 1)]));
+    }
+
+    #[test]
+    fn test_year_stats_match_event_log() {
+        let mut world = SimulationWorld::with_seed(12);
+        world.seed_population_on_tile(400, 1);
+        let first_year = world.calendar.year;
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 3 {
+            world.tick();
+        }
+
+        for year in first_year..=world.calendar.year {
+            let stats = world.stats_for_year(year).unwrap();
+            let logged = |event_type| world.event_log.count_by_type(event_type, year, year) as u32;
+            assert_eq!(stats.births, logged(EventType::Birth));
+            assert_eq!(stats.deaths, logged(EventType::Death));
+            assert_eq!(stats.marriages, logged(EventType::Marriage));
+            assert_eq!(world.calculate_vital_statistics(year, year).total_births, stats.births);
+        }
+        assert!(world.stats_for_year(first_year).unwrap().marriages > 0);
+
+        // Counts survive the events ageing out, and round-trip through an export
+        let before = world.calculate_vital_statistics(first_year, first_year);
+        world.event_log.prune_before(world.calendar.year);
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.export_world()).unwrap();
+        let after = restored.calculate_vital_statistics(first_year, first_year);
+        assert_eq!((after.total_births, after.total_deaths), (before.total_births, before.total_deaths));
+        assert_eq!(restored.stats_for_year(first_year), world.stats_for_year(first_year));
+    }
+
+    #[test]
+    fn test_days_counted_from_start_year() {
+        let mut world = SimulationWorld::with_start(3, Calendar::new(1000, 1, 1));
+        assert_eq!(world.calendar.year, 1000);
+        assert_eq!(world.days_since_start(), 0);
+        world.seed_population_on_tile(50, 1);
+        world.mark_genesis();
+
+        for _ in 0..10 {
+            world.tick();
+        }
+        world.tick_days(Calendar::DAYS_PER_YEAR as u32);
+        assert_eq!(world.days_since_start(), 10 + Calendar::DAYS_PER_YEAR as u64);
+        assert_eq!(world.calendar.year, 1001);
+        assert_eq!(world.date_after_start(world.days_since_start()), world.calendar);
+        assert_eq!(world.date_after_start(0), Calendar::new(1000, 1, 1));
+
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.export_world()).unwrap();
+        assert_eq!(restored.start_date, Calendar::new(1000, 1, 1));
+        assert_eq!(restored.days_since_start(), world.days_since_start());
+
+        assert_eq!(SimulationWorld::new().days_since_start(), 0);
+    }
+
+    #[test]
+    fn test_statistics_on_empty_world() {
+        let mut world = SimulationWorld::with_seed(1);
+        let year = world.current_year();
+        let finite = |values: &[f64]| values.iter().all(|v| v.is_finite());
+
+        let demographics = world.demographics();
+        assert_eq!((demographics.population, demographics.fertile_women), (0, 0));
+        assert_eq!(demographics.average_age, 0.0);
+        assert_eq!(demographics.age_brackets, [0; 7]);
+        assert_eq!(world.demographics_with_brackets(&[10, 20]).age_brackets, vec![0, 0, 0]);
+
+        for stats in [
+            world.calculate_vital_statistics(year, year),
+            world.calculate_vital_statistics(0, u16::MAX),
+            world.calculate_vital_statistics(year, 0),
+            world.calculate_current_year_statistics(),
+            world.calculate_recent_statistics(0),
+            world.calculate_recent_statistics(u16::MAX),
+        ] {
+            assert!(finite(&[stats.birth_rate, stats.death_rate, stats.marriage_rate, stats.natural_increase_rate, stats.period_years]));
+            assert_eq!((stats.total_births, stats.population), (0, 0));
+        }
+        for window in [0, 1, 1000] {
+            let growth = world.growth_rate(window);
+            assert_eq!((growth.natural_increase, growth.annual_growth_percent), (0, 0.0));
+        }
+
+        assert_eq!(world.entity_count(), 0);
+        assert_eq!(world.tile_population(1), 0);
+        assert!(world.population_by_tile().is_empty());
+        assert!(world.sex_ratio_by_tile().is_empty());
+        assert_eq!(world.tile_population_distribution(&[0, 10]), vec![0, 0, 0]);
+        assert!(world.largest_tiles(5).is_empty());
+        assert!(world.orphans().is_empty() && world.orphan_count_by_tile().is_empty());
+        assert_eq!(world.tile_pressure(1), 1.0);
+        assert!(world.all_tile_pressures().is_empty());
+        assert!(world.workforce_by_tile().is_empty());
+        assert_eq!(world.eligible_singles(), (vec![], vec![]));
+        assert!(world.women_by_parity().is_empty());
+        assert_eq!(world.max_generation(), 0);
+        assert_eq!(world.average_marriage_duration(), None);
+        assert_eq!(world.fertile_partnered_women(), 0);
+        assert!(world.population_series(0, u16::MAX).is_empty());
+        assert_eq!(world.memory_report().entities, 0);
+
+        // Ticking an empty world is a no-op apart from the date
+        let preview = world.simulate_tick_preview().unwrap();
+        assert_eq!((preview.births, preview.deaths, preview.population), (0, 0, 0));
+        let result = world.tick();
+        assert_eq!((result.births, result.deaths, result.population), (0, 0, 0));
+        assert_eq!(world.population_series(0, u16::MAX), vec![(year, 0)]);
+        assert!(finite(&[world.calculate_current_year_statistics().birth_rate]));
+    }
+
+    #[test]
+    fn test_tile_demographics() {
+        let mut world = SimulationWorld::with_seed(6);
+        world.immigrate(1, 40, 20, 40);
+        world.seed_population_on_tile(25, 2);
+        let cal = world.calendar.clone();
+        // Tile 3: two young women, one pregnant, and a 70-year-old man
+        for (id, sex, age, pregnant) in [(900, Sex::Female, 18, true), (901, Sex::Female, 20, false), (902, Sex::Male, 70, false)] {
+            let person = Person::unnamed(id);
+            let entity = world.world.spawn((person, sex, BirthDate::from_age(age, &cal), TileId(3)));
+            if pregnant {
+                world.world.insert_one(entity, Pregnant::new(&cal)).unwrap();
+            }
+        }
+        world.rebuild_person_index();
+
+        let tile = world.tile_demographics(3);
+        assert_eq!((tile.population, tile.males, tile.females), (3, 1, 2));
+        assert_eq!((tile.single, tile.partnered, tile.pregnant), (3, 0, 1));
+        assert_eq!(tile.age_brackets, [0, 0, 2, 0, 0, 1, 0]);
+        assert!((tile.average_age - 36.0).abs() < 1e-9);
+
+        assert_eq!(world.tile_demographics(1).population, 40);
+        assert_eq!(world.tile_demographics(1).age_brackets[0..2], [0, 0]);
+        assert_eq!(world.tile_demographics(9).population, 0);
+        let total: u32 = (1..=3).map(|t| world.tile_demographics(t).population).sum();
+        assert_eq!(total, world.demographics().population);
+    }
+
+    #[test]
+    fn test_seed_from_records() {
+        let mut world = SimulationWorld::with_seed(2);
+        let record = |first_name: &str, sex: Sex, age: u16, partner: Option<usize>| SeedPerson {
+            first_name: first_name.to_string(),
+            last_name: "Stone".to_string(),
+            sex,
+            age,
+            tile_id: 4,
+            partner,
+        };
+        let ids = world
+            .seed_from_records(&[record("Ada", Sex::Female, 30, Some(2)), record("Tom", Sex::Male, 8, None), record("Ben", Sex::Male, 33, None)])
+            .unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(world.entity_count(), 3);
+
+        let (ada, ben) = (world.find_person(ids[0]).unwrap(), world.find_person(ids[2]).unwrap());
+        let partner_of = |entity| world.world.get::<&Partner>(entity).map(|p| (p.0, p.1 .0)).unwrap();
+        assert_eq!(partner_of(ada), (ben, ids[2]));
+        assert_eq!(partner_of(ben), (ada, ids[0]));
+        assert_eq!(*world.world.get::<&Household>(ada).unwrap(), *world.world.get::<&Household>(ben).unwrap());
+        assert!(world.world.get::<&Fertility>(ada).is_ok());
+        assert!(world.world.get::<&Partner>(world.find_person(ids[1]).unwrap()).is_err());
+        assert_eq!(world.world.get::<&Person>(ada).unwrap().first_name, "Ada");
+        assert_eq!(world.world.get::<&BirthDate>(ben).unwrap().age_years(&world.calendar), 33);
+
+        // Invalid batches spawn no one
+        assert!(world.seed_from_records(&[record("Al", Sex::Male, 30, Some(1)), record("Bo", Sex::Male, 30, None)]).is_err());
+        assert!(world.seed_from_records(&[record("Cy", Sex::Female, 30, Some(5))]).is_err());
+        let mut far = record("Di", Sex::Male, 30, None);
+        far.tile_id = 9;
+        assert!(world.seed_from_records(&[record("Eve", Sex::Female, 30, Some(1)), far]).is_err());
+        let triangle = [record("Fay", Sex::Female, 30, Some(1)), record("Gus", Sex::Male, 30, None), record("Hal", Sex::Male, 30, Some(0))];
+        assert!(world.seed_from_records(&triangle).is_err());
+        // Either partner under the marriage age, named from either side
+        let young = world.marriage_config.min_age_female - 1;
+        assert!(world.seed_from_records(&[record("Ivy", Sex::Female, young, Some(1)), record("Jon", Sex::Male, 30, None)]).is_err());
+        assert!(world.seed_from_records(&[record("Kit", Sex::Male, 30, Some(1)), record("Liv", Sex::Female, young, None)]).is_err());
+        assert_eq!(world.entity_count(), 3);
+
+        // A batch that doesn't fit under the cap is refused whole
+        world.max_population = Some(4);
+        let couple = [record("Max", Sex::Male, 30, Some(1)), record("Nia", Sex::Female, 30, None)];
+        assert!(world.seed_from_records(&couple).is_err());
+        assert_eq!(world.entity_count(), 3);
+        world.max_population = Some(5);
+        assert_eq!(world.seed_from_records(&couple).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_people_page_is_stable() {
+        let mut world = SimulationWorld::with_seed(27);
+        for tile in [5, 2, 9] {
+            world.seed_population_on_tile(40, tile);
+        }
+        for _ in 0..30 {
+            world.tick();
+        }
+        let total = world.entity_count();
+
+        for sort in [PersonSort::PersonId, PersonSort::Age, PersonSort::Tile, PersonSort::Name] {
+            let (page, count) = world.people_page(10, 25, sort);
+            assert_eq!(count, total);
+            assert_eq!(page.len(), 25);
+            assert_eq!(world.people_page(10, 25, sort), (page.clone(), count));
+
+            // Consecutive pages tile the full ordering with no gaps or repeats
+            let (all, _) = world.people_page(0, usize::MAX, sort);
+            assert_eq!(all.len(), total);
+            assert_eq!(&all[10..35], &page[..]);
+            let mut unique = all.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(unique.len(), total);
+        }
+
+        let (by_id, _) = world.people_page(0, usize::MAX, PersonSort::PersonId);
+        assert!(by_id.windows(2).all(|w| w[0] < w[1]));
+        let tiles: Vec<u16> = world.people_page(0, usize::MAX, PersonSort::Tile).0.iter()
+            .map(|id| world.world.get::<&TileId>(world.find_person(*id).unwrap()).unwrap().0)
+            .collect();
+        assert!(tiles.windows(2).all(|w| w[0] <= w[1]));
+        let ages: Vec<u32> = world.people_page(0, usize::MAX, PersonSort::Age).0.iter()
+            .map(|id| world.world.get::<&BirthDate>(world.find_person(*id).unwrap()).unwrap().age_days(&world.calendar))
+            .collect();
+        assert!(ages.windows(2).all(|w| w[0] <= w[1]));
+        assert!(world.people_page(total, 10, PersonSort::Name).0.is_empty());
+    }
+
+    #[test]
+    fn test_population_series() {
+        let mut world = SimulationWorld::with_seed(14);
+        world.seed_population_on_tile(300, 1);
+        let first_year = world.calendar.year;
+
+        let mut observed = Vec::new();
+        for _ in 0..5 {
+            for _ in 0..Calendar::DAYS_PER_YEAR {
+                world.tick();
+                if world.calendar.month == Calendar::MONTHS_PER_YEAR && world.calendar.day == Calendar::DAYS_PER_MONTH {
+                    observed.push((world.calendar.year, world.entity_count() as u32));
+                }
+            }
+        }
+        let last_year = world.calendar.year;
+        let series = world.population_series(first_year, last_year);
+        // The first year has run to its end; the current one reports its latest tick
+        assert_eq!(&series[..series.len() - 1], &observed[..]);
+        assert_eq!(series.last(), Some(&(last_year, world.entity_count() as u32)));
+        assert_eq!(world.population_series(first_year + 1, first_year + 2), observed[1..3].to_vec());
+
+        // Years without data are skipped, not zero-filled
+        world.year_stats.get_mut(&(first_year + 1)).unwrap().population = None;
+        let years: Vec<u16> = world.population_series(0, u16::MAX).into_iter().map(|(year, _)| year).collect();
+        assert!(!years.contains(&(first_year + 1)));
+        assert!(!years.contains(&(first_year - 1)));
+        assert_eq!(years.len(), series.len() - 1);
+
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.export_world()).unwrap();
+        assert_eq!(restored.population_series(0, u16::MAX), world.population_series(0, u16::MAX));
+    }
+
+    #[test]
+    fn test_tick_days_close_to_daily_ticks() {
+        let run = |seed: u64, stepped: bool| {
+            let mut world = SimulationWorld::with_seed(seed);
+            world.seed_population_on_tile(1000, 1);
+            let start = world.calendar.clone();
+            let pregnancies = if stepped {
+                world.tick_days(30).pregnancies
+            } else {
+                (0..30).map(|_| world.tick().pregnancies).sum()
+            };
+            assert_eq!(world.calendar.to_absolute_days(), start.to_absolute_days() + 30);
+            (1000 - world.entity_count() as u32, pregnancies)
+        };
+
+        let (mut daily_deaths, mut daily_pregnancies, mut step_deaths, mut step_pregnancies) = (0, 0, 0, 0);
+        for seed in 0..60 {
+            let (deaths, pregnancies) = run(seed, false);
+            daily_deaths += deaths;
+            daily_pregnancies += pregnancies;
+            let (deaths, pregnancies) = run(seed, true);
+            step_deaths += deaths;
+            step_pregnancies += pregnancies;
+        }
+
+        let close = |a: u32, b: u32| (a as f64 - b as f64).abs() <= 0.2 * a.max(b) as f64;
+        assert!(close(daily_deaths, step_deaths), "deaths: daily {} vs stepped {}", daily_deaths, step_deaths);
+        assert!(
+            close(daily_pregnancies, step_pregnancies),
+            "pregnancies: daily {} vs stepped {}",
+            daily_pregnancies,
+            step_pregnancies
+        );
+    }
+
+    #[test]
+    fn test_links_survive_despawn_and_reload() {
+        let mut world = SimulationWorld::with_seed(6);
+        let ids = world.immigrate(1, 6, 25, 25);
+        let (mother, father) = (world.find_person(ids[0]).unwrap(), world.find_person(ids[1]).unwrap());
+        world.world.insert_one(mother, Partner(father, PersonId(ids[1]))).unwrap();
+        world.world.insert_one(father, Partner(mother, PersonId(ids[0]))).unwrap();
+        let child = Person::unnamed(100);
+        let birth = BirthDate::from_age(3, &world.calendar);
+        world.world.spawn((child, Sex::Male, birth, TileId(1), Mother(mother, PersonId(ids[0])), Father(father, PersonId(ids[1]))));
+
+        // Free some entity slots and refill them, then lose the mother
+        for id in &ids[2..] {
+            let entity = world.find_person(*id).unwrap();
+            world.world.despawn(entity).unwrap();
+        }
+        world.world.despawn(mother).unwrap();
+        world.immigrate(1, 5, 20, 30);
+        world.rebuild_person_index();
+        systems::dissolution_system(&mut world.world);
+
+        let child = world.find_person(100).unwrap();
+        let link = *world.world.get::<&Mother>(child).unwrap();
+        assert_eq!(link.1, PersonId(ids[0]));
+        assert_eq!(world.find_person(link.1.0), None);
+        assert!(world.world.get::<&Partner>(father).is_err());
+
+        // After a reload the dead mother is still known by id, the father by id and entity
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.export_world()).unwrap();
+        let child = restored.find_person(100).unwrap();
+        assert_eq!(restored.world.get::<&Mother>(child).unwrap().1, PersonId(ids[0]));
+        let father_link = *restored.world.get::<&Father>(child).unwrap();
+        assert_eq!(restored.find_person(father_link.1.0), Some(father_link.0));
+        assert_eq!(restored.orphans(), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_tick_until_year_summarizes_each_year() {
+        let mut world = SimulationWorld::with_seed(9);
+        world.seed_population_on_tile(200, 1);
+        assert_eq!(world.calendar.year, 4000);
+        let cancel = AtomicBool::new(false);
+
+        let summaries = world.tick_until_year(4005, &cancel).unwrap();
+        assert_eq!((world.calendar.year, world.calendar.month, world.calendar.day), (4005, 1, 1));
+        assert_eq!(summaries.iter().map(|s| s.year).collect::<Vec<_>>(), vec![4000, 4001, 4002, 4003, 4004]);
+        assert_eq!(summaries.last().unwrap().population, world.entity_count() as u32);
+        assert!(summaries.iter().map(|s| s.births).sum::<u32>() > 0);
+
+        assert!(world.tick_until_year(4005, &cancel).unwrap().is_empty());
+        assert!(world.tick_until_year(4004, &cancel).is_err());
+    }
+
+    #[test]
+    fn test_growth_rate_sign() {
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(1000, 1);
+        assert_eq!(world.growth_rate(96).window_days, 0);
+
+        for _ in 0..96 {
+            world.record_vitals(3, 1);
+        }
+        let growing = world.growth_rate(96);
+        assert_eq!(growing.natural_increase, 192);
+        assert_eq!(growing.window_days, 96);
+        assert!(growing.annual_growth_percent > 0.0);
+
+        world.clear_vitals();
+        for _ in 0..200 {
+            world.record_vitals(1, 4);
+        }
+        let declining = world.growth_rate(96);
+        assert_eq!(declining.natural_increase, -288);
+        assert!(declining.annual_growth_percent < 0.0);
+        // Longer windows than recorded are clamped
+        assert_eq!(world.growth_rate(10_000).window_days, 200);
+    }
+
+    #[test]
+    fn test_matchmaking_sees_only_survivors() {
+        let mut world = SimulationWorld::with_seed(21);
+        let cal = world.calendar.clone();
+        let spawn = |world: &mut SimulationWorld, id: u64, sex: Sex| {
+            let person = Person::unnamed(id);
+            world.world.spawn((person, BirthDate::from_age(25, &cal), sex, TileId(1)))
+        };
+
+        let late_husband = spawn(&mut world, 1, Sex::Male);
+        let widow = spawn(&mut world, 2, Sex::Female);
+        world.world.insert_one(late_husband, Partner(widow, PersonId(2))).unwrap();
+        world.world.insert_one(widow, Partner(late_husband, PersonId(1))).unwrap();
+        let dead_suitor = spawn(&mut world, 3, Sex::Male);
+        let suitor = spawn(&mut world, 4, Sex::Male);
+
+        // Both die before the tick's matchmaking runs
+        world.world.despawn(late_husband).unwrap();
+        world.world.despawn(dead_suitor).unwrap();
+
+        let result = world.tick();
+        assert_eq!(result.dissolutions, 1);
+        assert_eq!(result.marriages, 1);
+        // Widowed and remarried to the living suitor in the same tick
+        assert_eq!(world.world.get::<&Partner>(widow).unwrap().0, suitor);
+        assert_eq!(world.world.get::<&Partner>(suitor).unwrap().0, widow);
+    }
+
+    #[test]
+    fn test_children_join_mothers_household() {
+        let mut world = SimulationWorld::with_seed(13);
+        world.immigrate(1, 60, 20, 24);
+        for _ in 0..400 {
+            world.tick();
+        }
+
+        let mut checked = 0;
+        for (_, (mother, household)) in world.world.query::<(&Mother, &Household)>().iter() {
+            if let Ok(mother_household) = world.world.get::<&Household>(mother.0) {
+                if world.world.get::<&Partner>(mother.0).is_ok() {
+                    assert_eq!(*mother_household, *household);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+        // Every couple shares one household
+        for (_, (partner, household)) in world.world.query::<(&Partner, &Household)>().iter() {
+            assert_eq!(*world.world.get::<&Household>(partner.0).unwrap(), *household);
+        }
+    }
+
+    #[test]
+    fn test_descendants_across_three_generations() {
+        fn spawn(world: &mut World, id: u64, sex: Sex, age: u16, mother: Option<hecs::Entity>, father: Option<hecs::Entity>) -> hecs::Entity {
+            let person = Person::unnamed(id);
+            let entity = world.spawn((person, sex, BirthDate::from_age(age, &Calendar::default()), TileId(1)));
+            if let Some(mother) = mother {
+                let id = world.get::<&Person>(mother).unwrap().id;
+                world.insert_one(entity, Mother(mother, id)).unwrap();
+            }
+            if let Some(father) = father {
+                let id = world.get::<&Person>(father).unwrap().id;
+                world.insert_one(entity, Father(father, id)).unwrap();
+            }
+            entity
+        }
+
+        let mut world = SimulationWorld::with_seed(5);
+        let w = &mut world.world;
+        let grandma = spawn(w, 1, Sex::Female, 70, None, None);
+        let grandpa = spawn(w, 2, Sex::Male, 72, None, None);
+        let son = spawn(w, 3, Sex::Male, 45, Some(grandma), Some(grandpa));
+        let daughter = spawn(w, 4, Sex::Female, 42, Some(grandma), Some(grandpa));
+        let in_law = spawn(w, 5, Sex::Female, 44, None, None);
+        spawn(w, 6, Sex::Female, 20, Some(in_law), Some(son));
+        spawn(w, 7, Sex::Male, 15, Some(daughter), None);
+        spawn(w, 8, Sex::Male, 12, Some(daughter), None);
+        world.rebuild_person_index();
+
+        assert_eq!(world.descendant_count(1), 5);
+        assert_eq!(world.living_lineage(2), vec![3, 4, 6, 7, 8]);
+        assert_eq!(world.living_lineage(4), vec![7, 8]);
+        assert_eq!(world.descendant_count(5), 1);
+        assert_eq!(world.descendant_count(8), 0);
+        assert_eq!(world.descendant_count(99), 0);
+
+        // The line through a despawned parent is lost
+        world.world.despawn(daughter).unwrap();
+        world.rebuild_person_index();
+        assert_eq!(world.descendant_count(1), 2);
+        assert_eq!(world.find_person(4), None);
+        assert_eq!(world.find_person(3), Some(son));
+    }
+
+    #[test]
+    fn test_births_record_father() {
+        let mut world = SimulationWorld::with_seed(13);
+        world.immigrate(1, 60, 20, 24);
+        for _ in 0..400 {
+            world.tick();
+        }
+
+        let mut checked = 0;
+        for (entity, (person, father)) in world.world.query::<(&Person, &Father)>().iter() {
+            assert_eq!(world.find_person(person.id.0), Some(entity));
+            if let Some(mother) = world.world.get::<&Mother>(entity).ok().map(|m| m.0) {
+                if let Ok(partner) = world.world.get::<&Partner>(mother) {
+                    assert_eq!(partner.0, father.0);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_tick_many_cancelled_from_another_thread() {
+        use std::sync::atomic::AtomicU32;
+        use std::sync::Arc;
+
+        let mut world = SimulationWorld::with_seed(2);
+        world.seed_population_on_tile(50, 1);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let progress = Arc::new(AtomicU32::new(0));
+
+        let canceller = {
+            let cancel = Arc::clone(&cancel);
+            let progress = Arc::clone(&progress);
+            std::thread::spawn(move || {
+                while progress.load(Ordering::Relaxed) < 10 {
+                    std::thread::yield_now();
+                }
+                cancel.store(true, Ordering::Relaxed);
+            })
+        };
+
+        let requested = 10_000_000;
+        let ran = world.tick_many(requested, &cancel, |_| {
+            progress.fetch_add(1, Ordering::Relaxed);
+        });
+        canceller.join().unwrap();
+
+        assert!(ran >= 10 && ran < requested);
+        assert_eq!(ran, progress.load(Ordering::Relaxed));
+
+        // Still set, so the next run does nothing until the caller resets it
+        assert_eq!(world.tick_many(5, &cancel, |_| {}), 0);
+        cancel.store(false, Ordering::Relaxed);
+        assert_eq!(world.tick_many(5, &cancel, |_| {}), 5);
+    }
+
+    #[test]
+    fn test_tick_progress_every_k_ticks() {
+        let mut world = SimulationWorld::with_seed(3);
+        world.seed_population_on_tile(20, 1);
+        let cancel = AtomicBool::new(false);
+        let mut reports = Vec::new();
+        let ran = world.tick_many_with_progress(1000, &cancel, 100, |_| {}, |p| reports.push(p));
+
+        assert_eq!(ran, 1000);
+        assert_eq!(reports.len(), 10);
+        assert_eq!(reports.iter().map(|p| p.ticks).collect::<Vec<_>>(), (1..=10).map(|k| k * 100).collect::<Vec<_>>());
+        assert!(reports.iter().all(|p| p.total == 1000));
+        assert_eq!(reports.last().unwrap().calendar, world.calendar);
+
+        // Cancelling part way stops the reports with the ticks
+        let mut reports = 0;
+        let mut ticks = 0;
+        world.tick_many_with_progress(1000, &cancel, 100, |_| {
+            ticks += 1;
+            if ticks == 250 {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        }, |_| reports += 1);
+        assert_eq!((ticks, reports), (250, 2));
+
+        cancel.store(false, Ordering::Relaxed);
+        let mut reports = 0;
+        world.tick_many_with_progress(50, &cancel, 0, |_| {}, |_| reports += 1);
+        assert_eq!(reports, 0);
+    }
+
+    #[test]
+    fn test_same_seed_same_run() {
+        let run = |seed: u64| {
+            let mut world = SimulationWorld::with_seed(seed);
+            for tile in [3u16, 7, 11] {
+                world.seed_population_on_tile_range(20, 40, tile);
+            }
+            for _ in 0..100 {
+                world.tick();
+            }
+            (world.demographics(), world.population_by_tile())
+        };
+
+        assert_eq!(run(99), run(99));
+        assert_ne!(run(99).0, run(100).0);
+    }
+
+    #[test]
+    fn test_same_state_same_births_regardless_of_spawn_history() {
+        struct Shuffled;
+
+        let mut original = SimulationWorld::with_seed(8);
+        original.seed_population_on_tile(300, 1);
+        let snapshot = original.export_world();
+
+        // Same people, but moving half of them to another archetype and back
+        // leaves them in a different ECS iteration order
+        let mut reordered = SimulationWorld::with_seed(8);
+        reordered.import_world(&snapshot).unwrap();
+        let entities: Vec<hecs::Entity> = reordered.world.iter().map(|e| e.entity()).step_by(2).collect();
+        for &entity in entities.iter().rev() {
+            reordered.world.insert_one(entity, Shuffled).unwrap();
+            reordered.world.remove_one::<Shuffled>(entity).unwrap();
+        }
+        let order = |world: &SimulationWorld| -> Vec<u64> {
+            world.world.query::<&Person>().iter().map(|(_, p)| p.id.0).collect()
+        };
+        assert_ne!(order(&original), order(&reordered));
+
+        let mut births = Vec::new();
+        for world in [&mut original, &mut reordered] {
+            world.rng = SimRng::seed_from_u64(42);
+            let mut born = 0;
+            for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 3 {
+                born += world.tick().births;
+            }
+            assert!(born > 0);
+            let mut people: Vec<String> = world.build_export_data().people
+                .iter()
+                .map(|p| serde_json::to_string(p).unwrap())
+                .collect();
+            people.sort();
+            births.push(people);
+        }
+        assert_eq!(births[0], births[1]);
+    }
+
+    #[test]
+    fn test_same_seed_same_names() {
+        let names = |seed: u64| {
+            let mut world = SimulationWorld::with_seed(seed);
+            world.immigrate(1, 60, 20, 24);
+            let first_id = world.next_person_id;
+            for _ in 0..300 {
+                world.tick();
+            }
+            let mut newborns: Vec<(u64, String, String)> = world.world
+                .query::<&Person>()
+                .iter()
+                .filter(|(_, person)| person.id.0 >= first_id)
+                .map(|(_, person)| (person.id.0, person.first_name.clone(), person.last_name.clone()))
+                .collect();
+            newborns.sort();
+            newborns
+        };
+
+        let first = names(21);
+        assert!(!first.is_empty());
+        assert_eq!(first, names(21));
+    }
+
+    #[test]
+    fn test_replay_to_reproduces_intermediate_state() {
+        let mut world = SimulationWorld::with_seed(2024);
+        world.seed_population_on_tile_range(40, 60, 1);
+        world.seed_population_on_tile_range(40, 60, 2);
+        world.mark_genesis();
+
+        let mut checkpoint = None;
+        for tick in 1..=200 {
+            world.tick();
+            if tick == 100 {
+                checkpoint = Some((world.calendar.clone(), world.demographics()));
+            }
+        }
+
+        let (date, expected) = checkpoint.unwrap();
+        let replayed = world.replay_to(date.year, date.month, date.day).unwrap();
+        assert_eq!(replayed.calendar, date);
+        assert_eq!(replayed.demographics(), expected);
+
+        assert!(world.replay_to(3999, 1, 1).is_err());
+        assert!(world.replay_to(4000, 13, 1).is_err());
+        assert!(world.replay_to(4000, 1, 0).is_err());
+        assert!(world.replay_to(4000, 1, 9).is_err());
+        assert!(world.replay_to(u16::MAX, 12, 9).is_err());
+        assert!(SimulationWorld::new().replay_to(4000, 1, 2).is_err());
+    }
+
+    #[test]
+    fn test_memory_report_tracks_population() {
+        let mut world = SimulationWorld::new();
+        world.seed_population_on_tile(100, 1);
+        let small = world.memory_report();
+        assert_eq!(small.entities as usize, world.entity_count());
+        assert!(small.archetypes >= 1);
+
+        world.seed_population_on_tile(400, 1);
+        let large = world.memory_report();
+        assert_eq!(large.entities as usize, world.entity_count());
+        assert!(large.approx_ecs_bytes > small.approx_ecs_bytes);
+    }
+
+    #[test]
+    fn test_births_attributed_to_mothers_tile() {
+        let mut world = SimulationWorld::with_seed(11);
+        world.immigrate(5, 6, 20, 25);
+        world.immigrate(6, 6, 20, 25);
+        let mothers: Vec<hecs::Entity> = world.world
+            .query::<(&Sex, &TileId)>()
+            .iter()
+            .filter(|(_, (_, tile))| tile.0 == 5)
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in &mothers {
+            // Due tomorrow
+            let due = Pregnant { due_year: world.calendar.year, due_month: world.calendar.month, father_generation: None };
+            world.world.insert_one(*entity, due).unwrap();
+        }
+
+        world.track_tile_events = true;
+        let result = world.tick();
+        assert_eq!(result.births, mothers.len() as u32);
+        assert_eq!(result.births_by_tile.get(&5).copied(), Some(mothers.len() as u32));
+        assert!(!result.births_by_tile.contains_key(&6));
+        assert_eq!(result.deaths_by_tile.values().sum::<u32>(), result.deaths);
+
+        world.track_tile_events = false;
+        assert!(world.tick().births_by_tile.is_empty());
+    }
+
+    #[test]
+    fn test_tile_pressure_crowded_center() {
+        let mut world = SimulationWorld::new();
+        world.set_tile_adjacency(HashMap::from([
+            (1, vec![2, 3, 4]),
+            (2, vec![1]),
+            (3, vec![1]),
+            (4, vec![1]),
+        ]));
+        world.seed_population_on_tile(30, 1);
+        for tile in 2..=4 {
+            world.seed_population_on_tile(10, tile);
+        }
+        world.seed_population_on_tile(5, 9); // isolated tile
+
+        assert!((world.tile_pressure(1) - 3.0).abs() < 1e-9);
+        assert!(world.tile_pressure(2) < 1.0);
+        assert_eq!(world.tile_pressure(9), 1.0);
+
+        let all = world.all_tile_pressures();
+        assert_eq!(all.len(), 5);
+        assert!(all[&1] > 1.0);
+        assert_eq!(all[&9], 1.0);
+    }
+
+    #[test]
+    fn test_events_on_tile() {
+        let mut world = SimulationWorld::with_seed(31);
+        world.immigrate(1, 60, 18, 24);
+        world.immigrate(2, 60, 18, 24);
+        world.immigrate(3, 10, 18, 24);
+        world.emigrate(3, 10);
+        for _ in 0..400 {
+            world.tick();
+        }
+
+        let births = |tile: u16| {
+            world.event_log.events_on_tile(tile, usize::MAX).iter().filter(|e| e.event_type == EventType::Birth).count()
+        };
+        let (on_1, on_2) = (births(1), births(2));
+        assert!(on_1 > 0 && on_2 > 0);
+        assert_eq!(on_1 + on_2, world.event_log.iter_by_type(EventType::Birth).count());
+        assert!(world.event_log.events_on_tile(1, usize::MAX).iter().all(|e| e.tile_id == Some(1)));
+
+        let tile_3 = world.event_log.events_on_tile(3, usize::MAX);
+        assert_eq!(tile_3.len(), 20);
+        assert!(tile_3[..10].iter().all(|e| e.event_type == EventType::Emigration));
+        assert_eq!(world.event_log.events_on_tile(3, 5).len(), 5);
+        assert!(world.event_log.events_on_tile(4, 5).is_empty());
+    }
+
+    #[test]
+    fn test_custom_events() {
+        let mut world = SimulationWorld::with_seed(8);
+        let id = world.immigrate(6, 1, 20, 20)[0];
+        world.log_custom_event("Harvest festival", None).unwrap();
+        world.log_custom_event("Battle of the ford", Some(id)).unwrap();
+        assert!(world.log_custom_event("", None).is_err());
+        assert!(world.log_custom_event(&"x".repeat(Event::MAX_LABEL_LEN + 1), None).is_err());
+        assert!(world.log_custom_event("Ghost", Some(id + 100)).is_err());
+
+        let recent = world.event_log.get_recent(2);
+        assert_eq!(recent[0].event_type, EventType::Custom);
+        assert_eq!(recent[0].label.as_deref(), Some("Battle of the ford"));
+        assert_eq!((recent[0].person_id, recent[0].tile_id), (Some(id), Some(6)));
+        assert_eq!(recent[1].label.as_deref(), Some("Harvest festival"));
+        assert_eq!(world.event_log.iter_by_type(EventType::Custom).count(), 2);
+
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.export_world()).unwrap();
+        let labels: Vec<Option<String>> = restored.event_log.get_recent(2).into_iter().map(|e| e.label).collect();
+        assert_eq!(labels, vec![Some("Battle of the ford".to_string()), Some("Harvest festival".to_string())]);
+    }
+
+    #[test]
+    fn test_tick_preview_leaves_world_untouched() {
+        let mut world = SimulationWorld::with_seed(23);
+        world.immigrate(1, 400, 18, 30);
+        for _ in 0..300 {
+            world.tick();
+        }
+        let before = world.snapshot();
+        let events = world.event_log.len();
+
+        let preview = world.simulate_tick_preview().unwrap();
+        assert_eq!(world.entity_count(), before.people.len());
+        assert_eq!(world.calendar.to_absolute_days(), Calendar::new(before.calendar.year, before.calendar.month, before.calendar.day).to_absolute_days());
+        assert_eq!(world.event_log.len(), events);
+        assert_eq!(world.next_person_id, before.next_person_id);
+
+        // Nothing happened in between, so the real tick follows the forked RNG
+        let real = world.tick();
+        assert_eq!(
+            (preview.births, preview.deaths, preview.marriages, preview.pregnancies, preview.population),
+            (real.births, real.deaths, real.marriages, real.pregnancies, real.population)
+        );
+    }
+
+    #[test]
+    fn test_rng_audit_stable_across_seeded_runs() {
+        let run = |audit: bool| {
+            let mut world = SimulationWorld::with_seed(17);
+            world.fertility_config.stillbirth_chance = 0.1;
+            world.seed_population_on_tile(300, 1);
+            world.rng_audit = audit;
+            let audits = (0..200)
+                .map(|_| {
+                    world.tick();
+                    world.last_tick_rng_audit()
+                })
+                .collect::<Vec<_>>();
+            (audits, world.export_world())
+        };
+
+        let (first, audited_world) = run(true);
+        assert_eq!(first, run(true).0);
+        let totals = first.iter().flatten().fold(systems::RngAudit::default(), |mut sum, a| {
+            sum.death += a.death;
+            sum.pregnancy += a.pregnancy;
+            sum.delivery += a.delivery;
+            sum.matchmaking += a.matchmaking;
+            sum.occupation += a.occupation;
+            sum
+        });
+        assert!(totals.death > 0 && totals.pregnancy > 0 && totals.delivery > 0 && totals.matchmaking > 0);
+        assert!(first.iter().all(|a| a.is_some_and(|a| a.death > 0)));
+
+        // Turning the audit off skips the counting but draws exactly the same values
+        let (unaudited, plain_world) = run(false);
+        assert!(unaudited.iter().all(Option::is_none));
+        assert_eq!(plain_world, audited_world);
+    }
+
+    #[test]
+    fn test_grandchild_is_second_generation() {
+        let mut world = SimulationWorld::with_seed(41);
+        world.immigrate(1, 40, 18, 22);
+        assert_eq!(world.max_generation(), 0);
+
+        let mut grandchild = None;
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 40 {
+            world.tick();
+            if world.max_generation() >= 2 {
+                grandchild = world.world.query::<(&Person, &Generation)>().iter()
+                    .find(|(_, (_, g))| g.0 == 2)
+                    .map(|(_, (p, _))| p.id.0);
+                break;
+            }
+        }
+        let grandchild = grandchild.expect("no grandchildren within 40 years");
+        assert_eq!(world.generation_of(grandchild), Some(2));
+
+        // Their mother is generation 1 and her mother a founder, if still alive
+        let entity = world.find_person(grandchild).unwrap();
+        let mother = world.world.get::<&Mother>(entity).unwrap().1.0;
+        let grandmother = world.find_person(mother)
+            .and_then(|e| world.world.get::<&Mother>(e).ok().map(|m| m.1.0))
+            .unwrap_or(0);
+        assert!(world.generation_of(mother).is_none_or(|g| g == 1));
+        assert!(world.generation_of(grandmother).is_none_or(|g| g == 0));
+
+        // Generations survive a save/load round trip
+        let mut restored = SimulationWorld::new();
+        restored.import_world(&world.snapshot().to_json()).unwrap();
+        assert_eq!(restored.generation_of(grandchild), Some(2));
+        assert_eq!(restored.max_generation(), world.max_generation());
+    }
+
+    #[test]
+    fn test_ticks_elapsed_counts_ticks_and_persists() {
+        let mut world = SimulationWorld::with_seed(2);
+        world.seed_population_on_tile(50, 1);
+        assert_eq!(world.ticks_elapsed, 0);
+        for _ in 0..37 {
+            world.tick();
+        }
+        assert_eq!(world.ticks_elapsed, 37);
+        world.tick_days(10);
+        assert_eq!(world.ticks_elapsed, 38);
+
+        let dir = std::env::temp_dir().join(format!("gridworld_ticks_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+        world.save_to_file("{}", 1, &path).unwrap();
+        let mut loaded = SimulationWorld::new();
+        loaded.load_from_file(&path).unwrap();
+        assert_eq!(loaded.ticks_elapsed, 38);
+        loaded.tick();
+        assert_eq!(loaded.ticks_elapsed, 39);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_marriage_duration() {
+        let mut world = SimulationWorld::with_seed(8);
+        let cal = world.calendar.clone();
+        let spawn = |world: &mut SimulationWorld, id: u64, sex: Sex| {
+            let person = Person::unnamed(id);
+            world.world.spawn((person, BirthDate::from_age(25, &cal), sex, TileId(1)))
+        };
+        spawn(&mut world, 1, Sex::Male);
+        spawn(&mut world, 2, Sex::Female);
+        world.rebuild_person_index();
+        assert_eq!(world.average_marriage_duration(), None);
+
+        assert_eq!(world.tick().marriages, 1);
+        assert_eq!(world.marriage_duration_years(1), Some(0));
+
+        // 3 years and 6 months later
+        world.calendar.advance_days(Calendar::DAYS_PER_YEAR as u32 * 3 + Calendar::DAYS_PER_MONTH as u32 * 6);
+        assert_eq!(world.marriage_duration_years(1), Some(3));
+        assert_eq!(world.marriage_duration_years(2), Some(3));
+        assert_eq!(world.average_marriage_duration(), Some(3.5));
+
+        // A widow's marriage no longer counts, even before the tick dissolves it
+        let husband = world.find_person(1).unwrap();
+        world.world.despawn(husband).unwrap();
+        world.rebuild_person_index();
+        assert_eq!(world.marriage_duration_years(2), None);
+        assert_eq!(world.average_marriage_duration(), None);
+        assert_eq!(world.marriage_duration_years(99), None);
+    }
+
+    #[test]
+    fn test_person_patches_only_touch_known_ids() {
+        let mut world = SimulationWorld::with_seed(5);
+        world.seed_population_on_tile(5, 1);
+        let snapshot = world.snapshot();
+
+        let result = world.apply_person_patches(vec![
+            PersonPatch { person_id: 1, first_name: Some("Ada".into()), ..Default::default() },
+            PersonPatch { person_id: 2, last_name: Some("Ng".into()), tile_id: Some(9), ..Default::default() },
+            PersonPatch { person_id: 99, first_name: Some("Ghost".into()), ..Default::default() },
+            PersonPatch { person_id: 3, ..Default::default() },
+        ]);
+        assert_eq!(result, PatchResult { applied: 3, unknown_ids: vec![99] });
+
+        let person = |world: &SimulationWorld, id: u64| {
+            let entity = world.find_person(id).unwrap();
+            let p = world.world.get::<&Person>(entity).unwrap();
+            (p.first_name.clone(), p.last_name.clone(), world.world.get::<&TileId>(entity).unwrap().0)
+        };
+        let before = |id: u64| {
+            let p = snapshot.people.iter().find(|p| p.person_id == id).unwrap();
+            (p.first_name.clone(), p.last_name.clone(), p.tile_id)
+        };
+        assert_eq!(person(&world, 1), ("Ada".to_string(), before(1).1, 1));
+        assert_eq!(person(&world, 2), (before(2).0, "Ng".to_string(), 9));
+        for id in 3..=5 {
+            assert_eq!(person(&world, id), before(id));
+        }
+        assert_eq!(world.population_by_tile(), HashMap::from([(1, 4), (9, 1)]));
+    }
+
+    #[test]
+    fn test_tile_population_distribution_and_largest() {
+        let mut world = SimulationWorld::new();
+        world.set_tile_adjacency(HashMap::from([(7, vec![8]), (8, vec![7])])); // both empty
+        for (tile, count) in [(1, 1), (2, 10), (3, 11), (4, 50), (5, 51), (6, 51)] {
+            world.seed_population_on_tile(count, tile);
+        }
+
+        assert_eq!(world.tile_population_distribution(&[0, 10, 50]), vec![2, 2, 2, 2]);
+        assert_eq!(world.tile_population_distribution(&[50, 0, 10, 10]), vec![2, 2, 2, 2]);
+        assert_eq!(world.tile_population_distribution(&[]), vec![8]);
+
+        assert_eq!(world.largest_tiles(3), vec![(5, 51), (6, 51), (4, 50)]);
+        assert_eq!(world.largest_tiles(100).len(), 6);
+        assert!(world.largest_tiles(0).is_empty());
+    }
+
+    #[test]
+    fn test_sex_ratio_by_tile() {
+        let mut world = SimulationWorld::new();
+        for tile in 1..=3 {
+            world.seed_population_on_tile(6, tile);
+        }
+        // Tile 1 all men, tile 2 all women, tile 3 split 2:4 by id
+        for (_, (person, tile, sex)) in world.world.query_mut::<(&Person, &TileId, &mut Sex)>() {
+            *sex = match tile.0 {
+                1 => Sex::Male,
+                2 => Sex::Female,
+                _ if person.id.0 % 3 == 0 => Sex::Male,
+                _ => Sex::Female,
+            };
+        }
+
+        let ratios = world.sex_ratio_by_tile();
+        assert_eq!(ratios.len(), 3);
+        assert_eq!(ratios[&1], (6, 0));
+        assert_eq!(ratios[&2], (0, 6));
+        assert_eq!(ratios[&3], (2, 4));
+        assert!(!ratios.contains_key(&4));
+    }
+
+    #[test]
+    fn test_ticking_stops_at_end_of_calendar() {
+        let mut world = SimulationWorld::with_seed(4);
+        world.calendar = Calendar::new(Calendar::MAX_YEAR, 12, Calendar::DAYS_PER_MONTH - 2);
+        world.seed_population_on_tile(50, 1);
+
+        world.tick();
+        assert!(!world.calendar.is_last());
+        world.tick_days(Calendar::DAYS_PER_YEAR as u32);
+        world.tick();
+        assert_eq!(world.calendar, Calendar::last());
+        assert_eq!(world.ticks_elapsed, 3);
+        assert_eq!(world.event_log.iter_by_type(EventType::CalendarExhausted).count(), 1);
+    }
+
+    #[test]
+    fn test_seed_relationships() {
+        let seeded = |seed: u64| {
+            let mut world = SimulationWorld::with_seed(seed);
+            world.seed_population_on_tile(400, 1);
+            world.seed_population_on_tile(200, 2);
+            let result = world.seed_relationships(0.5, true).unwrap();
+            (world, result)
+        };
+        let (world, result) = seeded(11);
+        assert_eq!(result, seeded(11).1);
+
+        let cal = world.calendar.clone();
+        let adults: Vec<bool> = world.world
+            .query::<(&BirthDate, Option<&Partner>)>()
+            .iter()
+            .filter(|(_, (birth, _))| birth.age_years(&cal) >= 16)
+            .map(|(_, (_, partner))| partner.is_some())
+            .collect();
+        let partnered = adults.iter().filter(|p| **p).count();
+        assert_eq!(partnered, result.couples as usize * 2);
+        let fraction = partnered as f64 / adults.len() as f64;
+        assert!((0.4..=0.55).contains(&fraction), "partnered fraction {}", fraction);
+
+        // Placed children belong to a couple of plausible age on their own tile
+        assert!(result.children_placed > 0);
+        for (_, (birth, tile, mother, father)) in world.world.query::<(&BirthDate, &TileId, &Mother, &Father)>().iter() {
+            let mother_birth = *world.world.get::<&BirthDate>(mother.0).unwrap();
+            let gap = mother_birth.age_years(&cal) - birth.age_years(&cal);
+            assert!(BirthDate::fertile_ages(Sex::Female).contains(&gap));
+            assert_eq!(world.world.get::<&TileId>(mother.0).unwrap().0, tile.0);
+            assert_eq!(world.world.get::<&Partner>(mother.0).unwrap().0, father.0);
+        }
+
+        let mut world = SimulationWorld::new();
+        assert!(world.seed_relationships(1.5, false).is_err());
+    }
+
+    #[test]
+    fn test_deaths_are_attributed_by_cause() {
+        use crate::config::{MortalityConfig, MortalityTable};
+
+        // Only the old die of natural causes, and every delivery kills the mother
+        let mut world = SimulationWorld::with_seed(21);
+        world.mortality_config = MortalityConfig {
+            shared: MortalityTable { neonatal_rate: 0.0, brackets: vec![(0, 0.0), (60, 1.0)] },
+            ..MortalityConfig::default()
+        };
+        world.fertility_config.maternal_mortality = 1.0;
+        let elders = world.immigrate(1, 20, 60, 70).len() as u32;
+        world.immigrate(1, 200, 20, 25);
+        world.seed_relationships(1.0, false).unwrap();
+
+        let mut by_cause: HashMap<DeathCause, u32> = HashMap::new();
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 3 {
+            let result = world.tick();
+            assert_eq!(result.deaths_by_cause.values().sum::<u32>(), result.deaths);
+            for (cause, deaths) in result.deaths_by_cause {
+                *by_cause.entry(cause).or_insert(0) += deaths;
+            }
+        }
+        assert_eq!(by_cause.get(&DeathCause::Natural), Some(&elders));
+        let childbirth = by_cause.get(&DeathCause::Childbirth).copied().unwrap_or(0);
+        assert!(childbirth > 0);
+
+        // Death events carry the same attribution, and it survives an export
+        let restored = {
+            let mut restored = SimulationWorld::new();
+            restored.import_world(&world.export_world()).unwrap();
+            restored
+        };
+        for w in [&world, &restored] {
+            let logged = |cause: DeathCause| {
+                w.event_log.iter_all().filter(|e| e.event_type == EventType::Death && e.cause == Some(cause)).count() as u32
+            };
+            assert_eq!((logged(DeathCause::Natural), logged(DeathCause::Childbirth)), (elders, childbirth));
+        }
+    }
+
+    #[test]
+    fn test_person_views_match_a_manual_query() {
+        let mut world = SimulationWorld::with_seed(17);
+        world.seed_population_on_tile(60, 1);
+        world.seed_population_on_tile(40, 2);
+        world.seed_relationships(0.8, true).unwrap();
+        for _ in 0..300 {
+            world.tick();
+        }
+
+        let mut viewed = Vec::new();
+        world.for_each_person(|view| viewed.push((view.id(), view.tile_id, view.partner_id, view.pregnant)));
+        viewed.sort_unstable();
+
+        let mut queried: Vec<(u64, u16, Option<u64>, bool)> = world.world
+            .query::<(&Person, &TileId, Option<&Partner>, Option<&Pregnant>)>()
+            .iter()
+            .map(|(_, (person, tile, partner, pregnant))| (person.id.0, tile.0, partner.map(|p| p.1.0), pregnant.is_some()))
+            .collect();
+        queried.sort_unstable();
+        assert_eq!(viewed, queried);
+        assert!(viewed.iter().any(|v| v.2.is_some()));
+
+        let (id, tile, partner, _) = viewed[0];
+        assert_eq!(world.with_person(id, |view| (view.tile_id, view.partner_id)), Some((tile, partner)));
+        assert_eq!(world.with_person(u64::MAX, |view| view.id()), None);
+    }
+
+    #[test]
+    fn test_event_retention_drops_old_years() {
+        let mut world = SimulationWorld::with_seed(30);
+        world.seed_population_on_tile(200, 1);
+        world.retention_years = Some(5);
+        let start_year = world.calendar.year;
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 * 12 {
+            world.tick();
+        }
+
+        let cutoff = world.calendar.year - 5;
+        assert!(cutoff > start_year);
+        let years: Vec<u16> = world.event_log.iter_all().map(|e| e.year).collect();
+        assert!(years.iter().all(|year| *year >= cutoff));
+        assert!(years.contains(&cutoff) && years.contains(&world.calendar.year));
+
+        // Turning retention off lets history build up again
+        world.retention_years = None;
+        for _ in 0..Calendar::DAYS_PER_YEAR as u32 {
+            world.tick();
+        }
+        assert!(world.event_log.iter_all().any(|e| e.year == cutoff));
+    }
+
+    #[test]
+    fn test_young_adults_leave_home_at_configured_age() {
+        let mut world = SimulationWorld::with_seed(31);
+        world.set_tile_adjacency(HashMap::from([(0, vec![1]), (1, vec![0, 2]), (2, vec![1])]));
+        world.seed_population_with_profile(100, 1, &AgeProfile::Uniform { min: 17, max: 17 }).unwrap();
+        world.seed_population_with_profile(100, 1, &AgeProfile::Uniform { min: 10, max: 10 }).unwrap();
+        // Keep everyone single so they're all eligible to leave
+        world.marriage_config.min_age_male = 30;
+        world.marriage_config.min_age_female = 30;
+        world.home_leaving_config = HomeLeavingConfig { age: 18, fraction: 0.5, adjacent_only: true };
+        for _ in 0..Calendar::DAYS_PER_YEAR {
+            world.tick();
+        }
+
+        let cal = world.calendar.clone();
+        let mut adults = (0u32, 0u32); // (moved, stayed)
+        for (_, (birth, tile)) in world.world.query::<(&BirthDate, &TileId)>().iter() {
+            let age = birth.age_years(&cal);
+            if age < 18 {
+                assert_eq!(tile.0, 1, "minors stay on their birth tile");
+            } else if tile.0 == 1 {
+                adults.1 += 1;
+            } else {
+                assert!(tile.0 == 0 || tile.0 == 2, "adjacent_only moves one hop");
+                adults.0 += 1;
+            }
+        }
+        assert!(adults.0 > 0 && adults.1 > 0, "about half leave: {:?}", adults);
+
+        // Off by default, and invalid fractions are rejected
+        assert_eq!(HomeLeavingConfig::default().fraction, 0.0);
+        let mut config = world.sim_config();
+        config.home_leaving.fraction = 1.5;
+        assert!(world.set_sim_config(config).is_err());
+    }
+
+    #[test]
+    fn test_tick_results_name_who_was_born_and_died() {
+        let mut world = SimulationWorld::with_seed(19);
+        world.immigrate(1, 300, 18, 30);
+        world.seed_population_on_tile(200, 1);
+
+        let results: Vec<TickResult> = (0..200).map(|_| world.tick()).collect();
+        let born: Vec<u64> = results.iter().flat_map(|r| r.birth_ids.iter().copied()).collect();
+        let died: Vec<u64> = results.iter().flat_map(|r| r.death_ids.iter().copied()).collect();
+        assert!(results.iter().all(|r| r.birth_ids.len() as u32 == r.births && r.death_ids.len() as u32 == r.deaths));
+        assert!(!born.is_empty() && !died.is_empty());
+
+        assert!(died.iter().all(|id| world.find_person(*id).is_none()));
+        assert!(born.iter().all(|id| died.contains(id) || world.find_person(*id).is_some()));
+        let mut logged: Vec<u64> = world.event_log.iter_by_type(EventType::Birth).filter_map(|e| e.person_id).collect();
+        logged.sort_unstable();
+        let mut sorted_born = born;
+        sorted_born.sort_unstable();
+        assert_eq!(logged, sorted_born);
+    }
+}
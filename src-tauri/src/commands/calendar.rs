@@ -1,119 +1,188 @@
-use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
-
-use simulation::calendar_runner::CalendarRunner;
-
-use crate::state::{
-    speed_interval, AppState, CalendarDate, CalendarState, SpeedMode, TickEvent,
-};
-
-#[tauri::command]
-pub fn get_calendar_state(state: State<AppState>) -> CalendarState {
-    let w = state.world.lock().unwrap();
-    let runner = state.calendar_runner.lock().unwrap();
-    let is_running = runner.as_ref().map(|r| r.is_running()).unwrap_or(false);
-
-    CalendarState {
-        date: CalendarDate {
-            year: w.calendar.year as i32,
-            month: w.calendar.month,
-            day: w.calendar.day as u32,
-        },
-        is_paused: !is_running,
-        current_speed: "1_day".into(),
-    }
-}
-
-#[tauri::command]
-pub fn get_calendar_speeds() -> Vec<SpeedMode> {
-    crate::state::default_speeds()
-}
-
-#[tauri::command]
-pub fn start_calendar(
-    app: AppHandle,
-    state: State<AppState>,
-    speed: Option<String>,
-) -> Result<CalendarState, String> {
-    let speed_key = speed.unwrap_or_else(|| "1_day".into());
-    let interval = speed_interval(&speed_key);
-
-    let world_clone = Arc::clone(&state.world);
-    let world_for_callback = Arc::clone(&state.world);
-
-    let mut runner = CalendarRunner::new();
-    runner.start(world_clone, interval, move |tick_result| {
-        // Read calendar state after tick
-        let (year, month, day) = {
-            let w = world_for_callback.lock().unwrap();
-            (w.calendar.year, w.calendar.month, w.calendar.day)
-        };
-
-        let event = TickEvent {
-            births: tick_result.births,
-            deaths: tick_result.deaths,
-            marriages: tick_result.marriages,
-            pregnancies: tick_result.pregnancies,
-            dissolutions: tick_result.dissolutions,
-            population: tick_result.population,
-            year: year as i32,
-            month,
-            day: day as u32,
-        };
-
-        let _ = app.emit("calendar-tick", &event);
-    });
-
-    // Store the runner
-    let mut global_runner = state.calendar_runner.lock().unwrap();
-    *global_runner = Some(runner);
-
-    // Return current state
-    let w = state.world.lock().unwrap();
-    Ok(CalendarState {
-        date: CalendarDate {
-            year: w.calendar.year as i32,
-            month: w.calendar.month,
-            day: w.calendar.day as u32,
-        },
-        is_paused: false,
-        current_speed: speed_key,
-    })
-}
-
-#[tauri::command]
-pub fn stop_calendar(state: State<AppState>) -> Result<CalendarState, String> {
-    let mut global_runner = state.calendar_runner.lock().unwrap();
-    if let Some(mut runner) = global_runner.take() {
-        runner.stop();
-    }
-
-    let w = state.world.lock().unwrap();
-    Ok(CalendarState {
-        date: CalendarDate {
-            year: w.calendar.year as i32,
-            month: w.calendar.month,
-            day: w.calendar.day as u32,
-        },
-        is_paused: true,
-        current_speed: "1_day".into(),
-    })
-}
-
-#[tauri::command]
-pub fn set_calendar_speed(
-    app: AppHandle,
-    state: State<AppState>,
-    speed: String,
-) -> Result<CalendarState, String> {
-    // Stop current runner
-    {
-        let mut global_runner = state.calendar_runner.lock().unwrap();
-        if let Some(mut runner) = global_runner.take() {
-            runner.stop();
-        }
-    }
-
-    // Restart with new speed
-    start_calendar(app, state, Some(speed))
-}
+use tauri::{AppHandle, Emitter, State};
+
+use simulation::calendar_runner::CalendarRunner;
+use simulation::SimulationWorld;
+
+use crate::state::{
+    speed_interval, AppState, CalendarDate, CalendarState, SpeedMode, TickEvent,
+};
+
+#[tauri::command]
+pub fn get_calendar_state(state: State<AppState>) -> CalendarState {
+    // Release the runner lock before taking the world lock: a start or speed change
+    // holds it while the old runner, possibly waiting on the world, shuts down
+    let is_running = state
+        .calendar_runner
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|r| r.is_running())
+        .unwrap_or(false);
+    let world = state.world();
+    let w = world.read().unwrap();
+
+    CalendarState {
+        date: CalendarDate {
+            year: w.calendar.year as i32,
+            month: w.calendar.month,
+            day: w.calendar.day as u32,
+        },
+        is_paused: !is_running,
+        current_speed: "1_day".into(),
+    }
+}
+
+/// Days since the world's start date (0 at genesis)
+#[tauri::command]
+pub fn get_current_day(state: State<AppState>) -> u64 {
+    state.world().read().unwrap().days_since_start()
+}
+
+/// Ticks run since the world began, including before it was last loaded
+#[tauri::command]
+pub fn get_ticks_elapsed(state: State<AppState>) -> u64 {
+    state.world().read().unwrap().ticks_elapsed
+}
+
+/// Convert a day count from `get_current_day` back to a calendar date
+#[tauri::command]
+pub fn calendar_from_day(state: State<AppState>, day: u64) -> CalendarDate {
+    date_for_day(&state.world().read().unwrap(), day)
+}
+
+fn date_for_day(world: &SimulationWorld, day: u64) -> CalendarDate {
+    let cal = world.date_after_start(day);
+    CalendarDate {
+        year: cal.year as i32,
+        month: cal.month,
+        day: cal.day as u32,
+    }
+}
+
+#[tauri::command]
+pub fn get_calendar_speeds() -> Vec<SpeedMode> {
+    crate::state::default_speeds()
+}
+
+/// Start ticking in the background. Fails if the calendar is already running unless
+/// `replace` is set, in which case the old runner is stopped and joined first.
+#[tauri::command]
+pub fn start_calendar(
+    app: AppHandle,
+    state: State<AppState>,
+    speed: Option<String>,
+    replace: Option<bool>,
+) -> Result<CalendarState, String> {
+    let speed_key = speed.unwrap_or_else(|| "1_day".into());
+    let interval = speed_interval(&speed_key);
+
+    // Held until the new runner is stored, so two starts can't race
+    let mut global_runner = state.calendar_runner.lock().unwrap();
+    if let Some(mut existing) = global_runner.take() {
+        if existing.is_running() && !replace.unwrap_or(false) {
+            *global_runner = Some(existing);
+            return Err("Calendar is already running".into());
+        }
+        existing.stop();
+    }
+
+    let world_clone = state.world();
+    let world_for_callback = state.world();
+
+    let mut runner = CalendarRunner::new();
+    runner.start(world_clone, interval, move |tick_result| {
+        // Read calendar state after tick
+        let (year, month, day) = {
+            let w = world_for_callback.read().unwrap();
+            (w.calendar.year, w.calendar.month, w.calendar.day)
+        };
+
+        let (birth_ids, births_cut) = crate::state::capped_ids(&tick_result.birth_ids);
+        let (death_ids, deaths_cut) = crate::state::capped_ids(&tick_result.death_ids);
+        let event = TickEvent {
+            births: tick_result.births,
+            deaths: tick_result.deaths,
+            marriages: tick_result.marriages,
+            pregnancies: tick_result.pregnancies,
+            dissolutions: tick_result.dissolutions,
+            population: tick_result.population,
+            year: year as i32,
+            month,
+            day: day as u32,
+            ticks: 1,
+            tiles: crate::state::tile_tick_data(&tick_result.births_by_tile, &tick_result.deaths_by_tile),
+            birth_ids,
+            death_ids,
+            ids_truncated: births_cut || deaths_cut,
+            deaths_by_cause: tick_result.deaths_by_cause.clone(),
+        };
+
+        let _ = app.emit("calendar-tick", &event);
+    });
+
+    // Store the runner
+    *global_runner = Some(runner);
+    drop(global_runner);
+
+    // Return current state
+    let world = state.world();
+    let w = world.read().unwrap();
+    Ok(CalendarState {
+        date: CalendarDate {
+            year: w.calendar.year as i32,
+            month: w.calendar.month,
+            day: w.calendar.day as u32,
+        },
+        is_paused: false,
+        current_speed: speed_key,
+    })
+}
+
+#[tauri::command]
+pub fn stop_calendar(state: State<AppState>) -> Result<CalendarState, String> {
+    // Don't hold the runner lock while waiting for its thread to exit
+    let runner = state.calendar_runner.lock().unwrap().take();
+    if let Some(mut runner) = runner {
+        runner.stop();
+    }
+
+    let world = state.world();
+
+    let w = world.read().unwrap();
+    Ok(CalendarState {
+        date: CalendarDate {
+            year: w.calendar.year as i32,
+            month: w.calendar.month,
+            day: w.calendar.day as u32,
+        },
+        is_paused: true,
+        current_speed: "1_day".into(),
+    })
+}
+
+#[tauri::command]
+pub fn set_calendar_speed(
+    app: AppHandle,
+    state: State<AppState>,
+    speed: String,
+) -> Result<CalendarState, String> {
+    // Restart with new speed, replacing the current runner
+    start_calendar(app, state, Some(speed), Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simulation::components::Calendar;
+
+    #[test]
+    fn test_current_day_roundtrips_to_date() {
+        let mut world = SimulationWorld::with_start(4, Calendar::new(1200, 3, 2));
+        world.tick_days(50);
+
+        let date = date_for_day(&world, world.days_since_start());
+        assert_eq!((date.year, date.month, date.day), (world.calendar.year as i32, world.calendar.month, world.calendar.day as u32));
+        let start = date_for_day(&world, 0);
+        assert_eq!((start.year, start.month, start.day), (1200, 3, 2));
+    }
+}
@@ -1,21 +1,79 @@
-use tauri::State;
-
-use crate::state::{AppConfig, AppState, CalendarConfig, HexasphereConfig};
-
-#[tauri::command]
-pub fn get_config(state: State<AppState>) -> AppConfig {
-    let seed = *state.seed.lock().unwrap();
-    AppConfig {
-        hexasphere: HexasphereConfig {
-            radius: 50.0,
-            subdivisions: 12,
-            tile_width_ratio: 1.0,
-        },
-        calendar: CalendarConfig {
-            days_per_month: 8,
-            months_per_year: 12,
-            start_year: 4000,
-        },
-        seed,
-    }
-}
+use tauri::State;
+
+use simulation::{HomeLeavingConfig, MarriageConfig, MortalityConfig, SimConfig};
+
+use crate::state::{AppConfig, AppState, CalendarConfig, HexasphereConfig};
+
+#[tauri::command]
+pub fn get_config(state: State<AppState>) -> AppConfig {
+    let seed = *state.seed.lock().unwrap();
+    let start_year = state.world().read().unwrap().start_date.year;
+    AppConfig {
+        hexasphere: HexasphereConfig {
+            radius: 50.0,
+            subdivisions: 12,
+            tile_width_ratio: 1.0,
+        },
+        calendar: CalendarConfig {
+            days_per_month: 8,
+            months_per_year: 12,
+            start_year,
+        },
+        seed,
+    }
+}
+
+/// Every simulation tunable; these are saved with the world
+#[tauri::command]
+pub fn get_sim_config(state: State<AppState>) -> SimConfig {
+    state.world().read().unwrap().sim_config()
+}
+
+#[tauri::command]
+pub fn set_sim_config(state: State<AppState>, config: SimConfig) -> Result<(), String> {
+    state.world().write().unwrap().set_sim_config(config)
+}
+
+#[tauri::command]
+pub fn get_marriage_config(state: State<AppState>) -> MarriageConfig {
+    state.world().read().unwrap().marriage_config.clone()
+}
+
+#[tauri::command]
+pub fn set_marriage_config(state: State<AppState>, config: MarriageConfig) {
+    state.world().write().unwrap().marriage_config = config;
+}
+
+#[tauri::command]
+pub fn set_smooth_mortality(state: State<AppState>, enabled: bool) {
+    state.world().write().unwrap().smooth_mortality = enabled;
+}
+
+#[tauri::command]
+pub fn set_max_population(state: State<AppState>, max_population: Option<u32>) {
+    state.world().write().unwrap().max_population = max_population;
+}
+
+#[tauri::command]
+pub fn get_mortality_config(state: State<AppState>) -> MortalityConfig {
+    state.world().read().unwrap().mortality_config.clone()
+}
+
+#[tauri::command]
+pub fn set_mortality_config(state: State<AppState>, config: MortalityConfig) -> Result<(), String> {
+    config.validate()?;
+    state.world().write().unwrap().mortality_config = config;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_home_leaving_config(state: State<AppState>) -> HomeLeavingConfig {
+    state.world().read().unwrap().home_leaving_config.clone()
+}
+
+#[tauri::command]
+pub fn set_home_leaving_config(state: State<AppState>, config: HomeLeavingConfig) -> Result<(), String> {
+    config.validate()?;
+    state.world().write().unwrap().home_leaving_config = config;
+    Ok(())
+}
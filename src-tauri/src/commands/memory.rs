@@ -1,22 +1,38 @@
-use serde::Serialize;
-
-#[derive(Serialize)]
-pub struct MemoryUsage {
-    pub physical_mem: u64,
-}
-
-#[tauri::command]
-pub fn get_memory_usage() -> MemoryUsage {
-    let physical = memory_stats::memory_stats()
-        .map(|stats| stats.physical_mem as u64)
-        .unwrap_or(0);
-
-    MemoryUsage {
-        physical_mem: physical,
-    }
-}
-
-#[tauri::command]
-pub fn exit_app() {
-    std::process::exit(0);
-}
+use serde::Serialize;
+use tauri::State;
+
+use simulation::world::{CompactResult, MemoryReport};
+
+use crate::state::AppState;
+
+#[derive(Serialize)]
+pub struct MemoryUsage {
+    pub physical_mem: u64,
+}
+
+#[tauri::command]
+pub fn get_memory_usage() -> MemoryUsage {
+    let physical = memory_stats::memory_stats()
+        .map(|stats| stats.physical_mem as u64)
+        .unwrap_or(0);
+
+    MemoryUsage {
+        physical_mem: physical,
+    }
+}
+
+#[tauri::command]
+pub fn get_memory_report(state: State<AppState>) -> MemoryReport {
+    state.world().read().unwrap().memory_report()
+}
+
+/// Despawn leftover non-person entities and rebuild the person index
+#[tauri::command]
+pub fn compact_world(state: State<AppState>) -> CompactResult {
+    state.world().write().unwrap().compact()
+}
+
+#[tauri::command]
+pub fn exit_app() {
+    std::process::exit(0);
+}
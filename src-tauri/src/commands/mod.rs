@@ -1,8 +1,8 @@
-pub mod calendar;
-pub mod config;
-pub mod memory;
-pub mod people;
-pub mod population;
-pub mod statistics;
-pub mod tiles;
-pub mod world;
+pub mod calendar;
+pub mod config;
+pub mod memory;
+pub mod people;
+pub mod population;
+pub mod statistics;
+pub mod tiles;
+pub mod world;
@@ -1,93 +1,177 @@
-use tauri::State;
-
-use simulation::components::{BirthDate, Partner, Person, PersonId, Pregnant, Sex, TileId};
-
-use crate::state::{AppState, PersonData};
-
-fn build_person_data(
-    world: &simulation::world::SimulationWorld,
-    entity: hecs::Entity,
-    person: &Person,
-    sex: &Sex,
-    birth_date: &BirthDate,
-    tile_id: &TileId,
-) -> PersonData {
-    let entity_ref = world.world.entity(entity).unwrap();
-    let is_partnered = entity_ref.get::<&Partner>().is_some();
-    let is_pregnant = entity_ref.get::<&Pregnant>().is_some();
-
-    let partner_id = if is_partnered {
-        entity_ref.get::<&Partner>().and_then(|partner| {
-            world
-                .world
-                .entity(partner.0)
-                .ok()
-                .and_then(|e| e.get::<&Person>())
-                .map(|p| p.id.0 as i64)
-        })
-    } else {
-        None
-    };
-
-    PersonData {
-        id: person.id.0 as i64,
-        first_name: person.first_name.clone(),
-        last_name: person.last_name.clone(),
-        tile_id: tile_id.0 as i32,
-        sex: matches!(sex, Sex::Male),
-        birth_year: birth_date.year as i32,
-        birth_month: birth_date.month as i32,
-        birth_day: birth_date.day as i32,
-        age_years: birth_date.age_years(&world.calendar) as i32,
-        is_partnered,
-        is_pregnant,
-        partner_id,
-    }
-}
-
-#[tauri::command]
-pub fn get_all_people(state: State<AppState>) -> Vec<PersonData> {
-    let w = state.world.lock().unwrap();
-    let mut people = Vec::new();
-
-    for (entity, (person, sex, birth_date, tile_id)) in
-        w.world.query::<(&Person, &Sex, &BirthDate, &TileId)>().iter()
-    {
-        people.push(build_person_data(&w, entity, person, sex, birth_date, tile_id));
-    }
-
-    people
-}
-
-#[tauri::command]
-pub fn get_person(state: State<AppState>, person_id: i64) -> Option<PersonData> {
-    let w = state.world.lock().unwrap();
-    let target_id = PersonId(person_id as u64);
-
-    for (entity, (person, sex, birth_date, tile_id)) in
-        w.world.query::<(&Person, &Sex, &BirthDate, &TileId)>().iter()
-    {
-        if person.id == target_id {
-            return Some(build_person_data(&w, entity, person, sex, birth_date, tile_id));
-        }
-    }
-
-    None
-}
-
-#[tauri::command]
-pub fn get_people_by_tile(state: State<AppState>, tile_id: i32) -> Vec<PersonData> {
-    let w = state.world.lock().unwrap();
-    let target_tile = TileId(tile_id as u16);
-    let mut people = Vec::new();
-
-    for (entity, (person, sex, birth_date, tid)) in
-        w.world.query::<(&Person, &Sex, &BirthDate, &TileId)>().iter()
-    {
-        if *tid == target_tile {
-            people.push(build_person_data(&w, entity, person, sex, birth_date, tid));
-        }
-    }
-
-    people
-}
+use tauri::State;
+
+use simulation::components::Sex;
+use simulation::systems::FertilityStatus;
+use simulation::world::{PatchResult, PersonPatch, PersonSort, PersonView, SimulationWorld};
+
+use crate::state::{checked_tile_id, AppState, PeoplePage, PersonData};
+
+fn build_person_data(world: &SimulationWorld, view: PersonView) -> PersonData {
+    PersonData {
+        id: view.id() as i64,
+        first_name: view.person.first_name.clone(),
+        last_name: view.person.last_name.clone(),
+        tile_id: view.tile_id as i32,
+        sex: matches!(view.sex, Sex::Male),
+        birth_year: view.birth.year as i32,
+        birth_month: view.birth.month as i32,
+        birth_day: view.birth.day as i32,
+        age_years: view.birth.age_years(&world.calendar) as i32,
+        is_partnered: view.partner_id.is_some(),
+        is_pregnant: view.pregnant,
+        // Links carry the linked person's id, which outlives them
+        partner_id: view.partner_id.map(|id| id as i64),
+        mother_id: view.mother_id.map(|id| id as i64),
+        father_id: view.father_id.map(|id| id as i64),
+    }
+}
+
+#[tauri::command]
+pub fn get_all_people(state: State<AppState>) -> Vec<PersonData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut people = Vec::new();
+    w.for_each_person(|view| people.push(build_person_data(&w, view)));
+    people
+}
+
+/// One page of people in a stable order (`sort_by` defaults to person id)
+#[tauri::command]
+pub fn get_people_page(
+    state: State<AppState>,
+    offset: u32,
+    limit: u32,
+    sort_by: Option<PersonSort>,
+) -> PeoplePage {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let (ids, total) = w.people_page(offset as usize, limit as usize, sort_by.unwrap_or_default());
+    let ids: Vec<i64> = ids.into_iter().map(|id| id as i64).collect();
+    PeoplePage {
+        people: people_by_ids(&w, &ids),
+        total: total as u32,
+    }
+}
+
+#[tauri::command]
+pub fn get_person(state: State<AppState>, person_id: i64) -> Option<PersonData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    person_data_by_id(&w, person_id)
+}
+
+/// Look up several people at once, in the order given; unknown ids are skipped
+#[tauri::command]
+pub fn get_people_by_ids(state: State<AppState>, ids: Vec<i64>) -> Vec<PersonData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    people_by_ids(&w, &ids)
+}
+
+fn people_by_ids(world: &SimulationWorld, ids: &[i64]) -> Vec<PersonData> {
+    ids.iter()
+        .filter_map(|id| person_data_by_id(world, *id))
+        .collect()
+}
+
+/// PersonData for a living person, found through the person index
+fn person_data_by_id(world: &SimulationWorld, person_id: i64) -> Option<PersonData> {
+    world.with_person(person_id as u64, |view| build_person_data(world, view))
+}
+
+#[tauri::command]
+pub fn get_people_by_tile(state: State<AppState>, tile_id: u32) -> Result<Vec<PersonData>, String> {
+    let target_tile = checked_tile_id(tile_id)?;
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut people = Vec::new();
+    w.for_each_person(|view| {
+        if view.tile_id == target_tile {
+            people.push(build_person_data(&w, view));
+        }
+    });
+    Ok(people)
+}
+
+#[tauri::command]
+pub fn get_descendant_count(state: State<AppState>, person_id: i64) -> u32 {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.descendant_count(person_id as u64)
+}
+
+#[tauri::command]
+pub fn get_living_lineage(state: State<AppState>, person_id: i64) -> Vec<i64> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.living_lineage(person_id as u64)
+        .into_iter()
+        .map(|id| id as i64)
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_fertility_status(state: State<AppState>, person_id: i64) -> Option<FertilityStatus> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.fertility_status(person_id as u64)
+}
+
+/// Generations since the founders (0 = founder); None if the person is not alive
+#[tauri::command]
+pub fn get_generation(state: State<AppState>, person_id: i64) -> Option<u16> {
+    state.world().read().unwrap().generation_of(person_id as u64)
+}
+
+#[tauri::command]
+pub fn get_max_generation(state: State<AppState>) -> u16 {
+    state.world().read().unwrap().max_generation()
+}
+
+/// Whole years the person has been married to their current partner (None if single or dead)
+#[tauri::command]
+pub fn get_marriage_duration(state: State<AppState>, person_id: i64) -> Option<u16> {
+    state.world().read().unwrap().marriage_duration_years(person_id as u64)
+}
+
+/// Write Node-owned edits (names, tile) back to living people without a full import
+#[tauri::command]
+pub fn apply_person_patches(state: State<AppState>, patches: Vec<PersonPatch>) -> PatchResult {
+    state.world().write().unwrap().apply_person_patches(patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_people_by_ids_skips_unknown_and_keeps_order() {
+        let mut world = SimulationWorld::with_seed(1);
+        let ids = world.immigrate(1, 4, 20, 30);
+        let requested = [ids[2] as i64, 999, ids[0] as i64, -1, ids[3] as i64];
+
+        let found: Vec<i64> = people_by_ids(&world, &requested).iter().map(|p| p.id).collect();
+        assert_eq!(found, vec![ids[2] as i64, ids[0] as i64, ids[3] as i64]);
+    }
+
+    #[test]
+    fn test_person_data_resolves_parent_ids() {
+        use simulation::components::{BirthDate, Father, Mother, Person, PersonId, TileId};
+
+        let mut world = SimulationWorld::with_seed(1);
+        let parents = world.immigrate(1, 2, 25, 25);
+        let (mother, father) = (world.find_person(parents[0]).unwrap(), world.find_person(parents[1]).unwrap());
+        let child = Person { id: PersonId(50), first_name: "Kid".into(), last_name: String::new() };
+        let birth = BirthDate::from_age(1, &world.calendar);
+        world.world.spawn((child, Sex::Female, birth, TileId(1), Mother(mother, PersonId(parents[0])), Father(father, PersonId(parents[1]))));
+        world.rebuild_person_index();
+
+        let data = person_data_by_id(&world, 50).unwrap();
+        assert_eq!(data.mother_id, Some(parents[0] as i64));
+        assert_eq!(data.father_id, Some(parents[1] as i64));
+
+        // Founders have no parents on record
+        let founder = person_data_by_id(&world, parents[0] as i64).unwrap();
+        assert_eq!((founder.mother_id, founder.father_id), (None, None));
+    }
+}
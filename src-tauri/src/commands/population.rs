@@ -1,39 +1,271 @@
-use tauri::State;
-
-use simulation::world::Demographics;
-
-use crate::state::{AppState, TilePopulationData};
-
-#[tauri::command]
-pub fn get_population(state: State<AppState>) -> u32 {
-    state.world.lock().unwrap().entity_count() as u32
-}
-
-#[tauri::command]
-pub fn get_demographics(state: State<AppState>) -> Demographics {
-    state.world.lock().unwrap().demographics()
-}
-
-#[tauri::command]
-pub fn get_population_by_tile(state: State<AppState>) -> Vec<TilePopulationData> {
-    let w = state.world.lock().unwrap();
-    let map = w.population_by_tile();
-    let mut result: Vec<TilePopulationData> = map
-        .into_iter()
-        .map(|(tile_id, count)| TilePopulationData {
-            tile_id: tile_id as u32,
-            count,
-        })
-        .collect();
-    result.sort_by_key(|t| t.tile_id);
-    result
-}
-
-#[tauri::command]
-pub fn get_tile_population(state: State<AppState>, tile_id: u32) -> u32 {
-    state
-        .world
-        .lock()
-        .unwrap()
-        .tile_population(tile_id as u16)
-}
+use std::collections::HashMap;
+
+use tauri::State;
+
+use simulation::components::Occupation;
+use simulation::AgeProfile;
+use simulation::world::{CustomDemographics, Demographics, SeedPerson, SeededRelationships};
+
+use crate::state::{
+    checked_tile_id, AppState, EligibleSinglesData, ParityData, TileAdjacency, TilePopulationData, TilePressureData,
+    TileSexData, TileWorkforceData,
+};
+
+#[tauri::command]
+pub fn get_population(state: State<AppState>) -> u32 {
+    state.world().read().unwrap().entity_count() as u32
+}
+
+#[tauri::command]
+pub fn get_demographics(state: State<AppState>) -> Demographics {
+    state.world().read().unwrap().demographics()
+}
+
+/// `get_demographics` for one tile, for map tooltips
+#[tauri::command]
+pub fn get_tile_demographics(state: State<AppState>, tile_id: u32) -> Result<Demographics, String> {
+    let tile_id = checked_tile_id(tile_id)?;
+    Ok(state.world().read().unwrap().tile_demographics(tile_id))
+}
+
+/// Demographics with age brackets ending at each of `bounds` (inclusive), plus an open last bracket
+#[tauri::command]
+pub fn get_demographics_custom(state: State<AppState>, bounds: Vec<u16>) -> CustomDemographics {
+    state.world().read().unwrap().demographics_with_brackets(&bounds)
+}
+
+#[tauri::command]
+pub fn get_population_by_tile(state: State<AppState>) -> Vec<TilePopulationData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let map = w.population_by_tile();
+    let mut result: Vec<TilePopulationData> = map
+        .into_iter()
+        .map(|(tile_id, count)| TilePopulationData {
+            tile_id: tile_id as u32,
+            count,
+        })
+        .collect();
+    result.sort_by_key(|t| t.tile_id);
+    result
+}
+
+/// Men and women on each occupied tile, for spotting tiles with no marriage market
+#[tauri::command]
+pub fn get_sex_ratio_by_tile(state: State<AppState>) -> Vec<TileSexData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut result: Vec<TileSexData> = w
+        .sex_ratio_by_tile()
+        .into_iter()
+        .map(|(tile_id, (males, females))| TileSexData {
+            tile_id: tile_id as u32,
+            males,
+            females,
+        })
+        .collect();
+    result.sort_by_key(|t| t.tile_id);
+    result
+}
+
+/// Number of tiles per population bin; `bins` are inclusive upper counts plus an open last bin
+#[tauri::command]
+pub fn get_tile_population_distribution(state: State<AppState>, bins: Vec<u32>) -> Vec<u32> {
+    state.world().read().unwrap().tile_population_distribution(&bins)
+}
+
+/// The `count` most populous tiles, largest first
+#[tauri::command]
+pub fn get_largest_tiles(state: State<AppState>, count: u32) -> Vec<TilePopulationData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.largest_tiles(count as usize)
+        .into_iter()
+        .map(|(tile_id, count)| TilePopulationData {
+            tile_id: tile_id as u32,
+            count,
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_tile_population(state: State<AppState>, tile_id: u32) -> Result<u32, String> {
+    let tile_id = checked_tile_id(tile_id)?;
+    Ok(state
+        .world()
+        .read()
+        .unwrap()
+        .tile_population(tile_id))
+}
+
+#[tauri::command]
+pub fn set_tile_adjacency(state: State<AppState>, adjacency: Vec<TileAdjacency>) -> Result<(), String> {
+    let graph = adjacency
+        .into_iter()
+        .map(|t| {
+            let neighbors = t.neighbors.into_iter().map(checked_tile_id).collect::<Result<_, _>>()?;
+            Ok((checked_tile_id(t.tile_id)?, neighbors))
+        })
+        .collect::<Result<_, String>>()?;
+    state.world().write().unwrap().set_tile_adjacency(graph);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_tile_pressures(state: State<AppState>) -> Vec<TilePressureData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut result: Vec<TilePressureData> = w
+        .all_tile_pressures()
+        .into_iter()
+        .map(|(tile_id, pressure)| TilePressureData {
+            tile_id: tile_id as u32,
+            pressure,
+        })
+        .collect();
+    result.sort_by_key(|t| t.tile_id);
+    result
+}
+
+#[tauri::command]
+pub fn get_workforce_by_tile(state: State<AppState>) -> Vec<TileWorkforceData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut result: Vec<TileWorkforceData> = w
+        .workforce_by_tile()
+        .into_iter()
+        .map(|(tile_id, counts)| {
+            let count = |occupation: Occupation| counts.get(&occupation).copied().unwrap_or(0);
+            TileWorkforceData {
+                tile_id: tile_id as u32,
+                children: count(Occupation::Child),
+                farmers: count(Occupation::Farmer),
+                laborers: count(Occupation::Laborer),
+                elders: count(Occupation::Elder),
+            }
+        })
+        .collect();
+    result.sort_by_key(|t| t.tile_id);
+    result
+}
+
+#[tauri::command]
+pub fn get_parity_distribution(state: State<AppState>) -> Vec<ParityData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let mut result: Vec<ParityData> = w
+        .women_by_parity()
+        .into_iter()
+        .map(|(children_born, women)| ParityData { children_born: children_born as u32, women })
+        .collect();
+    result.sort_by_key(|p| p.children_born);
+    result
+}
+
+/// Women past the fertile window by number of children born, smallest family first
+#[tauri::command]
+pub fn get_completed_family_sizes(state: State<AppState>) -> Vec<ParityData> {
+    let mut result: Vec<ParityData> = state
+        .world()
+        .read()
+        .unwrap()
+        .completed_family_sizes()
+        .into_iter()
+        .map(|(children_born, women)| ParityData { children_born: children_born as u32, women })
+        .collect();
+    result.sort_by_key(|p| p.children_born);
+    result
+}
+
+/// Mean children born per woman past the fertile window (None if there are none)
+#[tauri::command]
+pub fn get_mean_completed_family_size(state: State<AppState>) -> Option<f64> {
+    state.world().read().unwrap().mean_completed_family_size()
+}
+
+/// Eligible singles on one tile, or across the whole world if `tile_id` is omitted
+#[tauri::command]
+pub fn get_eligible_singles(state: State<AppState>, tile_id: Option<u32>) -> Result<EligibleSinglesData, String> {
+    let tile_id = tile_id.map(checked_tile_id).transpose()?;
+    let world = state.world();
+    let w = world.read().unwrap();
+    let (men, women) = match tile_id {
+        Some(tile_id) => w.eligible_singles_on_tile(tile_id),
+        None => w.eligible_singles(),
+    };
+    let to_ids = |ids: Vec<u64>| ids.into_iter().map(|id| id as i64).collect();
+    Ok(EligibleSinglesData { men: to_ids(men), women: to_ids(women) })
+}
+
+/// Seed founders on a tile with ages drawn from `profile` (standard mix if omitted)
+#[tauri::command]
+pub fn seed_population_with_profile(
+    state: State<AppState>,
+    tile_id: u32,
+    count: u32,
+    profile: Option<AgeProfile>,
+) -> Result<u32, String> {
+    state
+        .world()
+        .write()
+        .unwrap()
+        .seed_population_with_profile(count as usize, checked_tile_id(tile_id)?, &profile.unwrap_or_default())?;
+    Ok(count)
+}
+
+/// Pair about `marriage_fraction` of single adult founders on each tile, optionally
+/// giving unparented children to couples of fitting age
+#[tauri::command]
+pub fn seed_relationships(
+    state: State<AppState>,
+    marriage_fraction: f64,
+    place_children: bool,
+) -> Result<SeededRelationships, String> {
+    state.world().write().unwrap().seed_relationships(marriage_fraction, place_children)
+}
+
+/// Spawn designed founders; `partner` indexes into the same array. Returns their ids.
+#[tauri::command]
+pub fn seed_from_records(state: State<AppState>, records: Vec<SeedPerson>) -> Result<Vec<i64>, String> {
+    let ids = state.world().write().unwrap().seed_from_records(&records)?;
+    Ok(ids.into_iter().map(|id| id as i64).collect())
+}
+
+#[tauri::command]
+pub fn immigrate(
+    state: State<AppState>,
+    tile_id: u32,
+    count: u32,
+    age_min: Option<u16>,
+    age_max: Option<u16>,
+) -> Result<Vec<i64>, String> {
+    let tile_id = checked_tile_id(tile_id)?;
+    Ok(state
+        .world()
+        .write()
+        .unwrap()
+        .immigrate(tile_id, count as usize, age_min.unwrap_or(16), age_max.unwrap_or(40))
+        .into_iter()
+        .map(|id| id as i64)
+        .collect())
+}
+
+#[tauri::command]
+pub fn emigrate(state: State<AppState>, tile_id: u32, count: u32) -> Result<u32, String> {
+    let tile_id = checked_tile_id(tile_id)?;
+    Ok(state
+        .world()
+        .write()
+        .unwrap()
+        .emigrate(tile_id, count as usize))
+}
+
+/// Re-home everyone on the mapped tiles (`{ from: to }`); returns how many people moved
+#[tauri::command]
+pub fn remap_tiles(state: State<AppState>, mapping: HashMap<u32, u32>) -> Result<u32, String> {
+    let mapping: HashMap<u16, u16> = mapping
+        .into_iter()
+        .map(|(from, to)| Ok((checked_tile_id(from)?, checked_tile_id(to)?)))
+        .collect::<Result<_, String>>()?;
+    Ok(state.world().write().unwrap().remap_tiles(&mapping))
+}
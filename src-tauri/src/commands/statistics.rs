@@ -1,71 +1,149 @@
-use tauri::State;
-
-use simulation::components::EventType;
-use simulation::world::VitalStatistics;
-
-use crate::state::{AppState, EventData};
-
-#[tauri::command]
-pub fn get_vital_statistics(
-    state: State<AppState>,
-    start_year: i32,
-    end_year: i32,
-) -> VitalStatistics {
-    state
-        .world
-        .lock()
-        .unwrap()
-        .calculate_vital_statistics(start_year as u16, end_year as u16)
-}
-
-#[tauri::command]
-pub fn get_current_year_statistics(state: State<AppState>) -> VitalStatistics {
-    state
-        .world
-        .lock()
-        .unwrap()
-        .calculate_current_year_statistics()
-}
-
-#[tauri::command]
-pub fn get_recent_statistics(state: State<AppState>, years: Option<u32>) -> VitalStatistics {
-    state
-        .world
-        .lock()
-        .unwrap()
-        .calculate_recent_statistics(years.unwrap_or(10) as u16)
-}
-
-#[tauri::command]
-pub fn get_recent_events(state: State<AppState>, count: Option<u32>) -> Vec<EventData> {
-    let w = state.world.lock().unwrap();
-    w.event_log
-        .get_recent(count.unwrap_or(100) as usize)
-        .into_iter()
-        .map(event_to_data)
-        .collect()
-}
-
-#[tauri::command]
-pub fn get_event_count(state: State<AppState>) -> u32 {
-    state.world.lock().unwrap().event_log.len() as u32
-}
-
-fn event_to_data(event: simulation::components::Event) -> EventData {
-    let event_type = match event.event_type {
-        EventType::Birth => "birth",
-        EventType::Death => "death",
-        EventType::Marriage => "marriage",
-        EventType::PregnancyStarted => "pregnancy_started",
-        EventType::Dissolution => "dissolution",
-    }
-    .to_string();
-
-    EventData {
-        event_type,
-        year: event.year as i32,
-        month: event.month,
-        day: event.day,
-        person_id: event.person_id.map(|id| id as i64),
-    }
-}
+use tauri::State;
+
+use simulation::components::{Calendar, EventType};
+use simulation::world::{GrowthRate, VitalStatistics};
+
+use crate::state::{checked_tile_id, checked_year, AppState, EventData, YearPopulationData};
+
+#[tauri::command]
+pub fn get_vital_statistics(
+    state: State<AppState>,
+    start_year: i32,
+    end_year: i32,
+) -> Result<VitalStatistics, String> {
+    let (start_year, end_year) = (checked_year(start_year)?, checked_year(end_year)?);
+    Ok(state
+        .world()
+        .read()
+        .unwrap()
+        .calculate_vital_statistics(start_year, end_year))
+}
+
+#[tauri::command]
+pub fn get_current_year_statistics(state: State<AppState>) -> VitalStatistics {
+    state
+        .world()
+        .read()
+        .unwrap()
+        .calculate_current_year_statistics()
+}
+
+#[tauri::command]
+pub fn get_recent_statistics(state: State<AppState>, years: Option<u32>) -> Result<VitalStatistics, String> {
+    let years = years.unwrap_or(10);
+    let years = u16::try_from(years).map_err(|_| format!("Span of {} years is over {}", years, u16::MAX))?;
+    Ok(state
+        .world()
+        .read()
+        .unwrap()
+        .calculate_recent_statistics(years))
+}
+
+#[tauri::command]
+pub fn get_recent_events(state: State<AppState>, count: Option<u32>) -> Vec<EventData> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.event_log
+        .get_recent(count.unwrap_or(100) as usize)
+        .into_iter()
+        .map(event_to_data)
+        .collect()
+}
+
+/// The latest `count` (default 100) events on one tile, newest first
+#[tauri::command]
+pub fn get_events_on_tile(state: State<AppState>, tile_id: u32, count: Option<u32>) -> Result<Vec<EventData>, String> {
+    let tile_id = checked_tile_id(tile_id)?;
+    let world = state.world();
+    let w = world.read().unwrap();
+    Ok(w.event_log
+        .events_on_tile(tile_id, count.unwrap_or(100) as usize)
+        .into_iter()
+        .map(event_to_data)
+        .collect())
+}
+
+/// Log a game-specific event labelled `label`, optionally about one person
+#[tauri::command]
+pub fn log_custom_event(state: State<AppState>, label: String, person_id: Option<i64>) -> Result<(), String> {
+    state
+        .world()
+        .write()
+        .unwrap()
+        .log_custom_event(&label, person_id.map(|id| id as u64))
+}
+
+#[tauri::command]
+pub fn get_event_count(state: State<AppState>) -> u32 {
+    state.world().read().unwrap().event_log.len() as u32
+}
+
+#[tauri::command]
+pub fn clear_event_log(state: State<AppState>) {
+    state.world().write().unwrap().event_log.clear();
+}
+
+/// Keep only the last `years` years of events, pruned every tick (None = no time limit)
+#[tauri::command]
+pub fn set_event_retention(state: State<AppState>, years: Option<u16>) {
+    state.world().write().unwrap().retention_years = years;
+}
+
+/// Drop events from before `year`; returns how many were removed
+#[tauri::command]
+pub fn prune_events_before(state: State<AppState>, year: i32) -> Result<u32, String> {
+    let year = checked_year(year)?;
+    Ok(state.world().write().unwrap().event_log.prune_before(year) as u32)
+}
+
+fn event_to_data(event: simulation::components::Event) -> EventData {
+    let event_type = match event.event_type {
+        EventType::Birth => "birth",
+        EventType::Death => "death",
+        EventType::Marriage => "marriage",
+        EventType::PregnancyStarted => "pregnancy_started",
+        EventType::Dissolution => "dissolution",
+        EventType::Immigration => "immigration",
+        EventType::Emigration => "emigration",
+        EventType::Stillbirth => "stillbirth",
+        EventType::CapacityReached => "capacity_reached",
+        EventType::CalendarExhausted => "calendar_exhausted",
+        EventType::Custom => "custom",
+    }
+    .to_string();
+
+    EventData {
+        event_type,
+        year: event.year as i32,
+        month: event.month,
+        day: event.day,
+        person_id: event.person_id.map(|id| id as i64),
+        tile_id: event.tile_id.map(|tile| tile as u32),
+        label: event.label,
+        cause: event.cause,
+    }
+}
+
+/// End-of-year population for each recorded year in `start_year..=end_year`, oldest first
+#[tauri::command]
+pub fn get_population_series(
+    state: State<AppState>,
+    start_year: i32,
+    end_year: i32,
+) -> Result<Vec<YearPopulationData>, String> {
+    let (start_year, end_year) = (checked_year(start_year)?, checked_year(end_year)?);
+    let world = state.world();
+    let w = world.read().unwrap();
+    Ok(w.population_series(start_year, end_year)
+        .into_iter()
+        .map(|(year, population)| YearPopulationData { year: year as i32, population })
+        .collect())
+}
+
+/// Births minus deaths and annualized growth over the last `window_days` days (default one year)
+#[tauri::command]
+pub fn get_growth_rate(state: State<AppState>, window_days: Option<u32>) -> GrowthRate {
+    let world = state.world();
+    let w = world.read().unwrap();
+    w.growth_rate(window_days.unwrap_or(Calendar::DAYS_PER_YEAR as u32))
+}
@@ -1,203 +1,417 @@
-use serde::Deserialize;
-use tauri::State;
-
-use crate::state::{AppState, TileProperties};
-
-#[derive(Deserialize)]
-pub struct TileCenter {
-    pub id: u32,
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
-
-/// Calculate tile properties for a batch of tiles from their center coordinates.
-/// This is a port of the server-side calculateTileProperties() function.
-///
-/// Parameters:
-/// - land_water_ratio: 0-100, higher = more land (default: 50)
-/// - roughness: 0-100, higher = more mountainous (default: 50)
-#[tauri::command]
-pub fn calculate_tile_properties(
-    state: State<AppState>,
-    tiles: Vec<TileCenter>,
-    land_water_ratio: Option<f64>,
-    roughness: Option<f64>,
-) -> Vec<TileProperties> {
-    let seed = *state.seed.lock().unwrap();
-    let land_water_ratio = land_water_ratio.unwrap_or(50.0).clamp(0.0, 100.0);
-    let roughness = roughness.unwrap_or(50.0).clamp(0.0, 100.0);
-
-    tiles
-        .iter()
-        .map(|tile| {
-            let terrain = calculate_terrain(tile.x, tile.y, tile.z, seed, land_water_ratio, roughness);
-            let biome = calculate_biome(tile.x, tile.y, tile.z, &terrain, seed);
-            let fertility = calculate_fertility(tile.x, tile.y, tile.z, biome.as_deref(), &terrain, seed);
-            let is_habitable = check_habitable(&terrain, biome.as_deref());
-
-            TileProperties {
-                id: tile.id,
-                terrain_type: terrain,
-                biome,
-                fertility,
-                is_habitable,
-            }
-        })
-        .collect()
-}
-
-// -- Terrain generation (ported from server/services/terrain/index.ts) --
-
-fn position_hash(x: f64, y: f64, z: f64, seed: f64) -> f64 {
-    let h1 = (x * 12.9898 + y * 78.233 + z * 45.164 + seed * 0.001).sin() * 43758.5453;
-    let h2 = (x * 39.346 + y * 11.135 + z * 83.155 + seed * 0.002).sin() * 93751.1459;
-    let combined = h1 + h2;
-    combined - combined.floor()
-}
-
-struct PositionRandom {
-    x: f64,
-    y: f64,
-    z: f64,
-    seed: f64,
-    sample: u32,
-}
-
-impl PositionRandom {
-    fn new(x: f64, y: f64, z: f64, seed: f64) -> Self {
-        Self { x, y, z, seed, sample: 0 }
-    }
-
-    fn next(&mut self) -> f64 {
-        self.sample += 1;
-        let s = self.sample as f64;
-        position_hash(
-            self.x + s * 0.137,
-            self.y + s * 0.293,
-            self.z + s * 0.419,
-            self.seed,
-        )
-    }
-}
-
-/// Spatially coherent noise: sine waves in random 3D directions (no axis bias).
-fn spherical_noise(x: f64, y: f64, z: f64, seed: f64, octaves: &[(f64, f64)]) -> f64 {
-    let mut result = 0.0;
-    for (i, &(freq, amp)) in octaves.iter().enumerate() {
-        let idx = i as f64;
-        let theta = position_hash(seed, idx, 0.0, 0.0) * std::f64::consts::TAU;
-        let cos_phi = position_hash(seed, idx, 1.0, 0.0) * 2.0 - 1.0;
-        let sin_phi = (1.0 - cos_phi * cos_phi).sqrt();
-        let (dx, dy, dz) = (sin_phi * theta.cos(), sin_phi * theta.sin(), cos_phi);
-        let phase = position_hash(seed, idx, 2.0, 0.0) * std::f64::consts::TAU;
-        result += ((dx * x + dy * y + dz * z) * freq + phase).sin() * amp;
-    }
-    result
-}
-
-fn calculate_terrain(x: f64, y: f64, z: f64, seed: u32, land_water_ratio: f64, roughness: f64) -> String {
-    let seed_f = seed as f64;
-    let p1 = position_hash(seed_f, seed_f * 0.7, seed_f * 0.3, 0.0) * std::f64::consts::TAU;
-    let p2 = position_hash(seed_f * 0.5, seed_f, seed_f * 0.9, 0.0) * std::f64::consts::TAU;
-    let p3 = position_hash(seed_f * 0.3, seed_f * 0.6, seed_f, 0.0) * std::f64::consts::TAU;
-    let p4 = position_hash(seed_f * 0.8, seed_f * 0.2, seed_f * 0.5, 0.0) * std::f64::consts::TAU;
-
-    let c1 = (x * 0.10 + p1).sin() * (z * 0.12 + p2).cos();
-    let c2 = (z * 0.14 + p3).sin() * (y * 0.10 + p1).cos() * 0.6;
-    let c3 = (y * 0.12 + p2).sin() * (x * 0.08 + p4).cos() * 0.4;
-    let c4 = (x * 0.22 + z * 0.18 + p4).sin() * 0.25;
-
-    let continent_mask = c1 + c2 + c3 + c4;
-
-    // Map land_water_ratio (0-100) to threshold (2.5 to -2.5)
-    // continent_mask ranges roughly from -2.25 to +2.25
-    // land_water_ratio = 0 (100% water) → threshold = 2.5 (all tiles become ocean)
-    // land_water_ratio = 100 (100% land) → threshold = -2.5 (no tiles become ocean)
-    // land_water_ratio = 50 (balanced) → threshold = 0.0 (balanced mix)
-    let land_threshold = 2.5 - (land_water_ratio / 100.0) * 5.0;
-
-    if continent_mask < land_threshold {
-        return "ocean".into();
-    }
-
-    // Mountain/hill: per-tile uniform hash (0–1), independent of continent shape.
-    // Uniform distribution guarantees roughness=N → ~N% of land tiles become mountains.
-    let elevation = position_hash(x, y, z, seed_f + 500.0);
-
-    // roughness=25 → threshold=0.75 → top 25% of land tiles become mountains
-    let mountain_frac = roughness / 100.0;
-    let mountain_threshold = 1.0 - mountain_frac;
-    // Hills occupy the next band (1.5× roughness total elevated terrain)
-    let hill_frac = (mountain_frac * 1.5).min(1.0);
-    let hill_threshold = 1.0 - hill_frac;
-
-    if roughness < 5.0 {
-        "flats".into()
-    } else if elevation > mountain_threshold {
-        "mountains".into()
-    } else if elevation > hill_threshold {
-        "hills".into()
-    } else {
-        "flats".into()
-    }
-}
-
-fn calculate_biome(x: f64, y: f64, z: f64, terrain: &str, seed: u32) -> Option<String> {
-    if terrain == "ocean" {
-        return None;
-    }
-    if terrain == "mountains" {
-        return Some("alpine".into());
-    }
-
-    let radius = (x * x + y * y + z * z).sqrt();
-    let latitude = (y / radius).asin().to_degrees().abs();
-    let mut rng = PositionRandom::new(x, y, z, seed as f64);
-
-    if latitude > 60.0 {
-        Some("tundra".into())
-    } else if latitude > 45.0 {
-        Some(if rng.next() < 0.7 { "plains" } else { "tundra" }.into())
-    } else if latitude > 30.0 {
-        Some(if rng.next() < 0.6 { "grassland" } else { "plains" }.into())
-    } else if latitude > 15.0 {
-        Some(if rng.next() < 0.5 { "grassland" } else { "desert" }.into())
-    } else {
-        Some(if rng.next() < 0.7 { "grassland" } else { "desert" }.into())
-    }
-}
-
-fn calculate_fertility(x: f64, y: f64, z: f64, biome: Option<&str>, terrain: &str, seed: u32) -> u32 {
-    let biome = match biome {
-        Some(b) if terrain != "ocean" && terrain != "mountains" => b,
-        _ => return 0,
-    };
-
-    // Desert has 0 fertility (no variation)
-    if biome == "desert" {
-        return 0;
-    }
-
-    let base: i32 = match biome {
-        "grassland" => 80,
-        "plains" => 70,
-        "tundra" => 30,
-        "alpine" => 25,
-        _ => 50,
-    };
-
-    let mut rng = PositionRandom::new(x, y, z, (seed + 1000) as f64);
-    let variation = ((rng.next() - 0.5) * 20.0) as i32;
-    (base + variation).clamp(0, 100) as u32
-}
-
-fn check_habitable(terrain: &str, biome: Option<&str>) -> bool {
-    if terrain == "ocean" || terrain == "mountains" {
-        return false;
-    }
-    match biome {
-        Some("desert") | Some("tundra") | Some("alpine") => false,
-        _ => true,
-    }
-}
+use serde::Deserialize;
+use tauri::State;
+
+use crate::state::{AppState, TileCacheStats, TileProperties, TilePropertyCache};
+
+#[derive(Deserialize)]
+pub struct TileCenter {
+    pub id: u32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Optional overrides for terrain generation thresholds. Any unset field is
+/// derived from `land_water_ratio`/`roughness` as before.
+#[derive(Deserialize, Clone, Copy, Default)]
+pub struct TerrainConfig {
+    /// Continent mask below this is ocean (roughly -2.5 to 2.5; higher = more ocean)
+    pub land_threshold: Option<f64>,
+    /// Land with elevation (0-1) above this is mountains
+    pub mountain_elev: Option<f64>,
+    /// Land with elevation above this (but not mountains) is hills
+    pub hills_elev: Option<f64>,
+}
+
+/// Resolved cutoffs used by `calculate_terrain`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TerrainThresholds {
+    land: f64,
+    mountain: f64,
+    hills: f64,
+}
+
+impl TerrainConfig {
+    fn resolve(&self, land_water_ratio: f64, roughness: f64) -> TerrainThresholds {
+        // Map land_water_ratio (0-100) to threshold (2.5 to -2.5)
+        // continent_mask ranges roughly from -2.25 to +2.25
+        // land_water_ratio = 0 (100% water) → threshold = 2.5 (all tiles become ocean)
+        // land_water_ratio = 100 (100% land) → threshold = -2.5 (no tiles become ocean)
+        // land_water_ratio = 50 (balanced) → threshold = 0.0 (balanced mix)
+        let land = 2.5 - (land_water_ratio / 100.0) * 5.0;
+
+        // roughness=25 → threshold=0.75 → top 25% of land tiles become mountains
+        let mountain_frac = roughness / 100.0;
+        // Hills occupy the next band (1.5× roughness total elevated terrain)
+        let hill_frac = (mountain_frac * 1.5).min(1.0);
+        // Nearly smooth worlds are all flats
+        let (mountain, hills) = if roughness < 5.0 {
+            (f64::INFINITY, f64::INFINITY)
+        } else {
+            (1.0 - mountain_frac, 1.0 - hill_frac)
+        };
+
+        TerrainThresholds {
+            land: self.land_threshold.unwrap_or(land),
+            mountain: self.mountain_elev.unwrap_or(mountain),
+            hills: self.hills_elev.unwrap_or(hills),
+        }
+    }
+}
+
+/// Calculate tile properties for a batch of tiles from their center coordinates.
+/// This is a port of the server-side calculateTileProperties() function.
+///
+/// Parameters:
+/// - land_water_ratio: 0-100, higher = more land (default: 50)
+/// - roughness: 0-100, higher = more mountainous (default: 50)
+/// - terrain: explicit thresholds overriding the two above
+#[tauri::command]
+pub fn calculate_tile_properties(
+    state: State<AppState>,
+    tiles: Vec<TileCenter>,
+    land_water_ratio: Option<f64>,
+    roughness: Option<f64>,
+    terrain: Option<TerrainConfig>,
+) -> Vec<TileProperties> {
+    properties_for(&state, &tiles, land_water_ratio, roughness, terrain)
+}
+
+fn properties_for(
+    state: &AppState,
+    tiles: &[TileCenter],
+    land_water_ratio: Option<f64>,
+    roughness: Option<f64>,
+    terrain: Option<TerrainConfig>,
+) -> Vec<TileProperties> {
+    let seed = *state.seed.lock().unwrap();
+    let land_water_ratio = land_water_ratio.unwrap_or(50.0).clamp(0.0, 100.0);
+    let roughness = roughness.unwrap_or(50.0).clamp(0.0, 100.0);
+    let thresholds = terrain.unwrap_or_default().resolve(land_water_ratio, roughness);
+
+    let mut cache = state.tile_cache.lock().unwrap();
+    tile_properties(&mut cache, tiles, seed, thresholds)
+}
+
+/// Ids of the habitable tiles among `tiles`, in input order. Same inputs as
+/// `calculate_tile_properties`; the result can be passed straight to `restart_world`.
+#[tauri::command]
+pub fn get_habitable_tiles(
+    state: State<AppState>,
+    tiles: Vec<TileCenter>,
+    land_water_ratio: Option<f64>,
+    roughness: Option<f64>,
+    terrain: Option<TerrainConfig>,
+) -> Vec<u32> {
+    habitable_ids(properties_for(&state, &tiles, land_water_ratio, roughness, terrain))
+}
+
+fn habitable_ids(props: Vec<TileProperties>) -> Vec<u32> {
+    props
+        .into_iter()
+        .filter(|p| p.is_habitable)
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Size and hit count of the tile property cache, for diagnostics
+#[tauri::command]
+pub fn get_tile_cache_stats(state: State<AppState>) -> TileCacheStats {
+    cache_stats(&state)
+}
+
+fn cache_stats(state: &AppState) -> TileCacheStats {
+    let cache = state.tile_cache.lock().unwrap();
+    TileCacheStats {
+        entries: cache.len() as u32,
+        hits: cache.hits(),
+    }
+}
+
+/// Properties for each tile, reusing cached results for tiles already computed
+/// with the same seed and settings
+fn tile_properties(
+    cache: &mut TilePropertyCache,
+    tiles: &[TileCenter],
+    seed: u32,
+    thresholds: TerrainThresholds,
+) -> Vec<TileProperties> {
+    tiles
+        .iter()
+        .map(|tile| {
+            let round = |v: f64| (v * 1e6).round() as i64;
+            let key = (
+                [round(tile.x), round(tile.y), round(tile.z)],
+                [thresholds.land.to_bits(), thresholds.mountain.to_bits(), thresholds.hills.to_bits()],
+            );
+            let mut props = cache.get_or_compute(seed, key, || compute_tile_properties(tile, seed, thresholds));
+            props.id = tile.id;
+            props
+        })
+        .collect()
+}
+
+fn compute_tile_properties(tile: &TileCenter, seed: u32, thresholds: TerrainThresholds) -> TileProperties {
+    let Terrain { kind: terrain, elevation, continent_mask } =
+        calculate_terrain(tile.x, tile.y, tile.z, seed, thresholds);
+    let biome = calculate_biome(tile.x, tile.y, tile.z, &terrain, seed);
+    let fertility = calculate_fertility(tile.x, tile.y, tile.z, biome.as_deref(), &terrain, seed);
+    let is_habitable = check_habitable(&terrain, biome.as_deref());
+
+    TileProperties {
+        id: tile.id,
+        terrain_type: terrain,
+        biome,
+        fertility,
+        is_habitable,
+        elevation,
+        continent_mask,
+    }
+}
+
+// -- Terrain generation (ported from server/services/terrain/index.ts) --
+
+fn position_hash(x: f64, y: f64, z: f64, seed: f64) -> f64 {
+    let h1 = (x * 12.9898 + y * 78.233 + z * 45.164 + seed * 0.001).sin() * 43758.5453;
+    let h2 = (x * 39.346 + y * 11.135 + z * 83.155 + seed * 0.002).sin() * 93751.1459;
+    let combined = h1 + h2;
+    combined - combined.floor()
+}
+
+struct PositionRandom {
+    x: f64,
+    y: f64,
+    z: f64,
+    seed: f64,
+    sample: u32,
+}
+
+impl PositionRandom {
+    fn new(x: f64, y: f64, z: f64, seed: f64) -> Self {
+        Self { x, y, z, seed, sample: 0 }
+    }
+
+    fn next(&mut self) -> f64 {
+        self.sample += 1;
+        let s = self.sample as f64;
+        position_hash(
+            self.x + s * 0.137,
+            self.y + s * 0.293,
+            self.z + s * 0.419,
+            self.seed,
+        )
+    }
+}
+
+/// Spatially coherent noise: sine waves in random 3D directions (no axis bias).
+#[allow(dead_code)]
+fn spherical_noise(x: f64, y: f64, z: f64, seed: f64, octaves: &[(f64, f64)]) -> f64 {
+    let mut result = 0.0;
+    for (i, &(freq, amp)) in octaves.iter().enumerate() {
+        let idx = i as f64;
+        let theta = position_hash(seed, idx, 0.0, 0.0) * std::f64::consts::TAU;
+        let cos_phi = position_hash(seed, idx, 1.0, 0.0) * 2.0 - 1.0;
+        let sin_phi = (1.0 - cos_phi * cos_phi).sqrt();
+        let (dx, dy, dz) = (sin_phi * theta.cos(), sin_phi * theta.sin(), cos_phi);
+        let phase = position_hash(seed, idx, 2.0, 0.0) * std::f64::consts::TAU;
+        result += ((dx * x + dy * y + dz * z) * freq + phase).sin() * amp;
+    }
+    result
+}
+
+/// Terrain classification along with the raw values it was derived from
+struct Terrain {
+    kind: String,
+    /// Per-tile uniform hash, 0-1
+    elevation: f64,
+    /// Sum of continent waves, roughly -2.25 to 2.25
+    continent_mask: f64,
+}
+
+fn calculate_terrain(x: f64, y: f64, z: f64, seed: u32, thresholds: TerrainThresholds) -> Terrain {
+    let seed_f = seed as f64;
+    let p1 = position_hash(seed_f, seed_f * 0.7, seed_f * 0.3, 0.0) * std::f64::consts::TAU;
+    let p2 = position_hash(seed_f * 0.5, seed_f, seed_f * 0.9, 0.0) * std::f64::consts::TAU;
+    let p3 = position_hash(seed_f * 0.3, seed_f * 0.6, seed_f, 0.0) * std::f64::consts::TAU;
+    let p4 = position_hash(seed_f * 0.8, seed_f * 0.2, seed_f * 0.5, 0.0) * std::f64::consts::TAU;
+
+    let c1 = (x * 0.10 + p1).sin() * (z * 0.12 + p2).cos();
+    let c2 = (z * 0.14 + p3).sin() * (y * 0.10 + p1).cos() * 0.6;
+    let c3 = (y * 0.12 + p2).sin() * (x * 0.08 + p4).cos() * 0.4;
+    let c4 = (x * 0.22 + z * 0.18 + p4).sin() * 0.25;
+
+    let continent_mask = c1 + c2 + c3 + c4;
+
+    // Mountain/hill: per-tile uniform hash (0–1), independent of continent shape.
+    // Uniform distribution guarantees roughness=N → ~N% of land tiles become mountains.
+    let elevation = position_hash(x, y, z, seed_f + 500.0);
+
+    let kind = if continent_mask < thresholds.land {
+        "ocean"
+    } else if elevation > thresholds.mountain {
+        "mountains"
+    } else if elevation > thresholds.hills {
+        "hills"
+    } else {
+        "flats"
+    };
+
+    Terrain { kind: kind.into(), elevation, continent_mask }
+}
+
+fn calculate_biome(x: f64, y: f64, z: f64, terrain: &str, seed: u32) -> Option<String> {
+    if terrain == "ocean" {
+        return None;
+    }
+    if terrain == "mountains" {
+        return Some("alpine".into());
+    }
+
+    let radius = (x * x + y * y + z * z).sqrt();
+    let latitude = (y / radius).asin().to_degrees().abs();
+    let mut rng = PositionRandom::new(x, y, z, seed as f64);
+
+    if latitude > 60.0 {
+        Some("tundra".into())
+    } else if latitude > 45.0 {
+        Some(if rng.next() < 0.7 { "plains" } else { "tundra" }.into())
+    } else if latitude > 30.0 {
+        Some(if rng.next() < 0.6 { "grassland" } else { "plains" }.into())
+    } else if latitude > 15.0 {
+        Some(if rng.next() < 0.5 { "grassland" } else { "desert" }.into())
+    } else {
+        Some(if rng.next() < 0.7 { "grassland" } else { "desert" }.into())
+    }
+}
+
+fn calculate_fertility(x: f64, y: f64, z: f64, biome: Option<&str>, terrain: &str, seed: u32) -> u32 {
+    let biome = match biome {
+        Some(b) if terrain != "ocean" && terrain != "mountains" => b,
+        _ => return 0,
+    };
+
+    // Desert has 0 fertility (no variation)
+    if biome == "desert" {
+        return 0;
+    }
+
+    let base: i32 = match biome {
+        "grassland" => 80,
+        "plains" => 70,
+        "tundra" => 30,
+        "alpine" => 25,
+        _ => 50,
+    };
+
+    let mut rng = PositionRandom::new(x, y, z, (seed + 1000) as f64);
+    let variation = ((rng.next() - 0.5) * 20.0) as i32;
+    (base + variation).clamp(0, 100) as u32
+}
+
+#[allow(clippy::match_like_matches_macro)]
+fn check_habitable(terrain: &str, biome: Option<&str>) -> bool {
+    if terrain == "ocean" || terrain == "mountains" {
+        return false;
+    }
+    match biome {
+        Some("desert") | Some("tundra") | Some("alpine") => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balanced() -> TerrainThresholds {
+        TerrainConfig::default().resolve(50.0, 50.0)
+    }
+
+    fn sample_tiles() -> Vec<TileCenter> {
+        (0..40)
+            .map(|i| {
+                let angle = i as f64 * 0.4;
+                TileCenter { id: i, x: angle.cos() * 30.0, y: (i as f64 - 20.0) * 1.4, z: angle.sin() * 30.0 }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_habitable_ids_returned() {
+        // Points spread over a radius-50 sphere, so every terrain type turns up
+        let tiles: Vec<TileCenter> = (0..400)
+            .map(|i| {
+                let y = 1.0 - 2.0 * (i as f64 + 0.5) / 400.0;
+                let r = (1.0 - y * y).sqrt();
+                let theta = i as f64 * 2.399963;
+                TileCenter { id: i, x: r * theta.cos() * 50.0, y: y * 50.0, z: r * theta.sin() * 50.0 }
+            })
+            .collect();
+        let mut cache = TilePropertyCache::default();
+        let props = tile_properties(&mut cache, &tiles, 3, balanced());
+        for kind in ["ocean", "mountains"] {
+            assert!(props.iter().any(|p| p.terrain_type == kind), "no {} tiles", kind);
+        }
+        assert!(props.iter().any(|p| p.biome.as_deref() == Some("desert")));
+
+        let habitable = habitable_ids(props.clone());
+        assert!(!habitable.is_empty());
+        assert!(habitable.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(habitable.len(), props.iter().filter(|p| p.is_habitable).count());
+        for id in &habitable {
+            let p = &props[*id as usize];
+            assert!(p.terrain_type != "ocean" && p.terrain_type != "mountains");
+            assert!(!matches!(p.biome.as_deref(), Some("desert") | Some("tundra") | Some("alpine")));
+        }
+    }
+
+    #[test]
+    fn test_higher_land_threshold_means_more_ocean() {
+        let tiles = sample_tiles();
+        let oceans = |land_threshold: f64| {
+            let config = TerrainConfig { land_threshold: Some(land_threshold), ..TerrainConfig::default() };
+            let mut cache = TilePropertyCache::default();
+            tile_properties(&mut cache, &tiles, 7, config.resolve(50.0, 50.0))
+                .iter()
+                .filter(|p| p.terrain_type == "ocean")
+                .count()
+        };
+        assert!(oceans(1.0) > oceans(-1.0));
+        assert_eq!(oceans(3.0), tiles.len());
+
+        // Unset overrides reproduce the ratio-derived thresholds
+        assert_eq!(TerrainConfig::default().resolve(50.0, 50.0).land, 0.0);
+        assert_eq!(TerrainConfig::default().resolve(50.0, 2.0).mountain, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_ocean_tiles_are_below_land_threshold() {
+        let mut cache = TilePropertyCache::default();
+        let thresholds = balanced();
+        let props = tile_properties(&mut cache, &sample_tiles(), 7, thresholds);
+        assert!(props.iter().any(|p| p.terrain_type == "ocean"));
+        for p in &props {
+            assert_eq!(p.terrain_type == "ocean", p.continent_mask < thresholds.land);
+            assert!((0.0..1.0).contains(&p.elevation));
+        }
+    }
+
+    #[test]
+    fn test_repeated_call_hits_cache() {
+        let state = AppState::new(simulation::world::SimulationWorld::new(), 7);
+        let tiles = sample_tiles();
+
+        let first = properties_for(&state, &tiles, None, None, None);
+        assert_eq!(cache_stats(&state).hits, 0);
+        let second = properties_for(&state, &tiles, None, None, None);
+        assert_eq!(cache_stats(&state).hits, tiles.len() as u64);
+        assert_eq!(serde_json::to_string(&first).unwrap(), serde_json::to_string(&second).unwrap());
+
+        // A new seed starts over
+        *state.seed.lock().unwrap() = 8;
+        properties_for(&state, &tiles, None, None, None);
+        let stats = cache_stats(&state);
+        assert_eq!(stats.hits, tiles.len() as u64);
+        assert_eq!(stats.entries, tiles.len() as u32);
+    }
+}
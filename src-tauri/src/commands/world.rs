@@ -1,171 +1,487 @@
-use tauri::State;
-
-use crate::state::{AppState, CalendarDate, LoadResult, SaveResult, TickEvent, WorldConfig};
-
-#[tauri::command]
-pub fn tick(state: State<AppState>, count: Option<u32>) -> Result<TickEvent, String> {
-    let mut w = state.world.lock().unwrap();
-    let n = count.unwrap_or(1);
-
-    let mut total_births: u32 = 0;
-    let mut total_deaths: u32 = 0;
-    let mut total_marriages: u32 = 0;
-    let mut total_pregnancies: u32 = 0;
-    let mut total_dissolutions: u32 = 0;
-
-    for _ in 0..n {
-        let r = w.tick();
-        total_births += r.births;
-        total_deaths += r.deaths;
-        total_marriages += r.marriages;
-        total_pregnancies += r.pregnancies;
-        total_dissolutions += r.dissolutions;
-    }
-
-    Ok(TickEvent {
-        births: total_births,
-        deaths: total_deaths,
-        marriages: total_marriages,
-        pregnancies: total_pregnancies,
-        dissolutions: total_dissolutions,
-        population: w.entity_count() as u32,
-        year: w.calendar.year as i32,
-        month: w.calendar.month,
-        day: w.calendar.day as u32,
-    })
-}
-
-#[tauri::command]
-pub fn save_world(
-    state: State<AppState>,
-    file_path: String,
-    world_config: Option<WorldConfig>,
-) -> Result<SaveResult, String> {
-    let seed = *state.seed.lock().unwrap();
-    let w = state.world.lock().unwrap();
-
-    // Stop calendar before saving
-    {
-        let mut runner = state.calendar_runner.lock().unwrap();
-        if let Some(mut r) = runner.take() {
-            r.stop();
-        }
-    }
-
-    let config_json = match &world_config {
-        Some(cfg) => serde_json::to_string(cfg).unwrap_or_else(|_| "{}".into()),
-        None => "{}".into(),
-    };
-
-    let stats = w
-        .save_to_file(&config_json, seed, &file_path)
-        .map_err(|e| e.to_string())?;
-
-    Ok(SaveResult {
-        population: stats.population,
-        file_bytes: stats.file_bytes as i64,
-    })
-}
-
-#[tauri::command]
-pub fn load_world(state: State<AppState>, file_path: String) -> Result<LoadResult, String> {
-    // Stop calendar before loading
-    {
-        let mut runner = state.calendar_runner.lock().unwrap();
-        if let Some(mut r) = runner.take() {
-            r.stop();
-        }
-    }
-
-    let mut w = state.world.lock().unwrap();
-    let result = w.load_from_file(&file_path).map_err(|e| e.to_string())?;
-
-    // Update seed from loaded data
-    *state.seed.lock().unwrap() = result.seed;
-
-    // Parse world config from node_state JSON
-    let world_config: WorldConfig =
-        serde_json::from_str(&result.node_state_json).unwrap_or_default();
-
-    Ok(LoadResult {
-        population: result.import_result.population,
-        partners: result.import_result.partners,
-        calendar_year: result.import_result.calendar_year as i32,
-        seed: result.seed,
-        world_config,
-    })
-}
-
-#[tauri::command]
-pub fn check_save_exists(file_path: String) -> bool {
-    std::path::Path::new(&file_path).exists()
-}
-
-#[tauri::command]
-pub fn restart_world(
-    state: State<AppState>,
-    habitable_tile_ids: Vec<u32>,
-    new_seed: Option<u32>,
-    tile_percent: Option<u32>,
-    pop_min: Option<usize>,
-    pop_max: Option<usize>,
-) -> Result<RestartResult, String> {
-    // Stop calendar
-    {
-        let mut runner = state.calendar_runner.lock().unwrap();
-        if let Some(mut r) = runner.take() {
-            r.stop();
-        }
-    }
-
-    // Generate or use provided seed
-    let seed = new_seed.unwrap_or_else(|| rand::random::<u32>());
-    *state.seed.lock().unwrap() = seed;
-
-    // Reset simulation
-    let mut w = state.world.lock().unwrap();
-    *w = simulation::world::SimulationWorld::new();
-
-    // Determine how many tiles to seed based on tile_percent
-    let pct = tile_percent.unwrap_or(40).clamp(1, 100) as usize;
-    let tiles_to_seed = (habitable_tile_ids.len() * pct + 99) / 100; // ceil division
-    let min = pop_min.unwrap_or(5);
-    let max = pop_max.unwrap_or(15);
-
-    // Seed population on selected tiles (deterministic subset using seed)
-    let mut total_population: u32 = 0;
-    let mut indices: Vec<usize> = (0..habitable_tile_ids.len()).collect();
-
-    // Fisher-Yates shuffle using seed for determinism
-    let mut hash_seed = seed as u64;
-    for i in (1..indices.len()).rev() {
-        hash_seed = hash_seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-        let j = (hash_seed >> 33) as usize % (i + 1);
-        indices.swap(i, j);
-    }
-
-    for &idx in indices.iter().take(tiles_to_seed) {
-        let tile_id = habitable_tile_ids[idx];
-        let count = w.seed_population_on_tile_range(min, max, tile_id as u16);
-        total_population += count as u32;
-    }
-
-    Ok(RestartResult {
-        seed,
-        population: total_population,
-        tiles: tiles_to_seed as u32,
-        calendar: CalendarDate {
-            year: w.calendar.year as i32,
-            month: w.calendar.month,
-            day: w.calendar.day as u32,
-        },
-    })
-}
-
-#[derive(serde::Serialize, Clone)]
-pub struct RestartResult {
-    pub seed: u32,
-    pub population: u32,
-    pub tiles: u32,
-    pub calendar: CalendarDate,
-}
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use rand::seq::SliceRandom;
+use tauri::{AppHandle, Emitter, State};
+
+use simulation::systems::RngAudit;
+use simulation::world::{SimulationWorld, TickProgress, YearSummary};
+use simulation::{Calendar, Compatibility, DeathCause, ExportData, LoadFileResult, SaveFormat, SaveHeader, SaveSlot, WorldDiff};
+
+use crate::state::{capped_ids, checked_tile_id, AppState, CalendarDate, LoadResult, SaveResult, TickEvent, TickProgressEvent, WorldConfig, MAX_TICK_IDS};
+
+/// Async so long runs execute off the main thread, leaving `request_cancel` responsive.
+/// The loop runs on a blocking thread, not the async runtime, since it holds the world lock.
+/// With `progress_every`, emits "tick-progress" after every that many ticks.
+#[tauri::command]
+pub async fn tick(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    count: Option<u32>,
+    progress_every: Option<u32>,
+) -> Result<TickEvent, String> {
+    let world = state.world();
+    let cancel = state.tick_cancel.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        run_ticks(&app, &world, &cancel, count.unwrap_or(1), progress_every.unwrap_or(0))
+    })
+    .await
+    .map_err(|e| format!("Tick task failed: {}", e))
+}
+
+fn run_ticks(app: &AppHandle, world: &RwLock<SimulationWorld>, cancel: &AtomicBool, n: u32, progress_every: u32) -> TickEvent {
+    let mut w = world.write().unwrap();
+    // Cleared only once the lock is held, so a cancel aimed at the run holding it isn't lost
+    cancel.store(false, Ordering::Relaxed);
+
+    let mut total_births: u32 = 0;
+    let mut total_deaths: u32 = 0;
+    let mut total_marriages: u32 = 0;
+    let mut total_pregnancies: u32 = 0;
+    let mut total_dissolutions: u32 = 0;
+    let mut births_by_tile: HashMap<u16, u32> = HashMap::new();
+    let mut deaths_by_tile: HashMap<u16, u32> = HashMap::new();
+    let mut deaths_by_cause: HashMap<DeathCause, u32> = HashMap::new();
+    let mut birth_ids: Vec<u64> = Vec::new();
+    let mut death_ids: Vec<u64> = Vec::new();
+
+    let on_progress = |p: TickProgress| {
+        let _ = app.emit("tick-progress", &TickProgressEvent {
+            ticks: p.ticks,
+            total: p.total,
+            year: p.calendar.year as i32,
+            month: p.calendar.month,
+            day: p.calendar.day as u32,
+        });
+    };
+    let ticks = w.tick_many_with_progress(n, cancel, progress_every, |r| {
+        total_births += r.births;
+        total_deaths += r.deaths;
+        total_marriages += r.marriages;
+        total_pregnancies += r.pregnancies;
+        total_dissolutions += r.dissolutions;
+        for (tile, births) in &r.births_by_tile {
+            *births_by_tile.entry(*tile).or_insert(0) += births;
+        }
+        for (tile, deaths) in &r.deaths_by_tile {
+            *deaths_by_tile.entry(*tile).or_insert(0) += deaths;
+        }
+        for (cause, deaths) in &r.deaths_by_cause {
+            *deaths_by_cause.entry(*cause).or_insert(0) += deaths;
+        }
+        // One past the cap is enough to know the list was cut
+        birth_ids.extend(r.birth_ids.iter().take((MAX_TICK_IDS + 1).saturating_sub(birth_ids.len())));
+        death_ids.extend(r.death_ids.iter().take((MAX_TICK_IDS + 1).saturating_sub(death_ids.len())));
+    }, on_progress);
+    let (birth_ids, births_cut) = capped_ids(&birth_ids);
+    let (death_ids, deaths_cut) = capped_ids(&death_ids);
+
+    TickEvent {
+        births: total_births,
+        deaths: total_deaths,
+        marriages: total_marriages,
+        pregnancies: total_pregnancies,
+        dissolutions: total_dissolutions,
+        population: w.entity_count() as u32,
+        year: w.calendar.year as i32,
+        month: w.calendar.month,
+        day: w.calendar.day as u32,
+        ticks,
+        tiles: crate::state::tile_tick_data(&births_by_tile, &deaths_by_tile),
+        birth_ids,
+        death_ids,
+        ids_truncated: births_cut || deaths_cut,
+        deaths_by_cause,
+    }
+}
+
+/// What the next tick would report, computed on a scratch copy (indicative only)
+#[tauri::command]
+pub fn preview_tick(state: State<AppState>) -> Result<TickEvent, String> {
+    let world = state.world();
+    let w = world.read().unwrap();
+    let result = w.simulate_tick_preview()?;
+    let mut date = w.calendar.clone();
+    date.advance_days(1);
+    Ok(TickEvent {
+        births: result.births,
+        deaths: result.deaths,
+        marriages: result.marriages,
+        pregnancies: result.pregnancies,
+        dissolutions: result.dissolutions,
+        population: result.population,
+        year: date.year as i32,
+        month: date.month,
+        day: date.day as u32,
+        ticks: 1,
+        tiles: crate::state::tile_tick_data(&result.births_by_tile, &result.deaths_by_tile),
+        // Ids from a scratch run don't name anyone real
+        birth_ids: Vec::new(),
+        death_ids: Vec::new(),
+        ids_truncated: false,
+        deaths_by_cause: result.deaths_by_cause,
+    })
+}
+
+/// Run until the first day of `target_year`, returning per-year totals.
+/// Async and cancellable with `request_cancel`, like `tick`.
+#[tauri::command]
+pub async fn tick_until_year(state: State<'_, AppState>, target_year: u16) -> Result<Vec<YearSummary>, String> {
+    let world = state.world();
+    let cancel = state.tick_cancel.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut w = world.write().unwrap();
+        cancel.store(false, Ordering::Relaxed);
+        w.tick_until_year(target_year, &cancel)
+    })
+    .await
+    .map_err(|e| format!("Tick task failed: {}", e))?
+}
+
+/// Stop an in-progress multi-tick `tick` or `tick_until_year` call after its current tick
+#[tauri::command]
+pub fn request_cancel(state: State<AppState>) {
+    state.tick_cancel.store(true, Ordering::Relaxed);
+}
+
+#[tauri::command]
+pub fn set_track_tile_events(state: State<AppState>, enabled: bool) {
+    state.world().write().unwrap().track_tile_events = enabled;
+}
+
+/// Count RNG draws per system each tick, for comparing diverging runs
+#[tauri::command]
+pub fn set_rng_audit(state: State<AppState>, enabled: bool) {
+    state.world().write().unwrap().rng_audit = enabled;
+}
+
+#[tauri::command]
+pub fn get_last_rng_audit(state: State<AppState>) -> Option<RngAudit> {
+    state.world().read().unwrap().last_tick_rng_audit()
+}
+
+#[tauri::command]
+pub fn export_world(state: State<AppState>, pretty: Option<bool>) -> String {
+    // Serialize outside the lock so long exports don't stall the runner
+    let snapshot = state.world().read().unwrap().snapshot();
+    if pretty.unwrap_or(false) {
+        snapshot.to_json_pretty()
+    } else {
+        snapshot.to_json()
+    }
+}
+
+#[tauri::command]
+pub fn export_region(state: State<AppState>, tiles: Vec<u32>) -> Result<String, String> {
+    let tiles: Vec<u16> = tiles.into_iter().map(checked_tile_id).collect::<Result<_, _>>()?;
+    Ok(state.world().read().unwrap().export_region(&tiles))
+}
+
+/// Compare two JSON exports (older first)
+#[tauri::command]
+pub fn diff_saves(json_a: String, json_b: String) -> Result<WorldDiff, String> {
+    let parse = |json: &str| {
+        serde_json::from_str::<ExportData>(json).map_err(|e| format!("JSON parse error: {}", e))
+    };
+    Ok(parse(&json_a)?.diff(&parse(&json_b)?))
+}
+
+#[tauri::command]
+pub fn save_world(
+    state: State<AppState>,
+    file_path: String,
+    world_config: Option<WorldConfig>,
+    format: Option<SaveFormat>,
+) -> Result<SaveResult, String> {
+    let seed = *state.seed.lock().unwrap();
+
+    // Stop calendar before saving
+    let runner = state.calendar_runner.lock().unwrap().take();
+    if let Some(mut r) = runner {
+        r.stop();
+    }
+
+    // Only hold the world lock while copying its state; encoding and disk I/O happen after
+    let snapshot = state.world().read().unwrap().snapshot();
+
+    let config_json = match &world_config {
+        Some(cfg) => serde_json::to_string(cfg).unwrap_or_else(|_| "{}".into()),
+        None => "{}".into(),
+    };
+
+    let stats = snapshot
+        .save_to_file_as(&config_json, seed, &file_path, format.unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+
+    Ok(SaveResult {
+        population: stats.population,
+        file_bytes: stats.file_bytes as i64,
+    })
+}
+
+/// Parse the world config stored in a save's Node state. Lenient parsing falls back
+/// to defaults on malformed JSON; strict parsing reports why it failed.
+fn parse_world_config(node_state_json: &str, strict: bool) -> Result<WorldConfig, String> {
+    match serde_json::from_str(node_state_json) {
+        Ok(config) => Ok(config),
+        Err(e) if strict => Err(format!("Invalid world config: {}", e)),
+        Err(_) => Ok(WorldConfig::default()),
+    }
+}
+
+/// Load a save into `world` and parse its world config. With `strict`, a malformed
+/// config fails the load before anything is imported.
+fn load_with_config(world: &mut SimulationWorld, file_path: &str, strict: bool) -> Result<(LoadFileResult, WorldConfig), String> {
+    let result = world
+        .try_load_from_file_checked(file_path, |json| parse_world_config(json, strict).map(|_| ()))
+        .map_err(|e| e.to_string())?;
+    let world_config = parse_world_config(&result.node_state_json, strict)?;
+    Ok((result, world_config))
+}
+
+/// Load `file_path` into a fresh world and swap it in whole, so a tick still running
+/// on the old world can't land on the new one. Map adjacency and the tracking switches
+/// carry over from the old world; on failure the old world stays current.
+fn load_replacing(state: &AppState, file_path: &str, strict: bool) -> Result<(LoadFileResult, WorldConfig), String> {
+    let mut world = SimulationWorld::new();
+    {
+        let current = state.world();
+        let current = current.read().unwrap();
+        world.tile_neighbors = current.tile_neighbors.clone();
+        world.track_tile_events = current.track_tile_events;
+        world.rng_audit = current.rng_audit;
+        world.retention_years = current.retention_years;
+    }
+    let loaded = load_with_config(&mut world, file_path, strict)?;
+    state.replace_world(world);
+    Ok(loaded)
+}
+
+/// `strict` (default false) rejects saves whose world config doesn't parse
+/// instead of loading them with default settings.
+#[tauri::command]
+pub fn load_world(state: State<AppState>, file_path: String, strict: Option<bool>) -> Result<LoadResult, String> {
+    // Stop calendar before loading
+    let runner = state.calendar_runner.lock().unwrap().take();
+    if let Some(mut r) = runner {
+        r.stop();
+    }
+
+    let (result, world_config) = load_replacing(&state, &file_path, strict.unwrap_or(false))?;
+
+    // Update seed from loaded data
+    *state.seed.lock().unwrap() = result.seed;
+    state.tile_cache.lock().unwrap().clear();
+
+    Ok(LoadResult {
+        population: result.import_result.population,
+        partners: result.import_result.partners,
+        calendar_year: result.import_result.calendar_year as i32,
+        seed: result.seed,
+        world_config,
+    })
+}
+
+#[tauri::command]
+pub fn check_save_exists(file_path: String) -> bool {
+    std::path::Path::new(&file_path).exists()
+}
+
+#[tauri::command]
+pub fn read_save_header(file_path: String) -> Result<SaveHeader, String> {
+    simulation::read_save_header(&file_path)
+}
+
+/// Whether this build can load a save, checked from its header before loading
+#[tauri::command]
+pub fn get_save_compatibility(file_path: String) -> Result<Compatibility, String> {
+    simulation::save_compatibility(&file_path)
+}
+
+#[tauri::command]
+pub fn list_saves(dir: String) -> Vec<SaveSlot> {
+    simulation::list_saves(&dir)
+}
+
+#[tauri::command]
+pub fn restart_world(
+    state: State<AppState>,
+    habitable_tile_ids: Vec<u32>,
+    new_seed: Option<u32>,
+    tile_percent: Option<u32>,
+    pop_min: Option<usize>,
+    pop_max: Option<usize>,
+    start_year: Option<u16>,
+) -> Result<RestartResult, String> {
+    // Refuse out-of-range tiles before anything is torn down
+    let habitable_tile_ids = habitable_tile_ids
+        .into_iter()
+        .map(checked_tile_id)
+        .collect::<Result<Vec<u16>, String>>()?;
+
+    // Stop calendar
+    let runner = state.calendar_runner.lock().unwrap().take();
+    if let Some(mut r) = runner {
+        r.stop();
+    }
+
+    // Generate or use provided seed
+    let seed = new_seed.unwrap_or_else(rand::random::<u32>);
+    *state.seed.lock().unwrap() = seed;
+    state.tile_cache.lock().unwrap().clear();
+
+    // Build the new world aside and swap it in, like `load_world`; the world RNG seeded
+    // here drives tile selection, seeding and ticking
+    let start = start_year.map_or_else(Calendar::default, |year| Calendar::new(year, 1, 1));
+    let mut w = SimulationWorld::with_start(seed as u64, start);
+
+    // Determine how many tiles to seed based on tile_percent
+    let pct = tile_percent.unwrap_or(40).clamp(1, 100) as usize;
+    let tiles_to_seed = (habitable_tile_ids.len() * pct).div_ceil(100);
+    let min = pop_min.unwrap_or(5);
+    let max = pop_max.unwrap_or(15);
+
+    let (_, total_population) = seed_random_tiles(&mut w, &habitable_tile_ids, tiles_to_seed, min, max);
+    w.mark_genesis();
+    let calendar = CalendarDate {
+        year: w.calendar.year as i32,
+        month: w.calendar.month,
+        day: w.calendar.day as u32,
+    };
+    state.replace_world(w);
+
+    Ok(RestartResult {
+        seed,
+        population: total_population,
+        tiles: tiles_to_seed as u32,
+        calendar,
+    })
+}
+
+/// Export and re-import the world into a scratch copy, reporting any aggregate that changed
+#[tauri::command]
+pub fn verify_roundtrip(state: State<AppState>) -> Result<(), String> {
+    state.world().read().unwrap().assert_roundtrip()
+}
+
+/// Replace the simulation RNG stream mid-run, to branch a loaded world into a
+/// different future. The terrain seed is left alone.
+#[tauri::command]
+pub fn reseed(state: State<AppState>, seed: u32) {
+    state.world().write().unwrap().reseed(seed as u64);
+}
+
+/// Seed `min..=max` founders on each of `tiles_to_seed` tiles picked from
+/// `habitable_tile_ids`, all drawn from the world RNG so a seed reproduces both the
+/// selection and the founders. Returns the tiles seeded, in pick order, and the population.
+fn seed_random_tiles(
+    w: &mut SimulationWorld,
+    habitable_tile_ids: &[u16],
+    tiles_to_seed: usize,
+    min: usize,
+    max: usize,
+) -> (Vec<u16>, u32) {
+    let mut indices: Vec<usize> = (0..habitable_tile_ids.len()).collect();
+    indices.shuffle(&mut w.rng);
+
+    let mut total_population: u32 = 0;
+    let tiles: Vec<u16> = indices.iter().take(tiles_to_seed).map(|&idx| habitable_tile_ids[idx]).collect();
+    for &tile_id in &tiles {
+        let count = w.seed_population_on_tile_range(min, max, tile_id);
+        total_population += count as u32;
+    }
+    (tiles, total_population)
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct RestartResult {
+    pub seed: u32,
+    pub population: u32,
+    pub tiles: u32,
+    pub calendar: CalendarDate,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_node_state_strict_vs_lenient() {
+        let dir = std::env::temp_dir().join(format!("gridworld_node_state_{}", std::process::id()));
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+
+        let mut saved = SimulationWorld::with_seed(3);
+        saved.seed_population_on_tile(25, 1);
+        saved.save_to_file("{\"name\": \"Atlantis\", \"roughness\": ", 9, &path).unwrap();
+
+        let mut world = SimulationWorld::with_seed(4);
+        world.seed_population_on_tile(10, 2);
+        let err = load_with_config(&mut world, &path, true).err().unwrap();
+        assert!(err.contains("Invalid world config"), "{}", err);
+        assert_eq!(world.entity_count(), 10);
+
+        let (result, config) = load_with_config(&mut world, &path, false).unwrap();
+        assert_eq!(result.import_result.population, 25);
+        assert_eq!(config.name, "");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tile_selection_follows_seed() {
+        let habitable: Vec<u16> = (100..300).collect();
+        let restart = |seed: u64| {
+            let mut w = SimulationWorld::with_seed(seed);
+            let (tiles, population) = seed_random_tiles(&mut w, &habitable, 80, 5, 15);
+            (tiles, population, w.population_by_tile(), w.tick().births)
+        };
+
+        let first = restart(2024);
+        assert_eq!(first, restart(2024));
+        assert_eq!(first.0.len(), 80);
+        assert_eq!(first.2.len(), 80);
+        assert_ne!(first.0, restart(2025).0);
+    }
+
+    #[test]
+    fn test_reloading_while_ticking() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        let dir = std::env::temp_dir().join(format!("gridworld_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("world.bin").to_string_lossy().into_owned();
+        let mut saved = SimulationWorld::with_seed(5);
+        saved.seed_population_on_tile(200, 1);
+        saved.save_to_file("{}", 9, &path).unwrap();
+        let saved_day = saved.calendar.to_absolute_days();
+
+        let state = Arc::new(AppState::new(SimulationWorld::with_seed(6), 0));
+        state.world().write().unwrap().seed_population_on_tile(50, 2);
+        let stop = Arc::new(AtomicBool::new(false));
+        let ticker = {
+            let (state, stop) = (Arc::clone(&state), Arc::clone(&stop));
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let result = state.world().write().unwrap().tick();
+                    assert!(result.population > 0);
+                }
+            })
+        };
+
+        for _ in 0..25 {
+            let (result, _) = load_replacing(&state, &path, false).unwrap();
+            assert_eq!(result.import_result.population, 200);
+            // Only ticks that started on the new world move it forward
+            let world = state.world();
+            let w = world.read().unwrap();
+            assert!(w.calendar.to_absolute_days() >= saved_day);
+            assert!(w.population_by_tile().keys().all(|tile| *tile == 1));
+        }
+        stop.store(true, Ordering::Relaxed);
+        ticker.join().unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
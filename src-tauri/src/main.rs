@@ -1,59 +1,117 @@
-// Prevents additional console window on Windows in release
-#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-
-mod commands;
-mod state;
-
-use state::AppState;
-use std::sync::{Arc, Mutex};
-
-fn main() {
-    let world = Arc::new(Mutex::new(simulation::world::SimulationWorld::new()));
-
-    let app_state = AppState {
-        world,
-        calendar_runner: Mutex::new(None),
-        seed: Mutex::new(12345),
-    };
-
-    tauri::Builder::default()
-        .manage(app_state)
-        .invoke_handler(tauri::generate_handler![
-            // Config
-            commands::config::get_config,
-            // Calendar
-            commands::calendar::get_calendar_state,
-            commands::calendar::get_calendar_speeds,
-            commands::calendar::start_calendar,
-            commands::calendar::stop_calendar,
-            commands::calendar::set_calendar_speed,
-            // World
-            commands::world::tick,
-            commands::world::save_world,
-            commands::world::load_world,
-            commands::world::restart_world,
-            commands::world::check_save_exists,
-            // Population
-            commands::population::get_population,
-            commands::population::get_demographics,
-            commands::population::get_population_by_tile,
-            commands::population::get_tile_population,
-            // People
-            commands::people::get_all_people,
-            commands::people::get_person,
-            commands::people::get_people_by_tile,
-            // Statistics
-            commands::statistics::get_vital_statistics,
-            commands::statistics::get_current_year_statistics,
-            commands::statistics::get_recent_statistics,
-            commands::statistics::get_recent_events,
-            commands::statistics::get_event_count,
-            // Tiles
-            commands::tiles::calculate_tile_properties,
-            // Memory & App
-            commands::memory::get_memory_usage,
-            commands::memory::exit_app,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+// Prevents additional console window on Windows in release
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+mod commands;
+mod state;
+
+use state::AppState;
+
+fn main() {
+    let app_state = AppState::new(simulation::world::SimulationWorld::new(), 12345);
+
+    tauri::Builder::default()
+        .manage(app_state)
+        .invoke_handler(tauri::generate_handler![
+            // Config
+            commands::config::get_config,
+            commands::config::get_sim_config,
+            commands::config::set_sim_config,
+            commands::config::get_marriage_config,
+            commands::config::set_marriage_config,
+            commands::config::set_smooth_mortality,
+            commands::config::set_max_population,
+            commands::config::get_mortality_config,
+            commands::config::set_mortality_config,
+            commands::config::get_home_leaving_config,
+            commands::config::set_home_leaving_config,
+            // Calendar
+            commands::calendar::get_calendar_state,
+            commands::calendar::get_current_day,
+            commands::calendar::get_ticks_elapsed,
+            commands::calendar::calendar_from_day,
+            commands::calendar::get_calendar_speeds,
+            commands::calendar::start_calendar,
+            commands::calendar::stop_calendar,
+            commands::calendar::set_calendar_speed,
+            // World
+            commands::world::tick,
+            commands::world::tick_until_year,
+            commands::world::preview_tick,
+            commands::world::request_cancel,
+            commands::world::set_track_tile_events,
+            commands::world::set_rng_audit,
+            commands::world::get_last_rng_audit,
+            commands::world::export_world,
+            commands::world::export_region,
+            commands::world::diff_saves,
+            commands::world::save_world,
+            commands::world::load_world,
+            commands::world::restart_world,
+            commands::world::reseed,
+            commands::world::verify_roundtrip,
+            commands::world::check_save_exists,
+            commands::world::read_save_header,
+            commands::world::get_save_compatibility,
+            commands::world::list_saves,
+            // Population
+            commands::population::get_population,
+            commands::population::get_demographics,
+            commands::population::get_tile_demographics,
+            commands::population::get_demographics_custom,
+            commands::population::get_population_by_tile,
+            commands::population::get_tile_population,
+            commands::population::get_sex_ratio_by_tile,
+            commands::population::get_tile_population_distribution,
+            commands::population::get_largest_tiles,
+            commands::population::set_tile_adjacency,
+            commands::population::get_tile_pressures,
+            commands::population::get_workforce_by_tile,
+            commands::population::get_parity_distribution,
+            commands::population::get_completed_family_sizes,
+            commands::population::get_mean_completed_family_size,
+            commands::population::get_eligible_singles,
+            commands::population::seed_population_with_profile,
+            commands::population::seed_relationships,
+            commands::population::seed_from_records,
+            commands::population::immigrate,
+            commands::population::emigrate,
+            commands::population::remap_tiles,
+            // People
+            commands::people::get_all_people,
+            commands::people::get_people_page,
+            commands::people::get_person,
+            commands::people::get_people_by_ids,
+            commands::people::get_people_by_tile,
+            commands::people::get_descendant_count,
+            commands::people::get_living_lineage,
+            commands::people::get_fertility_status,
+            commands::people::get_generation,
+            commands::people::get_max_generation,
+            commands::people::get_marriage_duration,
+            commands::people::apply_person_patches,
+            // Statistics
+            commands::statistics::get_vital_statistics,
+            commands::statistics::get_current_year_statistics,
+            commands::statistics::get_recent_statistics,
+            commands::statistics::get_growth_rate,
+            commands::statistics::get_recent_events,
+            commands::statistics::get_population_series,
+            commands::statistics::log_custom_event,
+            commands::statistics::get_events_on_tile,
+            commands::statistics::get_event_count,
+            commands::statistics::clear_event_log,
+            commands::statistics::prune_events_before,
+            commands::statistics::set_event_retention,
+            // Tiles
+            commands::tiles::calculate_tile_properties,
+            commands::tiles::get_habitable_tiles,
+            commands::tiles::get_tile_cache_stats,
+            // Memory & App
+            commands::memory::get_memory_usage,
+            commands::memory::get_memory_report,
+            commands::memory::compact_world,
+            commands::memory::exit_app,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
@@ -1,165 +1,406 @@
-use serde::{Deserialize, Serialize};
-use simulation::calendar_runner::CalendarRunner;
-use simulation::world::SimulationWorld;
-use std::sync::{Arc, Mutex};
-
-/// Global application state managed by Tauri
-pub struct AppState {
-    pub world: Arc<Mutex<SimulationWorld>>,
-    pub calendar_runner: Mutex<Option<CalendarRunner>>,
-    pub seed: Mutex<u32>,
-}
-
-// -- Serializable types returned by commands --
-
-#[derive(Serialize, Clone)]
-pub struct HexasphereConfig {
-    pub radius: f64,
-    pub subdivisions: u32,
-    pub tile_width_ratio: f64,
-}
-
-#[derive(Serialize, Clone)]
-pub struct CalendarConfig {
-    pub days_per_month: u8,
-    pub months_per_year: u8,
-    pub start_year: u16,
-}
-
-#[derive(Serialize, Clone)]
-pub struct AppConfig {
-    pub hexasphere: HexasphereConfig,
-    pub calendar: CalendarConfig,
-    pub seed: u32,
-}
-
-#[derive(Serialize, Clone)]
-pub struct CalendarDate {
-    pub year: i32,
-    pub month: u8,
-    pub day: u32,
-}
-
-#[derive(Serialize, Clone)]
-pub struct CalendarState {
-    pub date: CalendarDate,
-    pub is_paused: bool,
-    pub current_speed: String,
-}
-
-#[derive(Serialize, Clone)]
-pub struct TickEvent {
-    pub births: u32,
-    pub deaths: u32,
-    pub marriages: u32,
-    pub pregnancies: u32,
-    pub dissolutions: u32,
-    pub population: u32,
-    pub year: i32,
-    pub month: u8,
-    pub day: u32,
-}
-
-#[derive(Serialize, Clone)]
-pub struct SpeedMode {
-    pub key: String,
-    pub name: String,
-    pub interval_ms: u64,
-}
-
-#[derive(Serialize, Clone)]
-pub struct PersonData {
-    pub id: i64,
-    pub first_name: String,
-    pub last_name: String,
-    pub tile_id: i32,
-    pub sex: bool,
-    pub birth_year: i32,
-    pub birth_month: i32,
-    pub birth_day: i32,
-    pub age_years: i32,
-    pub is_partnered: bool,
-    pub is_pregnant: bool,
-    pub partner_id: Option<i64>,
-}
-
-#[derive(Serialize, Clone)]
-pub struct EventData {
-    pub event_type: String,
-    pub year: i32,
-    pub month: u8,
-    pub day: u8,
-    pub person_id: Option<i64>,
-}
-
-#[derive(Serialize, Clone)]
-pub struct TilePopulationData {
-    pub tile_id: u32,
-    pub count: u32,
-}
-
-#[derive(Serialize, Deserialize, Clone, Default)]
-pub struct WorldConfig {
-    #[serde(default)]
-    pub name: String,
-    #[serde(default = "default_subdivisions")]
-    pub subdivisions: u32,
-    #[serde(default = "default_land_water_ratio")]
-    pub land_water_ratio: f64,
-    #[serde(default = "default_roughness")]
-    pub roughness: f64,
-    #[serde(default = "default_precipitation")]
-    pub precipitation: f64,
-}
-
-fn default_subdivisions() -> u32 { 12 }
-fn default_land_water_ratio() -> f64 { 35.0 }
-fn default_roughness() -> f64 { 25.0 }
-fn default_precipitation() -> f64 { 75.0 }
-
-#[derive(Serialize, Clone)]
-pub struct SaveResult {
-    pub population: u32,
-    pub file_bytes: i64,
-}
-
-#[derive(Serialize, Clone)]
-pub struct LoadResult {
-    pub population: u32,
-    pub partners: u32,
-    pub calendar_year: i32,
-    pub seed: u32,
-    pub world_config: WorldConfig,
-}
-
-#[derive(Serialize, Clone)]
-pub struct TileProperties {
-    pub id: u32,
-    pub terrain_type: String,
-    pub biome: Option<String>,
-    pub fertility: u32,
-    pub is_habitable: bool,
-}
-
-/// Default speeds available for the calendar
-pub fn default_speeds() -> Vec<SpeedMode> {
-    vec![
-        SpeedMode {
-            key: "1_day".into(),
-            name: "1 Day".into(),
-            interval_ms: 1000,
-        },
-        SpeedMode {
-            key: "1_month".into(),
-            name: "1 Month".into(),
-            interval_ms: 125,
-        },
-    ]
-}
-
-pub fn speed_interval(speed: &str) -> u64 {
-    match speed {
-        "1_month" => 125,
-        _ => 1000, // default to 1_day
-    }
-}
+use serde::{Deserialize, Serialize};
+use simulation::calendar_runner::CalendarRunner;
+use simulation::world::SimulationWorld;
+use simulation::DeathCause;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Global application state managed by Tauri
+pub struct AppState {
+    /// The current world; reach it through `world()`. Queries take its read lock so
+    /// they don't block each other; ticks and other mutations take the write lock
+    pub world_slot: RwLock<Arc<RwLock<SimulationWorld>>>,
+    pub calendar_runner: Mutex<Option<CalendarRunner>>,
+    pub seed: Mutex<u32>,
+    /// Set by `request_cancel` to stop a running multi-tick `tick` call
+    pub tick_cancel: Arc<AtomicBool>,
+    /// Tile properties already computed for the current seed
+    pub tile_cache: Mutex<TilePropertyCache>,
+}
+
+impl AppState {
+    pub fn new(world: SimulationWorld, seed: u32) -> Self {
+        Self {
+            world_slot: RwLock::new(Arc::new(RwLock::new(world))),
+            calendar_runner: Mutex::new(None),
+            seed: Mutex::new(seed),
+            tick_cancel: Arc::new(AtomicBool::new(false)),
+            tile_cache: Mutex::new(Default::default()),
+        }
+    }
+
+    /// The current world. Hold it for one operation only: after `replace_world`
+    /// it is the discarded world.
+    pub fn world(&self) -> Arc<RwLock<SimulationWorld>> {
+        Arc::clone(&self.world_slot.read().unwrap())
+    }
+
+    /// Swap in `world` as the current world. Whoever still holds the previous one
+    /// (a tick already under way) finishes on it, and that work is discarded.
+    pub fn replace_world(&self, world: SimulationWorld) {
+        *self.world_slot.write().unwrap() = Arc::new(RwLock::new(world));
+    }
+}
+
+/// Identifies one tile's generation inputs: its center rounded to 1e-6, and the
+/// bit patterns of the land, mountain and hills thresholds
+pub type TileCacheKey = ([i64; 3], [u64; 3]);
+
+/// Memoized `calculate_tile_properties` results. Everything is dropped when asked
+/// for a different seed, or explicitly via `clear` when a world is restarted or loaded.
+#[derive(Default)]
+pub struct TilePropertyCache {
+    seed: Option<u32>,
+    entries: HashMap<TileCacheKey, TileProperties>,
+    hits: u64,
+}
+
+impl TilePropertyCache {
+    /// Cached properties for `key` under `seed`, computing and storing them on a miss
+    pub fn get_or_compute<F: FnOnce() -> TileProperties>(
+        &mut self,
+        seed: u32,
+        key: TileCacheKey,
+        compute: F,
+    ) -> TileProperties {
+        if self.seed != Some(seed) {
+            self.clear();
+            self.seed = Some(seed);
+        }
+        if let Some(props) = self.entries.get(&key) {
+            self.hits += 1;
+            return props.clone();
+        }
+        let props = compute();
+        self.entries.insert(key, props.clone());
+        props
+    }
+
+    pub fn clear(&mut self) {
+        self.seed = None;
+        self.entries.clear();
+    }
+
+    /// Lookups answered from the cache so far
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of tiles currently cached
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct TileCacheStats {
+    pub entries: u32,
+    pub hits: u64,
+}
+
+// -- Serializable types returned by commands --
+
+#[derive(Serialize, Clone)]
+pub struct HexasphereConfig {
+    pub radius: f64,
+    pub subdivisions: u32,
+    pub tile_width_ratio: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CalendarConfig {
+    pub days_per_month: u8,
+    pub months_per_year: u8,
+    pub start_year: u16,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AppConfig {
+    pub hexasphere: HexasphereConfig,
+    pub calendar: CalendarConfig,
+    pub seed: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CalendarState {
+    pub date: CalendarDate,
+    pub is_paused: bool,
+    pub current_speed: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TickEvent {
+    pub births: u32,
+    pub deaths: u32,
+    pub marriages: u32,
+    pub pregnancies: u32,
+    pub dissolutions: u32,
+    pub population: u32,
+    pub year: i32,
+    pub month: u8,
+    pub day: u32,
+    /// Ticks actually run (fewer than requested if cancelled)
+    pub ticks: u32,
+    /// Per-tile births/deaths, only populated while tile tracking is on
+    pub tiles: Vec<TileTickData>,
+    /// Newborns' person ids, at most `MAX_TICK_IDS`
+    pub birth_ids: Vec<i64>,
+    /// Person ids of the dead, at most `MAX_TICK_IDS`
+    pub death_ids: Vec<i64>,
+    /// Whether either id list was cut short (the counts above stay exact)
+    pub ids_truncated: bool,
+    /// Deaths split by cause, e.g. {"natural": 3, "childbirth": 1}
+    pub deaths_by_cause: HashMap<DeathCause, u32>,
+}
+
+/// Emitted as "tick-progress" during a long `tick` call
+#[derive(Serialize, Clone)]
+pub struct TickProgressEvent {
+    pub ticks: u32,
+    pub total: u32,
+    pub year: i32,
+    pub month: u8,
+    pub day: u32,
+}
+
+/// Most person ids a `TickEvent` lists per kind, to keep events small
+pub const MAX_TICK_IDS: usize = 256;
+
+/// Up to `MAX_TICK_IDS` of `ids`, and whether any were left out
+pub fn capped_ids(ids: &[u64]) -> (Vec<i64>, bool) {
+    let kept = ids.iter().take(MAX_TICK_IDS).map(|id| *id as i64).collect();
+    (kept, ids.len() > MAX_TICK_IDS)
+}
+
+/// Narrow a tile id from the frontend to the simulation's `TileId`. Tile ids are
+/// stored as u16, so a map can have at most 65,536 tiles (ids 0 to 65535); larger
+/// ids are rejected rather than truncated onto some other tile.
+pub fn checked_tile_id(tile_id: u32) -> Result<u16, String> {
+    u16::try_from(tile_id).map_err(|_| format!("Tile id {} exceeds the maximum of {}", tile_id, u16::MAX))
+}
+
+/// Narrow a year from the frontend to the calendar's u16 years, rejecting negative
+/// or too-large years rather than wrapping them onto some other year
+pub fn checked_year(year: i32) -> Result<u16, String> {
+    u16::try_from(year).map_err(|_| format!("Year {} is outside 0..={}", year, u16::MAX))
+}
+
+#[derive(Serialize, Clone)]
+pub struct TileTickData {
+    pub tile_id: u32,
+    pub births: u32,
+    pub deaths: u32,
+}
+
+/// Merge per-tile birth/death counts into a list sorted by tile id
+pub fn tile_tick_data(births_by_tile: &HashMap<u16, u32>, deaths_by_tile: &HashMap<u16, u32>) -> Vec<TileTickData> {
+    let mut tiles: Vec<u16> = births_by_tile.keys().chain(deaths_by_tile.keys()).copied().collect();
+    tiles.sort_unstable();
+    tiles.dedup();
+    tiles
+        .into_iter()
+        .map(|tile| TileTickData {
+            tile_id: tile as u32,
+            births: births_by_tile.get(&tile).copied().unwrap_or(0),
+            deaths: deaths_by_tile.get(&tile).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[derive(Serialize, Clone)]
+pub struct SpeedMode {
+    pub key: String,
+    pub name: String,
+    pub interval_ms: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PeoplePage {
+    pub people: Vec<PersonData>,
+    /// Everyone living, across all pages
+    pub total: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PersonData {
+    pub id: i64,
+    pub first_name: String,
+    pub last_name: String,
+    pub tile_id: i32,
+    pub sex: bool,
+    pub birth_year: i32,
+    pub birth_month: i32,
+    pub birth_day: i32,
+    pub age_years: i32,
+    pub is_partnered: bool,
+    pub is_pregnant: bool,
+    pub partner_id: Option<i64>,
+    pub mother_id: Option<i64>,
+    pub father_id: Option<i64>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct EventData {
+    pub event_type: String,
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub person_id: Option<i64>,
+    pub tile_id: Option<u32>,
+    /// Set for "custom" events
+    pub label: Option<String>,
+    /// Set for "death" events
+    pub cause: Option<DeathCause>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct YearPopulationData {
+    pub year: i32,
+    pub population: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TilePopulationData {
+    pub tile_id: u32,
+    pub count: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TileSexData {
+    pub tile_id: u32,
+    pub males: u32,
+    pub females: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TilePressureData {
+    pub tile_id: u32,
+    pub pressure: f64,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct TileAdjacency {
+    pub tile_id: u32,
+    pub neighbors: Vec<u32>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TileWorkforceData {
+    pub tile_id: u32,
+    pub children: u32,
+    pub farmers: u32,
+    pub laborers: u32,
+    pub elders: u32,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ParityData {
+    pub children_born: u32,
+    pub women: u32,
+}
+
+/// Singles of marriageable age, by person id
+#[derive(Serialize, Clone)]
+pub struct EligibleSinglesData {
+    pub men: Vec<i64>,
+    pub women: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct WorldConfig {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_subdivisions")]
+    pub subdivisions: u32,
+    #[serde(default = "default_land_water_ratio")]
+    pub land_water_ratio: f64,
+    #[serde(default = "default_roughness")]
+    pub roughness: f64,
+    #[serde(default = "default_precipitation")]
+    pub precipitation: f64,
+}
+
+fn default_subdivisions() -> u32 { 12 }
+fn default_land_water_ratio() -> f64 { 35.0 }
+fn default_roughness() -> f64 { 25.0 }
+fn default_precipitation() -> f64 { 75.0 }
+
+#[derive(Serialize, Clone)]
+pub struct SaveResult {
+    pub population: u32,
+    pub file_bytes: i64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct LoadResult {
+    pub population: u32,
+    pub partners: u32,
+    pub calendar_year: i32,
+    pub seed: u32,
+    pub world_config: WorldConfig,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TileProperties {
+    pub id: u32,
+    pub terrain_type: String,
+    pub biome: Option<String>,
+    pub fertility: u32,
+    pub is_habitable: bool,
+    /// Raw elevation hash (0-1) that picks mountains and hills on land
+    pub elevation: f64,
+    /// Continent mask compared against the land threshold; below it is ocean
+    pub continent_mask: f64,
+}
+
+/// Default speeds available for the calendar
+pub fn default_speeds() -> Vec<SpeedMode> {
+    vec![
+        SpeedMode {
+            key: "1_day".into(),
+            name: "1 Day".into(),
+            interval_ms: 1000,
+        },
+        SpeedMode {
+            key: "1_month".into(),
+            name: "1 Month".into(),
+            interval_ms: 125,
+        },
+    ]
+}
+
+pub fn speed_interval(speed: &str) -> u64 {
+    match speed {
+        "1_month" => 125,
+        _ => 1000, // default to 1_day
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_ids_past_u16_are_rejected() {
+        assert_eq!(checked_tile_id(4464), Ok(4464));
+        assert_eq!(checked_tile_id(u16::MAX as u32), Ok(u16::MAX));
+        let err = checked_tile_id(70_000).unwrap_err();
+        assert!(err.contains("70000"), "{}", err);
+    }
+
+    #[test]
+    fn test_years_outside_u16_are_rejected() {
+        assert_eq!(checked_year(0), Ok(0));
+        assert_eq!(checked_year(65_535), Ok(u16::MAX));
+        assert!(checked_year(-1).is_err());
+        assert!(checked_year(65_536).is_err());
+    }
+}
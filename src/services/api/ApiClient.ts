@@ -1,333 +1,943 @@
-/**
- * ApiClient - Centralized Tauri IPC service
- *
- * Replaces the old HTTP fetch-based API client with Tauri invoke() calls.
- * All methods call Rust #[tauri::command] functions via IPC.
- */
-
-import { invoke } from '@tauri-apps/api/core';
-
-// ==================== Types matching Rust structs (snake_case) ====================
-
-/** App config from Rust get_config command */
-export interface AppConfig {
-    hexasphere: HexasphereConfig;
-    calendar: CalendarConfig;
-    seed: number;
-}
-
-/** Hexasphere configuration */
-export interface HexasphereConfig {
-    radius: number;
-    subdivisions: number;
-    tile_width_ratio: number;
-}
-
-/** Calendar configuration */
-export interface CalendarConfig {
-    days_per_month: number;
-    months_per_year: number;
-    start_year: number;
-}
-
-/** Calendar date */
-export interface CalendarDate {
-    year: number;
-    month: number;
-    day: number;
-}
-
-/** Calendar state from Rust */
-export interface CalendarState {
-    date: CalendarDate;
-    is_paused: boolean;
-    current_speed: string;
-}
-
-/** Speed mode configuration */
-export interface SpeedMode {
-    key: string;
-    name: string;
-    interval_ms: number;
-}
-
-/** Tile properties from Rust calculate_tile_properties */
-export interface TileProperties {
-    id: number;
-    terrain_type: string;
-    biome: string | null;
-    fertility: number;
-    is_habitable: boolean;
-}
-
-/** Tile center for calculate_tile_properties input */
-export interface TileCenter {
-    id: number;
-    x: number;
-    y: number;
-    z: number;
-}
-
-/** Person data from Rust */
-export interface PersonData {
-    id: number;
-    first_name: string;
-    last_name: string;
-    tile_id: number;
-    sex: boolean;
-    birth_year: number;
-    birth_month: number;
-    birth_day: number;
-    age_years: number;
-    is_partnered: boolean;
-    is_pregnant: boolean;
-    partner_id: number | null;
-}
-
-/** Demographics from Rust */
-export interface Demographics {
-    population: number;
-    males: number;
-    females: number;
-    partnered: number;
-    single: number;
-    pregnant: number;
-    average_age: number;
-    age_brackets: [number, number, number, number, number, number, number];
-}
-
-/** Vital statistics from Rust */
-export interface VitalStatistics {
-    birth_rate: number;
-    death_rate: number;
-    marriage_rate: number;
-    natural_increase_rate: number;
-    total_births: number;
-    total_deaths: number;
-    total_marriages: number;
-    population: number;
-    years_covered: number;
-}
-
-/** Event data from Rust */
-export interface EventData {
-    event_type: string;
-    year: number;
-    month: number;
-    day: number;
-    person_id: number | null;
-}
-
-/** Tile population data */
-export interface TilePopulationData {
-    tile_id: number;
-    count: number;
-}
-
-/** World configuration persisted in save files */
-export interface WorldConfig {
-    name: string;
-    subdivisions: number;
-    land_water_ratio: number;
-    roughness: number;
-    precipitation: number;
-}
-
-/** Save result from Rust */
-export interface SaveResult {
-    population: number;
-    file_bytes: number;
-}
-
-/** Load result from Rust */
-export interface LoadResult {
-    population: number;
-    partners: number;
-    calendar_year: number;
-    seed: number;
-    world_config: WorldConfig;
-}
-
-/** Memory usage from Rust process */
-export interface MemoryUsage {
-    physical_mem: number;
-}
-
-/** Restart result from Rust */
-export interface RestartResult {
-    seed: number;
-    population: number;
-    tiles: number;
-    calendar: CalendarDate;
-}
-
-/** Tick event from Rust (also used as calendar-tick event payload) */
-export interface TickEvent {
-    births: number;
-    deaths: number;
-    marriages: number;
-    pregnancies: number;
-    dissolutions: number;
-    population: number;
-    year: number;
-    month: number;
-    day: number;
-}
-
-/**
- * ApiClient - Singleton Tauri IPC client
- */
-class ApiClient {
-    private static instance: ApiClient | null = null;
-
-    private constructor() {}
-
-    static getInstance(): ApiClient {
-        if (!ApiClient.instance) {
-            ApiClient.instance = new ApiClient();
-        }
-        return ApiClient.instance;
-    }
-
-    // ==================== CONFIG ====================
-
-    async getConfig(): Promise<AppConfig> {
-        return invoke<AppConfig>('get_config');
-    }
-
-    // ==================== TILES ====================
-
-    async calculateTileProperties(
-        tiles: TileCenter[],
-        landWaterRatio?: number,
-        roughness?: number,
-        precipitation?: number
-    ): Promise<TileProperties[]> {
-        return invoke<TileProperties[]>('calculate_tile_properties', {
-            tiles,
-            landWaterRatio,
-            roughness,
-            precipitation,
-        });
-    }
-
-    // ==================== CALENDAR ====================
-
-    async getCalendarState(): Promise<CalendarState> {
-        return invoke<CalendarState>('get_calendar_state');
-    }
-
-    async startCalendar(speed?: string): Promise<CalendarState> {
-        return invoke<CalendarState>('start_calendar', { speed });
-    }
-
-    async stopCalendar(): Promise<CalendarState> {
-        return invoke<CalendarState>('stop_calendar');
-    }
-
-    async getCalendarSpeeds(): Promise<SpeedMode[]> {
-        return invoke<SpeedMode[]>('get_calendar_speeds');
-    }
-
-    async setCalendarSpeed(speed: string): Promise<CalendarState> {
-        return invoke<CalendarState>('set_calendar_speed', { speed });
-    }
-
-    // ==================== POPULATION ====================
-
-    async getPopulation(): Promise<number> {
-        return invoke<number>('get_population');
-    }
-
-    async getDemographics(): Promise<Demographics> {
-        return invoke<Demographics>('get_demographics');
-    }
-
-    async getPopulationByTile(): Promise<TilePopulationData[]> {
-        return invoke<TilePopulationData[]>('get_population_by_tile');
-    }
-
-    async getTilePopulation(tileId: number): Promise<number> {
-        return invoke<number>('get_tile_population', { tileId });
-    }
-
-    // ==================== PEOPLE ====================
-
-    async getAllPeople(): Promise<PersonData[]> {
-        return invoke<PersonData[]>('get_all_people');
-    }
-
-    async getPerson(personId: number): Promise<PersonData | null> {
-        return invoke<PersonData | null>('get_person', { personId });
-    }
-
-    async getPeopleByTile(tileId: number): Promise<PersonData[]> {
-        return invoke<PersonData[]>('get_people_by_tile', { tileId });
-    }
-
-    // ==================== STATISTICS ====================
-
-    async getVitalStatistics(startYear: number, endYear: number): Promise<VitalStatistics> {
-        return invoke<VitalStatistics>('get_vital_statistics', { startYear, endYear });
-    }
-
-    async getCurrentYearStatistics(): Promise<VitalStatistics> {
-        return invoke<VitalStatistics>('get_current_year_statistics');
-    }
-
-    async getRecentStatistics(years?: number): Promise<VitalStatistics> {
-        return invoke<VitalStatistics>('get_recent_statistics', { years });
-    }
-
-    async getRecentEvents(count?: number): Promise<EventData[]> {
-        return invoke<EventData[]>('get_recent_events', { count });
-    }
-
-    async getEventCount(): Promise<number> {
-        return invoke<number>('get_event_count');
-    }
-
-    // ==================== WORLD ====================
-
-    async tick(count?: number): Promise<TickEvent> {
-        return invoke<TickEvent>('tick', { count });
-    }
-
-    async saveWorld(filePath: string, worldConfig?: WorldConfig): Promise<SaveResult> {
-        return invoke<SaveResult>('save_world', { filePath, worldConfig });
-    }
-
-    async loadWorld(filePath: string): Promise<LoadResult> {
-        return invoke<LoadResult>('load_world', { filePath });
-    }
-
-    async checkSaveExists(filePath: string): Promise<boolean> {
-        return invoke<boolean>('check_save_exists', { filePath });
-    }
-
-    async restartWorld(
-        habitableTileIds: number[],
-        newSeed?: number,
-        tilePercent?: number,
-        popMin?: number,
-        popMax?: number,
-    ): Promise<RestartResult> {
-        return invoke<RestartResult>('restart_world', {
-            habitableTileIds,
-            newSeed,
-            tilePercent,
-            popMin,
-            popMax,
-        });
-    }
-
-    // ==================== MEMORY ====================
-
-    async getMemoryUsage(): Promise<MemoryUsage> {
-        return invoke<MemoryUsage>('get_memory_usage');
-    }
-}
-
-// Export singleton getter for convenience
-export const getApiClient = (): ApiClient => ApiClient.getInstance();
-export default ApiClient;
+/**
+ * ApiClient - Centralized Tauri IPC service
+ *
+ * Replaces the old HTTP fetch-based API client with Tauri invoke() calls.
+ * All methods call Rust #[tauri::command] functions via IPC.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+// ==================== Types matching Rust structs (snake_case) ====================
+
+/** App config from Rust get_config command */
+export interface AppConfig {
+    hexasphere: HexasphereConfig;
+    calendar: CalendarConfig;
+    seed: number;
+}
+
+/** Hexasphere configuration */
+export interface HexasphereConfig {
+    radius: number;
+    subdivisions: number;
+    tile_width_ratio: number;
+}
+
+/** Calendar configuration */
+export interface CalendarConfig {
+    days_per_month: number;
+    months_per_year: number;
+    start_year: number;
+}
+
+/** Calendar date */
+export interface CalendarDate {
+    year: number;
+    month: number;
+    day: number;
+}
+
+/** Calendar state from Rust */
+export interface CalendarState {
+    date: CalendarDate;
+    is_paused: boolean;
+    current_speed: string;
+}
+
+/** Speed mode configuration */
+export interface SpeedMode {
+    key: string;
+    name: string;
+    interval_ms: number;
+}
+
+/** Tile properties from Rust calculate_tile_properties */
+export interface TileProperties {
+    id: number;
+    terrain_type: string;
+    biome: string | null;
+    fertility: number;
+    is_habitable: boolean;
+    /** Raw elevation hash (0-1) that picks mountains and hills on land */
+    elevation: number;
+    /** Continent mask compared against the land threshold; below it is ocean */
+    continent_mask: number;
+}
+
+/** Optional terrain threshold overrides; unset fields derive from landWaterRatio/roughness */
+export interface TerrainConfig {
+    /** Continent mask below this is ocean (roughly -2.5 to 2.5; higher = more ocean) */
+    land_threshold?: number;
+    /** Land with elevation (0-1) above this is mountains */
+    mountain_elev?: number;
+    /** Land with elevation above this (but not mountains) is hills */
+    hills_elev?: number;
+}
+
+/** Tile properties cache counters */
+export interface TileCacheStats {
+    entries: number;
+    hits: number;
+}
+
+/** Tile center for calculate_tile_properties input */
+export interface TileCenter {
+    id: number;
+    x: number;
+    y: number;
+    z: number;
+}
+
+/** Person data from Rust */
+export interface PersonData {
+    id: number;
+    first_name: string;
+    last_name: string;
+    tile_id: number;
+    sex: boolean;
+    birth_year: number;
+    birth_month: number;
+    birth_day: number;
+    age_years: number;
+    is_partnered: boolean;
+    is_pregnant: boolean;
+    partner_id: number | null;
+    mother_id: number | null;
+    father_id: number | null;
+}
+
+/** Demographics from Rust */
+export interface Demographics {
+    population: number;
+    males: number;
+    females: number;
+    partnered: number;
+    single: number;
+    pregnant: number;
+    fertile_women: number;
+    average_age: number;
+    age_brackets: [number, number, number, number, number, number, number];
+}
+
+/** Vital statistics from Rust */
+export interface VitalStatistics {
+    birth_rate: number;
+    death_rate: number;
+    marriage_rate: number;
+    natural_increase_rate: number;
+    total_births: number;
+    total_deaths: number;
+    total_marriages: number;
+    population: number;
+    years_covered: number;
+}
+
+/** Event data from Rust */
+export interface EventData {
+    event_type: string;
+    year: number;
+    month: number;
+    day: number;
+    person_id: number | null;
+    tile_id: number | null;
+    /** Set for "custom" events */
+    label: string | null;
+    /** Set for "death" events */
+    cause: DeathCause | null;
+}
+
+/** What someone died of */
+export type DeathCause = 'natural' | 'childbirth';
+
+/** Tile population data */
+export interface TilePopulationData {
+    tile_id: number;
+    count: number;
+}
+
+/** World configuration persisted in save files */
+export interface WorldConfig {
+    name: string;
+    subdivisions: number;
+    land_water_ratio: number;
+    roughness: number;
+    precipitation: number;
+}
+
+/** Save result from Rust */
+export interface SaveResult {
+    population: number;
+    file_bytes: number;
+}
+
+/** Load result from Rust */
+export interface LoadResult {
+    population: number;
+    partners: number;
+    calendar_year: number;
+    seed: number;
+    world_config: WorldConfig;
+}
+
+/** Memory usage from Rust process */
+export interface MemoryUsage {
+    physical_mem: number;
+}
+
+/** Restart result from Rust */
+export interface RestartResult {
+    seed: number;
+    population: number;
+    tiles: number;
+    calendar: CalendarDate;
+}
+
+/** Tick event from Rust (also used as calendar-tick event payload) */
+export interface TickEvent {
+    births: number;
+    deaths: number;
+    marriages: number;
+    pregnancies: number;
+    dissolutions: number;
+    population: number;
+    year: number;
+    month: number;
+    day: number;
+    birth_ids: number[];
+    death_ids: number[];
+    /** Whether either id list was capped */
+    ids_truncated: boolean;
+    /** Deaths split by cause */
+    deaths_by_cause: Partial<Record<DeathCause, number>>;
+}
+
+/** Payload of "tick-progress" events during a long tick call */
+export interface TickProgressEvent {
+    ticks: number;
+    total: number;
+    year: number;
+    month: number;
+    day: number;
+}
+
+/** Fertility and conception parameters */
+export interface FertilityConfig {
+    min_birth_interval_months: number;
+    stillbirth_chance: number;
+    maternal_mortality: number;
+    newlywed_boost: number;
+    newlywed_months: number;
+    post_marriage_delay_months: number;
+    min_motherhood_age: number;
+    fertility_decline_start: number;
+    fertility_decline_per_year: number;
+    children_penalty: number;
+    children_factor_floor: number;
+}
+
+/** Which partner moves after a cross-tile marriage */
+export type MarriageRelocation = 'WifeMoves' | 'HusbandMoves';
+
+/** Who may marry whom */
+export interface MarriageConfig {
+    min_age_male: number;
+    min_age_female: number;
+    max_age_gap: number;
+    prefer_older_male: boolean;
+    search_radius: number;
+    relocation: MarriageRelocation;
+    forbid_half_siblings: boolean;
+    forbid_first_cousins: boolean;
+}
+
+/** Annual mortality by age */
+export interface MortalityTable {
+    neonatal_rate: number;
+    /** [starting age in years, annual probability], ascending by age */
+    brackets: [number, number][];
+}
+
+/** Mortality tables, optionally split by sex */
+export interface MortalityConfig {
+    /** Used for any sex without its own table */
+    shared: MortalityTable;
+    male: MortalityTable | null;
+    female: MortalityTable | null;
+}
+
+/** Young adults leaving the family home */
+export interface HomeLeavingConfig {
+    age: number;
+    fraction: number;
+    adjacent_only: boolean;
+}
+
+/** Every simulation tunable, from get_sim_config */
+export interface SimConfig {
+    fertility: FertilityConfig;
+    marriage: MarriageConfig;
+    mortality: MortalityConfig;
+    home_leaving: HomeLeavingConfig;
+    smooth_mortality: boolean;
+    max_population: number | null;
+}
+
+/** Page of living people from get_people_page */
+export interface PeoplePage {
+    people: PersonData[];
+    /** Everyone living, across all pages */
+    total: number;
+}
+
+/** Ordering for get_people_page; ties fall back to person id */
+export type PersonSort = 'person_id' | 'age' | 'tile' | 'name';
+
+/** Why a woman is or isn't conceiving */
+export interface FertilityStatus {
+    has_partner: boolean;
+    in_age_window: boolean;
+    interval_ok: boolean;
+    is_pregnant: boolean;
+    effective_rate: number;
+}
+
+/** Edit to one person; omitted fields are left unchanged */
+export interface PersonPatch {
+    person_id: number;
+    first_name?: string;
+    last_name?: string;
+    tile_id?: number;
+}
+
+/** Outcome of apply_person_patches */
+export interface PatchResult {
+    applied: number;
+    /** Ids with no living person, in patch order */
+    unknown_ids: number[];
+}
+
+/** Demographics with caller-chosen age brackets */
+export interface CustomDemographics {
+    population: number;
+    males: number;
+    females: number;
+    partnered: number;
+    single: number;
+    pregnant: number;
+    fertile_women: number;
+    average_age: number;
+    bounds: number[];
+    /** bounds.length + 1 entries, the last open-ended */
+    age_brackets: number[];
+}
+
+/** Males and females on one tile */
+export interface TileSexData {
+    tile_id: number;
+    males: number;
+    females: number;
+}
+
+/** Neighbors of one tile, for set_tile_adjacency */
+export interface TileAdjacency {
+    tile_id: number;
+    neighbors: number[];
+}
+
+/** Population pressure on one tile */
+export interface TilePressureData {
+    tile_id: number;
+    pressure: number;
+}
+
+/** Occupations on one tile */
+export interface TileWorkforceData {
+    tile_id: number;
+    children: number;
+    farmers: number;
+    laborers: number;
+    elders: number;
+}
+
+/** Number of women who have borne a given number of children */
+export interface ParityData {
+    children_born: number;
+    women: number;
+}
+
+/** Singles of marriageable age, by person id */
+export interface EligibleSinglesData {
+    men: number[];
+    women: number[];
+}
+
+/** Age distribution for seeded founders */
+export type AgeProfile =
+    | 'Standard'
+    | { Uniform: { min: number; max: number } }
+    | { Weighted: [number, number][] }
+    | { Exponential: { mean: number; max: number } };
+
+/** Outcome of seed_relationships */
+export interface SeededRelationships {
+    couples: number;
+    children_placed: number;
+}
+
+/** One designed founder for seed_from_records */
+export interface SeedPerson {
+    first_name: string;
+    last_name: string;
+    sex: 'Male' | 'Female';
+    age: number;
+    tile_id: number;
+    /** Index of this person's spouse within the same batch */
+    partner?: number | null;
+}
+
+/** Population at the end of one year */
+export interface YearPopulationData {
+    year: number;
+    population: number;
+}
+
+/** Natural increase over a recent window */
+export interface GrowthRate {
+    natural_increase: number;
+    annual_growth_percent: number;
+    /** Days actually covered (less than requested early in a run) */
+    window_days: number;
+}
+
+/** Totals for one year from tick_until_year */
+export interface YearSummary {
+    year: number;
+    births: number;
+    deaths: number;
+    marriages: number;
+    population: number;
+}
+
+/** RNG values each system drew during the last tick */
+export interface RngAudit {
+    death: number;
+    pregnancy: number;
+    delivery: number;
+    leaving_home: number;
+    matchmaking: number;
+    occupation: number;
+}
+
+/** What changed between two exported snapshots */
+export interface WorldDiff {
+    born: number[];
+    died: number[];
+    new_partnerships: [number, number][];
+    /** [person_id, from tile, to tile] */
+    relocations: [number, number, number][];
+}
+
+/** Fixed-layout header at the start of a save file */
+export interface SaveHeader {
+    version: number;
+    codec: number;
+    population: number;
+    year: number;
+    month: number;
+    day: number;
+    seed: number;
+    /** Unix seconds */
+    saved_at: number;
+}
+
+/** Whether a save can be loaded, from its header alone */
+export interface Compatibility {
+    save_version: number;
+    status: 'Current' | 'Older' | 'NewerThanSupported';
+    supported: boolean;
+    needs_migration: boolean;
+}
+
+/** A save file found by list_saves */
+export interface SaveSlot {
+    name: string;
+    path: string;
+    header: SaveHeader;
+}
+
+/** Approximate memory breakdown of the simulation */
+export interface MemoryReport {
+    entities: number;
+    archetypes: number;
+    approx_ecs_bytes: number;
+    event_log_bytes: number;
+}
+
+/** What compact_world cleaned up */
+export interface CompactResult {
+    despawned: number;
+    stale_index_entries: number;
+    dissolved: number;
+}
+
+/**
+ * ApiClient - Singleton Tauri IPC client
+ */
+class ApiClient {
+    private static instance: ApiClient | null = null;
+
+    // Commands returning a Rust Result reject with its error string
+
+    private constructor() {}
+
+    static getInstance(): ApiClient {
+        if (!ApiClient.instance) {
+            ApiClient.instance = new ApiClient();
+        }
+        return ApiClient.instance;
+    }
+
+    // ==================== CONFIG ====================
+
+    async getConfig(): Promise<AppConfig> {
+        return invoke<AppConfig>('get_config');
+    }
+
+    async getSimConfig(): Promise<SimConfig> {
+        return invoke<SimConfig>('get_sim_config');
+    }
+
+    async setSimConfig(config: SimConfig): Promise<void> {
+        return invoke<void>('set_sim_config', { config });
+    }
+
+    async getMarriageConfig(): Promise<MarriageConfig> {
+        return invoke<MarriageConfig>('get_marriage_config');
+    }
+
+    async setMarriageConfig(config: MarriageConfig): Promise<void> {
+        return invoke<void>('set_marriage_config', { config });
+    }
+
+    async setSmoothMortality(enabled: boolean): Promise<void> {
+        return invoke<void>('set_smooth_mortality', { enabled });
+    }
+
+    /** null removes the cap */
+    async setMaxPopulation(maxPopulation: number | null): Promise<void> {
+        return invoke<void>('set_max_population', { maxPopulation });
+    }
+
+    async getMortalityConfig(): Promise<MortalityConfig> {
+        return invoke<MortalityConfig>('get_mortality_config');
+    }
+
+    async setMortalityConfig(config: MortalityConfig): Promise<void> {
+        return invoke<void>('set_mortality_config', { config });
+    }
+
+    async getHomeLeavingConfig(): Promise<HomeLeavingConfig> {
+        return invoke<HomeLeavingConfig>('get_home_leaving_config');
+    }
+
+    async setHomeLeavingConfig(config: HomeLeavingConfig): Promise<void> {
+        return invoke<void>('set_home_leaving_config', { config });
+    }
+
+    // ==================== TILES ====================
+
+    async calculateTileProperties(
+        tiles: TileCenter[],
+        landWaterRatio?: number,
+        roughness?: number,
+        terrain?: TerrainConfig
+    ): Promise<TileProperties[]> {
+        return invoke<TileProperties[]>('calculate_tile_properties', {
+            tiles,
+            landWaterRatio,
+            roughness,
+            terrain,
+        });
+    }
+
+    async getHabitableTiles(
+        tiles: TileCenter[],
+        landWaterRatio?: number,
+        roughness?: number,
+        terrain?: TerrainConfig
+    ): Promise<number[]> {
+        return invoke<number[]>('get_habitable_tiles', {
+            tiles,
+            landWaterRatio,
+            roughness,
+            terrain,
+        });
+    }
+
+    async getTileCacheStats(): Promise<TileCacheStats> {
+        return invoke<TileCacheStats>('get_tile_cache_stats');
+    }
+
+    // ==================== CALENDAR ====================
+
+    async getCalendarState(): Promise<CalendarState> {
+        return invoke<CalendarState>('get_calendar_state');
+    }
+
+    async getCurrentDay(): Promise<number> {
+        return invoke<number>('get_current_day');
+    }
+
+    async getTicksElapsed(): Promise<number> {
+        return invoke<number>('get_ticks_elapsed');
+    }
+
+    async calendarFromDay(day: number): Promise<CalendarDate> {
+        return invoke<CalendarDate>('calendar_from_day', { day });
+    }
+
+    /** Rejects if already running, unless `replace` restarts the runner */
+    async startCalendar(speed?: string, replace?: boolean): Promise<CalendarState> {
+        return invoke<CalendarState>('start_calendar', { speed, replace });
+    }
+
+    async stopCalendar(): Promise<CalendarState> {
+        return invoke<CalendarState>('stop_calendar');
+    }
+
+    async getCalendarSpeeds(): Promise<SpeedMode[]> {
+        return invoke<SpeedMode[]>('get_calendar_speeds');
+    }
+
+    async setCalendarSpeed(speed: string): Promise<CalendarState> {
+        return invoke<CalendarState>('set_calendar_speed', { speed });
+    }
+
+    // ==================== POPULATION ====================
+
+    async getPopulation(): Promise<number> {
+        return invoke<number>('get_population');
+    }
+
+    async getDemographics(): Promise<Demographics> {
+        return invoke<Demographics>('get_demographics');
+    }
+
+    async getPopulationByTile(): Promise<TilePopulationData[]> {
+        return invoke<TilePopulationData[]>('get_population_by_tile');
+    }
+
+    async getTilePopulation(tileId: number): Promise<number> {
+        return invoke<number>('get_tile_population', { tileId });
+    }
+
+    async getTileDemographics(tileId: number): Promise<Demographics> {
+        return invoke<Demographics>('get_tile_demographics', { tileId });
+    }
+
+    /** `bounds` are the inclusive upper ages of each bracket but the last, ascending */
+    async getDemographicsCustom(bounds: number[]): Promise<CustomDemographics> {
+        return invoke<CustomDemographics>('get_demographics_custom', { bounds });
+    }
+
+    async getSexRatioByTile(): Promise<TileSexData[]> {
+        return invoke<TileSexData[]>('get_sex_ratio_by_tile');
+    }
+
+    async getTilePopulationDistribution(bins: number[]): Promise<number[]> {
+        return invoke<number[]>('get_tile_population_distribution', { bins });
+    }
+
+    async getLargestTiles(count: number): Promise<TilePopulationData[]> {
+        return invoke<TilePopulationData[]>('get_largest_tiles', { count });
+    }
+
+    async setTileAdjacency(adjacency: TileAdjacency[]): Promise<void> {
+        return invoke<void>('set_tile_adjacency', { adjacency });
+    }
+
+    async getTilePressures(): Promise<TilePressureData[]> {
+        return invoke<TilePressureData[]>('get_tile_pressures');
+    }
+
+    async getWorkforceByTile(): Promise<TileWorkforceData[]> {
+        return invoke<TileWorkforceData[]>('get_workforce_by_tile');
+    }
+
+    async getParityDistribution(): Promise<ParityData[]> {
+        return invoke<ParityData[]>('get_parity_distribution');
+    }
+
+    async getCompletedFamilySizes(): Promise<ParityData[]> {
+        return invoke<ParityData[]>('get_completed_family_sizes');
+    }
+
+    async getMeanCompletedFamilySize(): Promise<number | null> {
+        return invoke<number | null>('get_mean_completed_family_size');
+    }
+
+    async getEligibleSingles(tileId?: number): Promise<EligibleSinglesData> {
+        return invoke<EligibleSinglesData>('get_eligible_singles', { tileId });
+    }
+
+    async seedPopulationWithProfile(
+        tileId: number,
+        count: number,
+        profile?: AgeProfile,
+    ): Promise<number> {
+        return invoke<number>('seed_population_with_profile', { tileId, count, profile });
+    }
+
+    async seedRelationships(
+        marriageFraction: number,
+        placeChildren: boolean,
+    ): Promise<SeededRelationships> {
+        return invoke<SeededRelationships>('seed_relationships', { marriageFraction, placeChildren });
+    }
+
+    async seedFromRecords(records: SeedPerson[]): Promise<number[]> {
+        return invoke<number[]>('seed_from_records', { records });
+    }
+
+    async immigrate(
+        tileId: number,
+        count: number,
+        ageMin?: number,
+        ageMax?: number,
+    ): Promise<number[]> {
+        return invoke<number[]>('immigrate', { tileId, count, ageMin, ageMax });
+    }
+
+    async emigrate(tileId: number, count: number): Promise<number> {
+        return invoke<number>('emigrate', { tileId, count });
+    }
+
+    /** Maps old tile ids to new ones; resolves to the number of people moved */
+    async remapTiles(mapping: Record<number, number>): Promise<number> {
+        return invoke<number>('remap_tiles', { mapping });
+    }
+
+    // ==================== PEOPLE ====================
+
+    async getAllPeople(): Promise<PersonData[]> {
+        return invoke<PersonData[]>('get_all_people');
+    }
+
+    async getPerson(personId: number): Promise<PersonData | null> {
+        return invoke<PersonData | null>('get_person', { personId });
+    }
+
+    async getPeopleByTile(tileId: number): Promise<PersonData[]> {
+        return invoke<PersonData[]>('get_people_by_tile', { tileId });
+    }
+
+    async getPeoplePage(offset: number, limit: number, sortBy?: PersonSort): Promise<PeoplePage> {
+        return invoke<PeoplePage>('get_people_page', { offset, limit, sortBy });
+    }
+
+    async getPeopleByIds(ids: number[]): Promise<PersonData[]> {
+        return invoke<PersonData[]>('get_people_by_ids', { ids });
+    }
+
+    async getDescendantCount(personId: number): Promise<number> {
+        return invoke<number>('get_descendant_count', { personId });
+    }
+
+    async getLivingLineage(personId: number): Promise<number[]> {
+        return invoke<number[]>('get_living_lineage', { personId });
+    }
+
+    async getFertilityStatus(personId: number): Promise<FertilityStatus | null> {
+        return invoke<FertilityStatus | null>('get_fertility_status', { personId });
+    }
+
+    async getGeneration(personId: number): Promise<number | null> {
+        return invoke<number | null>('get_generation', { personId });
+    }
+
+    async getMaxGeneration(): Promise<number> {
+        return invoke<number>('get_max_generation');
+    }
+
+    async getMarriageDuration(personId: number): Promise<number | null> {
+        return invoke<number | null>('get_marriage_duration', { personId });
+    }
+
+    async applyPersonPatches(patches: PersonPatch[]): Promise<PatchResult> {
+        return invoke<PatchResult>('apply_person_patches', { patches });
+    }
+
+    // ==================== STATISTICS ====================
+
+    async getVitalStatistics(startYear: number, endYear: number): Promise<VitalStatistics> {
+        return invoke<VitalStatistics>('get_vital_statistics', { startYear, endYear });
+    }
+
+    async getCurrentYearStatistics(): Promise<VitalStatistics> {
+        return invoke<VitalStatistics>('get_current_year_statistics');
+    }
+
+    async getRecentStatistics(years?: number): Promise<VitalStatistics> {
+        return invoke<VitalStatistics>('get_recent_statistics', { years });
+    }
+
+    async getRecentEvents(count?: number): Promise<EventData[]> {
+        return invoke<EventData[]>('get_recent_events', { count });
+    }
+
+    async getEventCount(): Promise<number> {
+        return invoke<number>('get_event_count');
+    }
+
+    async getEventsOnTile(tileId: number, count?: number): Promise<EventData[]> {
+        return invoke<EventData[]>('get_events_on_tile', { tileId, count });
+    }
+
+    async logCustomEvent(label: string, personId?: number): Promise<void> {
+        return invoke<void>('log_custom_event', { label, personId });
+    }
+
+    async clearEventLog(): Promise<void> {
+        return invoke<void>('clear_event_log');
+    }
+
+    /** Keep only the last `years` of events; omit to keep everything */
+    async setEventRetention(years?: number): Promise<void> {
+        return invoke<void>('set_event_retention', { years });
+    }
+
+    async pruneEventsBefore(year: number): Promise<number> {
+        return invoke<number>('prune_events_before', { year });
+    }
+
+    async getPopulationSeries(startYear: number, endYear: number): Promise<YearPopulationData[]> {
+        return invoke<YearPopulationData[]>('get_population_series', { startYear, endYear });
+    }
+
+    async getGrowthRate(windowDays?: number): Promise<GrowthRate> {
+        return invoke<GrowthRate>('get_growth_rate', { windowDays });
+    }
+
+    // ==================== WORLD ====================
+
+    /** With `progressEvery`, "tick-progress" events report each batch of that many ticks */
+    async tick(count?: number, progressEvery?: number): Promise<TickEvent> {
+        return invoke<TickEvent>('tick', { count, progressEvery });
+    }
+
+    /** What the next tick would report, computed on a scratch copy (indicative only) */
+    async previewTick(): Promise<TickEvent> {
+        return invoke<TickEvent>('preview_tick');
+    }
+
+    async tickUntilYear(targetYear: number): Promise<YearSummary[]> {
+        return invoke<YearSummary[]>('tick_until_year', { targetYear });
+    }
+
+    /** Stops a running tick or tickUntilYear call early */
+    async requestCancel(): Promise<void> {
+        return invoke<void>('request_cancel');
+    }
+
+    async setTrackTileEvents(enabled: boolean): Promise<void> {
+        return invoke<void>('set_track_tile_events', { enabled });
+    }
+
+    async setRngAudit(enabled: boolean): Promise<void> {
+        return invoke<void>('set_rng_audit', { enabled });
+    }
+
+    async getLastRngAudit(): Promise<RngAudit | null> {
+        return invoke<RngAudit | null>('get_last_rng_audit');
+    }
+
+    async exportWorld(pretty?: boolean): Promise<string> {
+        return invoke<string>('export_world', { pretty });
+    }
+
+    async exportRegion(tiles: number[]): Promise<string> {
+        return invoke<string>('export_region', { tiles });
+    }
+
+    /** Compares two exportWorld snapshots, `jsonA` the older */
+    async diffSaves(jsonA: string, jsonB: string): Promise<WorldDiff> {
+        return invoke<WorldDiff>('diff_saves', { jsonA, jsonB });
+    }
+
+    async saveWorld(
+        filePath: string,
+        worldConfig?: WorldConfig,
+        format?: 'monolithic' | 'records',
+    ): Promise<SaveResult> {
+        return invoke<SaveResult>('save_world', { filePath, worldConfig, format });
+    }
+
+    async loadWorld(filePath: string, strict?: boolean): Promise<LoadResult> {
+        return invoke<LoadResult>('load_world', { filePath, strict });
+    }
+
+    async checkSaveExists(filePath: string): Promise<boolean> {
+        return invoke<boolean>('check_save_exists', { filePath });
+    }
+
+    async readSaveHeader(filePath: string): Promise<SaveHeader> {
+        return invoke<SaveHeader>('read_save_header', { filePath });
+    }
+
+    async getSaveCompatibility(filePath: string): Promise<Compatibility> {
+        return invoke<Compatibility>('get_save_compatibility', { filePath });
+    }
+
+    async listSaves(dir: string): Promise<SaveSlot[]> {
+        return invoke<SaveSlot[]>('list_saves', { dir });
+    }
+
+    async restartWorld(
+        habitableTileIds: number[],
+        newSeed?: number,
+        tilePercent?: number,
+        popMin?: number,
+        popMax?: number,
+        startYear?: number,
+    ): Promise<RestartResult> {
+        return invoke<RestartResult>('restart_world', {
+            habitableTileIds,
+            newSeed,
+            tilePercent,
+            popMin,
+            popMax,
+            startYear,
+        });
+    }
+
+    /** Rejects if exporting and re-importing the world changes any aggregate */
+    async verifyRoundtrip(): Promise<void> {
+        return invoke<void>('verify_roundtrip');
+    }
+
+    async reseed(seed: number): Promise<void> {
+        return invoke<void>('reseed', { seed });
+    }
+
+    // ==================== MEMORY ====================
+
+    async getMemoryUsage(): Promise<MemoryUsage> {
+        return invoke<MemoryUsage>('get_memory_usage');
+    }
+
+    async getMemoryReport(): Promise<MemoryReport> {
+        return invoke<MemoryReport>('get_memory_report');
+    }
+
+    async compactWorld(): Promise<CompactResult> {
+        return invoke<CompactResult>('compact_world');
+    }
+
+    async exitApp(): Promise<void> {
+        return invoke<void>('exit_app');
+    }
+}
+
+// Export singleton getter for convenience
+export const getApiClient = (): ApiClient => ApiClient.getInstance();
+export default ApiClient;